@@ -1,8 +1,7 @@
 #[cfg(feature = "commit_4")]
-use packed::{Id, Packed, PackedEdge};
+use automata::ts::packed::{Id, Packed};
 
 fn runs() {
-    #[cfg(not(feature = "commit_4"))]
     let ts = DTS::builder()
         .default_color(Void)
         .with_transitions([
@@ -18,14 +17,7 @@ fn runs() {
         .into_dts_with_initial(0);
 
     #[cfg(feature = "commit_4")]
-    let ts = Packed::new(
-        CharAlphabet::of_size(2),
-        vec![Some(Void), Some(Void), Some(Void), Some(Void)],
-        [].into_iter()
-            .map(|(q, a, p)| PackedEdge::new(q, p, a, Void))
-            .collect(),
-    )
-    .with_initial(Id(0));
+    let ts = Packed::from(&ts).with_initial(Id(0));
 
     let words = vec![
         upw!("abba"),
@@ -1,6 +1,6 @@
 use automata::TransitionSystem;
 use automata::hoa::WriteHoa;
-use tracing::{debug, info, trace};
+use tracing::{debug, error, info, trace};
 use tracing_subscriber::{filter, prelude::*};
 
 use automata::automaton::DeterministicOmegaAutomaton;
@@ -63,6 +63,13 @@ pub fn main() {
             debug!("converting input automata into DPAs");
 
             for aut in stream {
+                let aut = match aut {
+                    Ok(aut) => aut,
+                    Err(e) => {
+                        error!("skipping automaton: {e}");
+                        continue;
+                    }
+                };
                 debug!("read deterministic automaton with {} states", aut.size());
 
                 let start = std::time::Instant::now();
@@ -19,9 +19,9 @@ use automata::{
 };
 use automata_learning::passive::{
     OmegaSample,
-    sprout::{SproutError, sprout},
+    sprout::{SproutConfig, SproutError, sprout_with_config},
 };
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 fn main() {
     // initialize logger
@@ -56,12 +56,56 @@ fn main() {
     }
     if args.contains(&"sprout".to_string()) {
         info!("Running sprout learner on all tasks");
-        run_sprout();
+        run_sprout(SproutLimits::from_args(&args));
     }
     info!("Done");
 }
 
-pub fn run_sprout() {
+/// Limits for each run of the sprout learner, which are read from the arguments
+/// `--timeout=<seconds>` and `--max-states=<n>`. Without a timeout, the default of
+/// [`SproutConfig`] is used.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SproutLimits {
+    timeout: Option<Duration>,
+    max_states: Option<usize>,
+}
+
+impl SproutLimits {
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |name: &str| {
+            args.iter()
+                .find_map(|arg| arg.strip_prefix(name)?.strip_prefix('='))
+                .map(|value| {
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("{name} expects a number, got {value}"))
+                })
+        };
+        Self {
+            timeout: value("--timeout").map(Duration::from_secs),
+            max_states: value("--max-states").map(|n| n as usize),
+        }
+    }
+
+    /// Builds the configuration for the given task, which logs the progress of the run.
+    pub fn config(&self, task: usize) -> SproutConfig<'static> {
+        let mut config = SproutConfig::default().with_progress(move |progress| {
+            debug!(
+                "task {task}: {} states, {} escaping words",
+                progress.states, progress.escaping
+            )
+        });
+        if let Some(timeout) = self.timeout {
+            config = config.with_timeout(timeout);
+        }
+        if let Some(max_states) = self.max_states {
+            config = config.with_max_states(max_states);
+        }
+        config
+    }
+}
+
+pub fn run_sprout(limits: SproutLimits) {
     // load task directories
     let mut task_dirs = vec![];
     let entries = fs::read_dir("data/tasks").expect("No learning tasks available");
@@ -95,7 +139,7 @@ pub fn run_sprout() {
                     "starting DBA learner for task {i} {:?}",
                     dir.to_string_lossy()
                 );
-                match sprout(sample, BuchiCondition) {
+                match sprout_with_config(sample, BuchiCondition, limits.config(i)) {
                     Ok(learned) => {
                         let elapsed = time.elapsed();
                         info!(
@@ -125,10 +169,14 @@ pub fn run_sprout() {
                         );
                         export_sprout_result(dir, &learned, elapsed);
                     }
-                    Err(SproutError::Timeout { aut: partial }) => {
+                    Err(
+                        SproutError::Timeout { aut: partial }
+                        | SproutError::TooManyStates { aut: partial, .. }
+                        | SproutError::Cancelled { aut: partial },
+                    ) => {
                         let elapsed = time.elapsed();
                         info!(
-                            "exceeded timeout on task {i} with partial ts of size {}: {:?}",
+                            "gave up on task {i} with partial ts of size {}: {:?}",
                             partial.size(),
                             dir.to_string_lossy()
                         );
@@ -141,7 +189,7 @@ pub fn run_sprout() {
                     "starting DPA learner for task {i} {:?}",
                     dir.to_string_lossy()
                 );
-                match sprout(sample, MinEvenParityCondition) {
+                match sprout_with_config(sample, MinEvenParityCondition, limits.config(i)) {
                     Ok(learned) => {
                         let elapsed = time.elapsed();
                         info!(
@@ -171,10 +219,14 @@ pub fn run_sprout() {
                         );
                         export_sprout_result(dir, &learned, elapsed);
                     }
-                    Err(SproutError::Timeout { aut: partial }) => {
+                    Err(
+                        SproutError::Timeout { aut: partial }
+                        | SproutError::TooManyStates { aut: partial, .. }
+                        | SproutError::Cancelled { aut: partial },
+                    ) => {
                         let elapsed = time.elapsed();
                         info!(
-                            "exceeded timeout on task {i} with partial ts of size {}: {:?}",
+                            "gave up on task {i} with partial ts of size {}: {:?}",
                             partial.size(),
                             dir.to_string_lossy()
                         );
@@ -626,3 +678,34 @@ pub fn export_sprout_result<Z, C>(
         start.elapsed().as_micros()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use automata_learning::passive::dba_rpni;
+
+    #[test]
+    fn dba_rpni_on_generated_task() {
+        let dba = generate_dba(2, 4, 0.95);
+        let (train, _) = generate_set(2, 8, 8, 50, 0);
+
+        // write and read the training set as it is stored in a task directory
+        let dir = env::temp_dir().join(format!("omega-learning-task-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        export_labelled_set(
+            dir.join("train.csv").to_string_lossy().into_owned(),
+            &label_set(&dba, &train),
+        );
+        let sample = load_sample(dir.clone());
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(sample.count_words(), train.len());
+
+        let learned = dba_rpni(&sample);
+        for w in sample.positive_words() {
+            assert!(learned.accepts(w), "{w:?} should be accepted");
+        }
+        for w in sample.negative_words() {
+            assert!(!learned.accepts(w), "{w:?} should be rejected");
+        }
+    }
+}
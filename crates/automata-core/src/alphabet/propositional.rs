@@ -53,6 +53,7 @@ macro_rules! impl_raw_symbol_repr {
 impl_raw_symbol_repr!(u8, u16, u32, u64, u128, usize);
 
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PropSymbol<RawTy: RawSymbolRepr = u32> {
     num_aps: u8,
     raw: RawTy,
@@ -134,6 +135,7 @@ impl<RawTy: RawSymbolRepr> Show for PropSymbol<RawTy> {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PropExpression<RawTy: RawSymbolRepr = u32> {
     num_aps: u8,
     bdd: Bdd,
@@ -179,6 +181,29 @@ impl<RawTy: RawSymbolRepr> PropExpression<RawTy> {
             _ty: PhantomData,
         }
     }
+    /// Renders `self` as a label in the HOA format, where atomic propositions are referred
+    /// to by their index and `t`/`f` denote the constants.
+    pub fn to_hoa_label(&self) -> String {
+        if self.bdd.is_true() {
+            return "t".to_string();
+        }
+        if self.bdd.is_false() {
+            return "f".to_string();
+        }
+        let dnf = self.bdd.to_optimized_dnf();
+        let clause = |clause: &BddPartialValuation| {
+            let mut vals = clause.to_values();
+            vals.sort();
+            vals.into_iter()
+                .map(|(v, b)| format!("{}{}", if b { "" } else { "!" }, v.to_index()))
+                .join("&")
+        };
+        if dnf.len() == 1 {
+            clause(&dnf[0])
+        } else {
+            dnf.iter().map(|c| format!("({})", clause(c))).join(" | ")
+        }
+    }
     pub fn chars_iter(&self) -> impl Iterator<Item = char> + '_ {
         PropExpressionSymbols {
             iter: self.bdd.sat_valuations(),
@@ -356,7 +381,6 @@ pub struct PropAlphabet<RawTy: RawSymbolRepr = u32> {
 impl<RawTy: RawSymbolRepr> PropAlphabet<RawTy> {
     pub fn new(aps: Vec<String>) -> Self {
         assert!(aps.len() < RawTy::max_aps());
-        assert!(!aps.is_empty());
 
         let universal = PropExpression::<RawTy>::universal(aps.len() as u8);
         Self {
@@ -425,6 +449,29 @@ impl<RawTy: RawSymbolRepr> PropAlphabet<RawTy> {
     }
 }
 
+/// Only the names of the atomic propositions are serialized, the cache of expressions is
+/// rebuilt on demand after deserialization.
+#[cfg(feature = "serde")]
+impl<RawTy: RawSymbolRepr> serde::Serialize for PropAlphabet<RawTy> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.aps.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, RawTy: RawSymbolRepr> serde::Deserialize<'de> for PropAlphabet<RawTy> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let aps = Vec::<String>::deserialize(deserializer)?;
+        if aps.is_empty() || aps.len() >= RawTy::max_aps() {
+            return Err(serde::de::Error::invalid_length(
+                aps.len(),
+                &"a positive number of atomic propositions that fits the raw representation",
+            ));
+        }
+        Ok(Self::new(aps))
+    }
+}
+
 impl<RawTy: RawSymbolRepr> Alphabet for PropAlphabet<RawTy> {
     type Symbol = PropSymbol<RawTy>;
 
@@ -477,6 +524,30 @@ mod tests {
         assert_eq!(ps.show(), "10011".to_string());
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        let alphabet = PropAlphabet::<u32>::from_apnames(["p", "q"]);
+        let json = serde_json::to_string(&alphabet).unwrap();
+        let back: PropAlphabet = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.apnames(), alphabet.apnames());
+        let bytes = bincode::serialize(&alphabet).unwrap();
+        let back: PropAlphabet = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back.universe().count(), 4);
+        assert!(serde_json::from_str::<PropAlphabet>("[]").is_err());
+
+        let pe = PropExpression::<u32>::new(2, "x_0 & !x_1");
+        let back: PropExpression =
+            serde_json::from_str(&serde_json::to_string(&pe).unwrap()).unwrap();
+        assert_eq!(back, pe);
+        let back: PropExpression = bincode::deserialize(&bincode::serialize(&pe).unwrap()).unwrap();
+        assert_eq!(back, pe);
+
+        let symbol = PropSymbol::<u32>::from_bools(vec![true, false]);
+        let back: PropSymbol = bincode::deserialize(&bincode::serialize(&symbol).unwrap()).unwrap();
+        assert_eq!(back, symbol);
+    }
+
     #[test]
     fn prop_expression() {
         let pe = PropExpression::<u32>::new(3, "x_0 | (x_1 & x_2)");
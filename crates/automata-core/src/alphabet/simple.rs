@@ -31,6 +31,7 @@ where
 /// Now an **expression** would also be just a single character, e.g. 'a'. Then such an expression is
 /// matched by a symbol if the expression equals the symbol.
 #[derive(Clone, Hash, PartialEq, Eq, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharAlphabet(pub(crate) Vec<char>);
 
 impl CharAlphabet {
@@ -241,6 +242,7 @@ impl<S: Symbol + Matcher<S> + Expression<S = S>, const N: usize> Alphabet for Fi
 /// appended to the end of a word or prepended to the beginning of a word. This is used to
 /// implement the [`Directional`] alphabet.
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InvertibleChar(char, bool);
 
 impl InvertibleChar {
@@ -303,6 +305,7 @@ impl Show for InvertibleChar {
 /// appended to the end of a word or prepended to the beginning of a word. This can be used to
 /// represent two-sided congruences.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Directional(Vec<InvertibleChar>);
 
 impl FromIterator<char> for Directional {
@@ -35,6 +35,7 @@ pub type Int = u8;
 /// representation, but we don't care about the colors on the edges. In that case, the state
 /// colors may be kept and the edge colors are dropped.
 #[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Void;
 
 impl std::fmt::Debug for Void {
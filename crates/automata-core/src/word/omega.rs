@@ -172,6 +172,7 @@ fn deduplicate<S: Eq>(input: Vec<S>) -> Vec<S> {
 /// repeated infinitely often. Note, that the loop cannot be empty.
 /// TODO: make non-empty word into a new type
 #[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodicOmegaWord<S> {
     representation: Vec<S>,
 }
@@ -289,6 +290,7 @@ impl<S: Symbol> OmegaWord for PeriodicOmegaWord<S> {
 /// verify whether an instance of `Self` is normalized through the [`ReducedOmegaWord::is_reduced()`]
 /// method.
 #[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReducedOmegaWord<S> {
     pub(super) word: Vec<S>,
     pub(super) loop_index: usize,
@@ -616,6 +618,36 @@ mod tests {
         assert_eq!(nupw.word, vec!['a', 'b']);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        use crate::word::PeriodicOmegaWord;
+
+        let reduced = ReducedOmegaWord::ultimately_periodic("ab", "bab");
+        let json = serde_json::to_string(&reduced).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ReducedOmegaWord<char>>(&json).unwrap(),
+            reduced
+        );
+        let bytes = bincode::serialize(&reduced).unwrap();
+        assert_eq!(
+            bincode::deserialize::<ReducedOmegaWord<char>>(&bytes).unwrap(),
+            reduced
+        );
+
+        let periodic = PeriodicOmegaWord::new("abab");
+        let json = serde_json::to_string(&periodic).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PeriodicOmegaWord<char>>(&json).unwrap(),
+            periodic
+        );
+        let bytes = bincode::serialize(&periodic).unwrap();
+        assert_eq!(
+            bincode::deserialize::<PeriodicOmegaWord<char>>(&bytes).unwrap(),
+            periodic
+        );
+    }
+
     #[test]
     fn deduplication() {
         let input = vec![1, 2, 3, 1, 2, 3];
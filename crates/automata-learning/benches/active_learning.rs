@@ -0,0 +1,60 @@
+use automata::automaton::MooreMachine;
+use automata::core::alphabet::CharAlphabet;
+use automata::random::generate_random_moore_with;
+use automata_learning::active::{KearnsVazirani, LStar, MooreOracle};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::{SeedableRng, rngs::StdRng};
+
+const SYMBOLS: usize = 3;
+const MAX_COLOR: usize = 4;
+
+/// Learns the same seeded random Moore machines with Kearns-Vazirani and LStar. Besides the
+/// running time, the number of membership queries each learner poses is reported, as that is
+/// usually the more relevant measure when queries are expensive.
+fn learn_moore(c: &mut Criterion) {
+    let mut group = c.benchmark_group("learn_moore");
+    group.sample_size(10);
+    let mut rng = StdRng::seed_from_u64(0);
+    for size in [10, 25, 50] {
+        let target = generate_random_moore_with(&mut rng, SYMBOLS, MAX_COLOR, size);
+
+        let mut learner = KearnsVazirani::new(
+            CharAlphabet::of_size(SYMBOLS),
+            MooreOracle::new(target.clone()),
+        );
+        let _: MooreMachine = learner.infer();
+        let mut lstar = LStar::new(
+            CharAlphabet::of_size(SYMBOLS),
+            MooreOracle::new(target.clone()),
+        );
+        let _: MooreMachine = lstar.infer();
+        println!(
+            "learn_moore/{size}: {} membership queries for KV, {} for LStar",
+            learner.membership_queries(),
+            lstar.statistics().membership_queries
+        );
+
+        group.bench_with_input(BenchmarkId::new("kv", size), &target, |b, target| {
+            b.iter(|| {
+                let _: MooreMachine = KearnsVazirani::new(
+                    CharAlphabet::of_size(SYMBOLS),
+                    MooreOracle::new(target.clone()),
+                )
+                .infer();
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("lstar", size), &target, |b, target| {
+            b.iter(|| {
+                let _: MooreMachine = LStar::new(
+                    CharAlphabet::of_size(SYMBOLS),
+                    MooreOracle::new(target.clone()),
+                )
+                .infer();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, learn_moore);
+criterion_main!(benches);
@@ -1,6 +1,12 @@
 mod lstar;
 pub use lstar::*;
 
+mod kv;
+pub use kv::*;
+
+mod conformance;
+pub use conformance::*;
+
 pub(crate) mod oracle;
 pub use oracle::*;
 
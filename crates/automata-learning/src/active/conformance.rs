@@ -0,0 +1,695 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use automata::core::alphabet::Alphabet;
+use automata::core::word::{Concat, FiniteWord};
+use automata::core::{Color, Show, math};
+use automata::ts::{Deterministic, SymbolOf};
+use automata::{Pointed, TransitionSystem};
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use tracing::trace;
+
+use super::{Counterexample, Hypothesis, oracle::Oracle};
+
+/// An oracle that can only answer membership queries, which it does by calling a function.
+/// As it has no means of checking equivalence, it accepts every hypothesis. It is meant to be
+/// wrapped in one of the conformance-testing oracles, such as [`WMethodOracle`] or
+/// [`RandomWordOracle`], which approximate equivalence queries through membership queries.
+#[derive(Clone)]
+pub struct MembershipOracle<A: Alphabet, O, F> {
+    alphabet: A,
+    membership: F,
+    _output: PhantomData<O>,
+}
+
+impl<A: Alphabet, O: Color, F: Fn(&[A::Symbol]) -> O> MembershipOracle<A, O, F> {
+    /// Creates a new instance, which answers membership queries by calling `membership`.
+    pub fn new(alphabet: A, membership: F) -> Self {
+        Self {
+            alphabet,
+            membership,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<A: Alphabet, O: Color, F: Fn(&[A::Symbol]) -> O> Oracle for MembershipOracle<A, O, F> {
+    type Alphabet = A;
+    type Output = O;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        &self.alphabet
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        (self.membership)(&word.collect_vec())
+    }
+
+    fn equivalence<H>(
+        &self,
+        _hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        Ok(())
+    }
+}
+
+/// An oracle that approximates equivalence queries with the W-method. If the hypothesis has
+/// `n` states, the test suite consists of the words `p x w`, where `p` reaches a state and
+/// then takes a transition, `x` is a word of length at most `extra_states` and `w` is taken
+/// from a characterization set, which separates all states of the hypothesis. If the target
+/// has at most `n + extra_states` states, the test suite finds a counterexample whenever the
+/// hypothesis is wrong.
+///
+/// Membership queries are answered by the wrapped oracle. Once all tests pass, the
+/// equivalence query is forwarded to the wrapped oracle, so conformance-testing oracles can be
+/// stacked on top of each other and on top of a [`MembershipOracle`].
+#[derive(Debug, Clone)]
+pub struct WMethodOracle<T> {
+    oracle: T,
+    extra_states: usize,
+}
+
+impl<T: Oracle> WMethodOracle<T> {
+    /// Creates a new instance, which wraps `oracle` and assumes that the target has at most
+    /// `extra_states` more states than the hypothesis.
+    pub fn new(oracle: T, extra_states: usize) -> Self {
+        Self {
+            oracle,
+            extra_states,
+        }
+    }
+
+    /// Returns a reference to the wrapped oracle.
+    pub fn inner(&self) -> &T {
+        &self.oracle
+    }
+}
+
+impl<T: Oracle> Oracle for WMethodOracle<T> {
+    type Alphabet = T::Alphabet;
+    type Output = T::Output;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.oracle.alphabet()
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        self.oracle.output(word)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let info = HypothesisInfo::new(hypothesis);
+        let suffixes = info.characterization_set();
+        let middles = info.middles(self.extra_states);
+        let tests = info
+            .transition_cover()
+            .cartesian_product(&middles)
+            .flat_map(|(prefix, middle)| {
+                suffixes
+                    .iter()
+                    .map(move |suffix| Concat(Concat(&prefix, middle), suffix).collect_vec())
+            });
+        run_tests(&self.oracle, hypothesis, tests)?;
+        self.oracle.equivalence(hypothesis)
+    }
+}
+
+/// An oracle that approximates equivalence queries with the Wp-method, which gives the same
+/// guarantees as the W-method of [`WMethodOracle`] with a smaller test suite. Only the words
+/// `q x`, where `q` reaches a state, are followed by the whole characterization set. For the
+/// remaining words `p x`, which take a transition from the state reached by `p`, it suffices
+/// to append the words that separate the state that is reached by `p x` from all others.
+///
+/// Like [`WMethodOracle`], membership queries are answered by the wrapped oracle, to which
+/// the equivalence query is forwarded once all tests pass.
+#[derive(Debug, Clone)]
+pub struct WpMethodOracle<T> {
+    oracle: T,
+    extra_states: usize,
+}
+
+impl<T: Oracle> WpMethodOracle<T> {
+    /// Creates a new instance, which wraps `oracle` and assumes that the target has at most
+    /// `extra_states` more states than the hypothesis.
+    pub fn new(oracle: T, extra_states: usize) -> Self {
+        Self {
+            oracle,
+            extra_states,
+        }
+    }
+
+    /// Returns a reference to the wrapped oracle.
+    pub fn inner(&self) -> &T {
+        &self.oracle
+    }
+}
+
+impl<T: Oracle> Oracle for WpMethodOracle<T> {
+    type Alphabet = T::Alphabet;
+    type Output = T::Output;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.oracle.alphabet()
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        self.oracle.output(word)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let info = HypothesisInfo::new(hypothesis);
+        let suffixes = info.characterization_set();
+        let middles = info.middles(self.extra_states);
+
+        let global = info
+            .access
+            .iter()
+            .cartesian_product(&middles)
+            .flat_map(|(prefix, middle)| {
+                suffixes
+                    .iter()
+                    .map(move |suffix| Concat(Concat(prefix, middle), suffix).collect_vec())
+            });
+        let local = info
+            .transition_cover()
+            .filter(|prefix| !info.access.contains(prefix))
+            .cartesian_product(&middles)
+            .flat_map(|(prefix, middle)| {
+                let word = Concat(&prefix, middle).collect_vec();
+                let state = info.index_of(hypothesis.reached_state_index(&word).unwrap());
+                info.identification_set(state)
+                    .into_iter()
+                    .map(move |suffix| Concat(&word, &suffix).collect_vec())
+            });
+        run_tests(&self.oracle, hypothesis, global.chain(local))?;
+        self.oracle.equivalence(hypothesis)
+    }
+}
+
+/// An oracle that approximates equivalence queries by testing the hypothesis on a number of
+/// words, which are drawn uniformly at random with a length in the given range. Membership
+/// queries are answered by the wrapped oracle, to which the equivalence query is forwarded if
+/// no counterexample is found.
+#[derive(Debug, Clone)]
+pub struct RandomWordOracle<T> {
+    oracle: T,
+    words: usize,
+    min_length: usize,
+    max_length: usize,
+    rng: RefCell<StdRng>,
+}
+
+impl<T: Oracle> RandomWordOracle<T> {
+    /// Creates a new instance that tests `words` many words with a length between
+    /// `min_length` and `max_length` (inclusive) for each equivalence query.
+    pub fn new(oracle: T, words: usize, min_length: usize, max_length: usize) -> Self {
+        assert!(min_length <= max_length, "length range must not be empty");
+        Self {
+            oracle,
+            words,
+            min_length,
+            max_length,
+            rng: RefCell::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// Seeds the random number generator, which makes the tests reproducible.
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.rng.replace(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Returns a reference to the wrapped oracle.
+    pub fn inner(&self) -> &T {
+        &self.oracle
+    }
+}
+
+impl<T: Oracle> Oracle for RandomWordOracle<T> {
+    type Alphabet = T::Alphabet;
+    type Output = T::Output;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.oracle.alphabet()
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        self.oracle.output(word)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let symbols = self.alphabet().universe().collect_vec();
+        let mut rng = self.rng.borrow_mut();
+        let tests = (0..self.words)
+            .map(|_| {
+                let length = rng.gen_range(self.min_length..=self.max_length);
+                (0..length)
+                    .map(|_| {
+                        *symbols
+                            .choose(&mut *rng)
+                            .expect("alphabet must not be empty")
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+        run_tests(&self.oracle, hypothesis, tests)?;
+        self.oracle.equivalence(hypothesis)
+    }
+}
+
+/// An oracle that approximates equivalence queries by a random walk, which picks a symbol
+/// uniformly at random in every step. After each step, the output on the word read so far is
+/// compared with the output of the hypothesis. With the given probability, the walk is
+/// restarted with the empty word before each step. Membership queries are answered by the
+/// wrapped oracle, to which the equivalence query is forwarded if no counterexample is found.
+#[derive(Debug, Clone)]
+pub struct RandomWalkOracle<T> {
+    oracle: T,
+    steps: usize,
+    restart_probability: f64,
+    rng: RefCell<StdRng>,
+}
+
+impl<T: Oracle> RandomWalkOracle<T> {
+    /// Creates a new instance that takes `steps` many steps for each equivalence query and
+    /// restarts with the given probability.
+    pub fn new(oracle: T, steps: usize, restart_probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&restart_probability),
+            "restart probability must be between 0 and 1"
+        );
+        Self {
+            oracle,
+            steps,
+            restart_probability,
+            rng: RefCell::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// Seeds the random number generator, which makes the tests reproducible.
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.rng.replace(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Returns a reference to the wrapped oracle.
+    pub fn inner(&self) -> &T {
+        &self.oracle
+    }
+}
+
+impl<T: Oracle> Oracle for RandomWalkOracle<T> {
+    type Alphabet = T::Alphabet;
+    type Output = T::Output;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.oracle.alphabet()
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        self.oracle.output(word)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let symbols = self.alphabet().universe().collect_vec();
+        let mut rng = self.rng.borrow_mut();
+        let mut word = vec![];
+        for _ in 0..self.steps {
+            if !word.is_empty() && rng.gen_bool(self.restart_probability) {
+                word.clear();
+            }
+            word.push(
+                *symbols
+                    .choose(&mut *rng)
+                    .expect("alphabet must not be empty"),
+            );
+            let expected = self.oracle.output(&word);
+            if hypothesis.output(&word) != expected {
+                trace!("random walk found counterexample {}", word.as_string());
+                return Err((word, expected));
+            }
+        }
+        self.oracle.equivalence(hypothesis)
+    }
+}
+
+/// Poses a membership query for each of the given words, skipping duplicates, and returns the
+/// first one on which the hypothesis disagrees.
+fn run_tests<T, H, I>(
+    oracle: &T,
+    hypothesis: &H,
+    tests: I,
+) -> Result<(), Counterexample<T::Alphabet, T::Output>>
+where
+    T: Oracle,
+    H: Hypothesis<Alphabet = T::Alphabet, Output = T::Output>,
+    I: IntoIterator<Item = Vec<<T::Alphabet as Alphabet>::Symbol>>,
+{
+    let mut tested = math::Set::default();
+    for word in tests {
+        if !tested.insert(word.clone()) {
+            continue;
+        }
+        let expected = oracle.output(&word);
+        if hypothesis.output(&word) != expected {
+            trace!(
+                "conformance test found counterexample {} after {} tests",
+                word.as_string(),
+                tested.len()
+            );
+            return Err((word, expected));
+        }
+    }
+    Ok(())
+}
+
+/// The parts of a hypothesis that the W- and Wp-method build their test suites from.
+struct HypothesisInfo<'a, H: Hypothesis> {
+    hypothesis: &'a H,
+    // the reachable states together with their minimal access words
+    states: Vec<H::StateIndex>,
+    access: Vec<Vec<SymbolOf<H>>>,
+    // for each pair of states `(p, q)` with `p < q`, a shortest word separating them
+    separating: math::Map<(usize, usize), Vec<SymbolOf<H>>>,
+    // the words on which the outputs of the hypothesis are determined
+    mandatory: Vec<Vec<SymbolOf<H>>>,
+}
+
+impl<'a, H: Hypothesis> HypothesisInfo<'a, H> {
+    fn new(hypothesis: &'a H) -> Self {
+        let (access, states): (Vec<_>, Vec<_>) = hypothesis
+            .minimal_representatives_iter()
+            .map(|rep| rep.decompose())
+            .unzip();
+        let mandatory = H::mandatory_experiments(hypothesis.alphabet())
+            .into_iter()
+            .map(|e| e.collect_vec())
+            .collect_vec();
+        let mut info = Self {
+            hypothesis,
+            states,
+            access,
+            separating: math::Map::default(),
+            mandatory,
+        };
+        info.separating = info.separating_words();
+        info
+    }
+
+    fn index_of(&self, state: H::StateIndex) -> usize {
+        self.states
+            .iter()
+            .position(|q| *q == state)
+            .expect("state must be reachable")
+    }
+
+    /// Computes a shortest separating word for every pair of states that can be separated,
+    /// by extending the separating words for the successors of the pair.
+    fn separating_words(&self) -> math::Map<(usize, usize), Vec<SymbolOf<H>>> {
+        let n = self.states.len();
+        let symbols = self.hypothesis.alphabet().universe().collect_vec();
+        let successors = self
+            .states
+            .iter()
+            .map(|q| {
+                symbols
+                    .iter()
+                    .map(|a| {
+                        self.index_of(
+                            self.hypothesis
+                                .successor_index(*q, *a)
+                                .expect("hypothesis must be complete"),
+                        )
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let mut separating = math::Map::default();
+        for (p, q) in (0..n).tuple_combinations() {
+            if let Some(e) = self.mandatory.iter().find(|e| {
+                self.hypothesis.output_from(*e, self.states[p])
+                    != self.hypothesis.output_from(*e, self.states[q])
+            }) {
+                separating.insert((p, q), e.clone());
+            }
+        }
+
+        loop {
+            let mut found = vec![];
+            for (p, q) in (0..n).tuple_combinations() {
+                if separating.contains_key(&(p, q)) {
+                    continue;
+                }
+                let extension = symbols.iter().enumerate().find_map(|(i, a)| {
+                    let (p, q) = (successors[p][i], successors[q][i]);
+                    let w = separating.get(&(p.min(q), p.max(q)))?;
+                    Some(Concat([*a], w).collect_vec())
+                });
+                if let Some(w) = extension {
+                    found.push(((p, q), w));
+                }
+            }
+            if found.is_empty() {
+                return separating;
+            }
+            separating.extend(found);
+        }
+    }
+
+    /// The mandatory experiments together with all separating words.
+    fn characterization_set(&self) -> Vec<Vec<SymbolOf<H>>> {
+        self.mandatory
+            .iter()
+            .chain(self.separating.values())
+            .unique()
+            .cloned()
+            .collect()
+    }
+
+    /// The mandatory experiments together with the words that separate `state` from the
+    /// other states.
+    fn identification_set(&self, state: usize) -> Vec<Vec<SymbolOf<H>>> {
+        self.mandatory
+            .iter()
+            .chain(
+                self.separating
+                    .iter()
+                    .filter(|((p, q), _)| *p == state || *q == state)
+                    .map(|(_, w)| w),
+            )
+            .unique()
+            .cloned()
+            .collect()
+    }
+
+    /// The access words of all states and their extensions by one symbol.
+    fn transition_cover(&self) -> impl Iterator<Item = Vec<SymbolOf<H>>> + '_ {
+        self.access.iter().cloned().chain(
+            self.access
+                .iter()
+                .cartesian_product(self.hypothesis.alphabet().universe().collect_vec())
+                .map(|(u, a)| Concat(u, [a]).collect_vec()),
+        )
+    }
+
+    /// All words of length at most `length`, ordered by length.
+    fn middles(&self, length: usize) -> Vec<Vec<SymbolOf<H>>> {
+        let symbols = self.hypothesis.alphabet().universe().collect_vec();
+        let mut out: Vec<Vec<SymbolOf<H>>> = vec![vec![]];
+        let mut layer = 0..1;
+        for _ in 0..length {
+            let next = out[layer.clone()]
+                .iter()
+                .cartesian_product(&symbols)
+                .map(|(w, a)| Concat(w, [*a]).collect_vec())
+                .collect_vec();
+            layer = out.len()..out.len() + next.len();
+            out.extend(next);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use automata::TransitionSystem;
+    use automata::automaton::{DFA, MealyMachine, MooreMachine};
+    use automata::core::alphabet::CharAlphabet;
+    use automata::representation::IntoTs;
+    use automata::ts::TSBuilder;
+
+    use super::{
+        MembershipOracle, RandomWalkOracle, RandomWordOracle, WMethodOracle, WpMethodOracle,
+    };
+    use crate::active::{
+        DFAOracle, Hypothesis, KearnsVazirani, LStar, MealyOracle, MooreOracle, Oracle,
+    };
+
+    fn modulo_counter() -> MooreMachine {
+        // counts the `a`s modulo 6
+        TSBuilder::default()
+            .with_state_colors([1, 0, 0, 2, 0, 0])
+            .with_edges((0..6).flat_map(|q| [(q, 'a', (q + 1) % 6), (q, 'b', q)]))
+            .into_dts_with_initial(0)
+            .into_moore()
+    }
+
+    #[test]
+    fn w_and_wp_method() {
+        let target = modulo_counter();
+        let queries = Cell::new(0);
+        let membership = MembershipOracle::new(CharAlphabet::of_size(2), |w: &[char]| {
+            queries.set(queries.get() + 1);
+            target.output(w)
+        });
+
+        let oracle = WMethodOracle::new(membership.clone(), 6);
+        let learned: MooreMachine = LStar::new(CharAlphabet::of_size(2), oracle.clone()).infer();
+        assert_eq!(learned.size(), 6);
+        assert_eq!(
+            MooreOracle::new(target.clone()).equivalence(&learned),
+            Ok(())
+        );
+
+        let oracle = WpMethodOracle::new(membership.clone(), 6);
+        let learned: MooreMachine =
+            KearnsVazirani::new(CharAlphabet::of_size(2), oracle.clone()).infer();
+        assert_eq!(learned.size(), 6);
+        assert_eq!(
+            MooreOracle::new(target.clone()).equivalence(&learned),
+            Ok(())
+        );
+
+        // the Wp-method poses fewer queries to confirm the correct hypothesis
+        queries.set(0);
+        assert_eq!(
+            WMethodOracle::new(membership.clone(), 2).equivalence(&learned),
+            Ok(())
+        );
+        let w_queries = queries.replace(0);
+        assert_eq!(
+            WpMethodOracle::new(membership, 2).equivalence(&learned),
+            Ok(())
+        );
+        assert!(queries.get() < w_queries);
+    }
+
+    #[test]
+    fn conformance_counterexamples() {
+        let target = modulo_counter();
+        let membership =
+            MembershipOracle::new(CharAlphabet::of_size(2), |w: &[char]| target.output(w));
+        // counts modulo 3 instead of 6, so it is wrong on `aaa`
+        let hypothesis: MooreMachine = TSBuilder::default()
+            .with_state_colors([1, 0, 0])
+            .with_edges((0..3).flat_map(|q| [(q, 'a', (q + 1) % 3), (q, 'b', q)]))
+            .into_dts_with_initial(0)
+            .into_moore();
+
+        assert_eq!(
+            WMethodOracle::new(membership.clone(), 0).equivalence(&hypothesis),
+            Err((vec!['a', 'a', 'a'], 2))
+        );
+        assert_eq!(
+            WpMethodOracle::new(membership.clone(), 0).equivalence(&hypothesis),
+            Err((vec!['a', 'a', 'a'], 2))
+        );
+        let Err((counterexample, expected)) = RandomWalkOracle::new(membership, 100, 0.0)
+            .with_seed(1)
+            .equivalence(&hypothesis)
+        else {
+            panic!("the random walk should find a counterexample");
+        };
+        assert_ne!(hypothesis.output(&counterexample), expected);
+    }
+
+    #[test]
+    fn random_testing() {
+        // accepts words that contain `aa`
+        let target = TSBuilder::default()
+            .with_state_colors([false, false, true])
+            .with_edges([
+                (0, 'a', 1),
+                (0, 'b', 0),
+                (1, 'a', 2),
+                (1, 'b', 0),
+                (2, 'a', 2),
+                (2, 'b', 2),
+            ])
+            .into_dfa(0);
+        let membership =
+            MembershipOracle::new(CharAlphabet::of_size(2), |w: &[char]| target.accepts(w));
+
+        let oracle = RandomWordOracle::new(membership.clone(), 200, 1, 8).with_seed(42);
+        let learned: DFA = KearnsVazirani::new(CharAlphabet::of_size(2), oracle).infer();
+        assert!(learned.equivalent(&target));
+
+        // the random walk is stacked on top of a W-method oracle
+        let oracle =
+            RandomWalkOracle::new(WMethodOracle::new(membership, 1), 200, 0.1).with_seed(7);
+        let learned: DFA = LStar::new(CharAlphabet::of_size(2), oracle).infer();
+        assert!(learned.equivalent(&target));
+        assert_eq!(DFAOracle::new(target).equivalence(&learned), Ok(()));
+    }
+
+    #[test]
+    fn wp_method_mealy() {
+        for _ in 0..5 {
+            let target = automata::random::generate_random_mealy(2, 3, 5);
+            let membership =
+                MembershipOracle::new(CharAlphabet::of_size(2), |w: &[char]| target.output(w));
+            let oracle = WpMethodOracle::new(membership, 5);
+            let learned: MealyMachine =
+                KearnsVazirani::new(CharAlphabet::of_size(2), oracle).infer();
+            assert_eq!(MealyOracle::new(&target).equivalence(&learned), Ok(()));
+        }
+    }
+}
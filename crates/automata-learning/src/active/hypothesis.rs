@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 
 use super::{Experiment, Experiments};
-use automata::automaton::{MealyMachine, MooreMachine};
+use automata::automaton::{DFA, MealyMachine, MooreMachine};
 use automata::core::{
     Color, Void,
     alphabet::{Alphabet, Symbol},
@@ -143,3 +143,52 @@ impl<A: Alphabet, C: Color> Hypothesis for MealyMachine<A, Void, C> {
         source_row[i].clone()
     }
 }
+
+impl<A: Alphabet> Hypothesis for DFA<A> {
+    type Output = bool;
+
+    fn output_from<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        input: W,
+        source: Self::StateIndex,
+    ) -> Self::Output {
+        self.reached_state_color_from(source, input)
+            .expect("Hypothesis must be complete")
+    }
+    fn from_transition_system(
+        ts: DTS<Self::Alphabet, Self::StateColor, Self::EdgeColor>,
+        initial: StateIndex,
+    ) -> Self {
+        Self::from_parts(ts, initial)
+    }
+    fn mandatory_experiments(
+        alphabet: &Self::Alphabet,
+    ) -> impl IntoIterator<Item = Experiment<SymbolOf<Self>>> {
+        [Experiment::empty()]
+    }
+    fn give_state_color(
+        mr: &[SymbolOf<Self>],
+        experiments: &Experiments<Self>,
+        row: &[Self::Output],
+    ) -> Self::StateColor {
+        debug_assert!(
+            experiments
+                .first()
+                .expect("we need at least one experiment")
+                .0
+                .is_empty(),
+            "first experiment should be empty word!"
+        );
+        row[0]
+    }
+    fn give_transition_color(
+        source_mr: &[SymbolOf<Self>],
+        a: SymbolOf<Self>,
+        target_mr: &[SymbolOf<Self>],
+        experiments: &Experiments<Self>,
+        source_row: &[Self::Output],
+        target_row: &[Self::Output],
+    ) -> Self::EdgeColor {
+        Void
+    }
+}
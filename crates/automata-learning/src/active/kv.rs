@@ -0,0 +1,383 @@
+use std::cell::{Cell, RefCell};
+
+use automata::DTS;
+use automata::core::alphabet::Alphabet;
+use automata::core::word::{Concat, FiniteWord};
+use automata::core::{Show, math};
+use automata::ts::{ForAlphabet, Sproutable, SymbolOf};
+use itertools::Itertools;
+use tracing::{info, trace};
+
+use super::{Experiments, Hypothesis, oracle::Oracle};
+
+type Word<D> = Vec<SymbolOf<D>>;
+
+/// A node in the discrimination tree of [`KearnsVazirani`].
+#[derive(Debug, Clone)]
+enum Node<S, O> {
+    /// A leaf, which corresponds to the state with the given index.
+    Leaf(usize),
+    /// An inner node, a word `u` is sifted into the child that is associated with the
+    /// output on `u` followed by the discriminator.
+    Inner {
+        discriminator: Vec<S>,
+        children: math::Map<O, usize>,
+    },
+}
+
+/// An implementation of the Kearns-Vazirani algorithm, which maintains a discrimination tree
+/// instead of an observation table. Every state is identified by an access word and two
+/// states are separated by exactly one discriminator, namely the one at their lowest common
+/// ancestor in the tree. Thus, in contrast to [`super::LStar`], no membership queries are
+/// spent on redundant experiments, which pays off especially for long counterexamples.
+///
+/// The mandatory experiments of the [`Hypothesis`] form the topmost levels of the tree, so
+/// the colors of the hypothesis can always be read off the access words. Counterexamples are
+/// decomposed with the binary search of Rivest and Schapire, which yields a single new
+/// discriminator and state. As long as the current hypothesis still disagrees on a
+/// counterexample, it is processed again before the next equivalence query is posed.
+pub struct KearnsVazirani<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet>> {
+    // the alphabet of what we are learning
+    alphabet: D::Alphabet,
+    // the oracle
+    oracle: T,
+    // a mapping containing all queries that have been posed so far, together with their output
+    queries: RefCell<math::OrderedMap<Word<D>, D::Output>>,
+    // the number of membership and equivalence queries posed to the oracle
+    membership_queries: Cell<usize>,
+    equivalence_queries: usize,
+    // the experiments that determine the colors of the hypothesis
+    mandatory: Experiments<D>,
+    // the access words of the states, the initial state is accessed by the empty word
+    access: Vec<Word<D>>,
+    // the index of the leaf for each state
+    leaves: Vec<usize>,
+    // the discrimination tree, the root is at index 0
+    tree: Vec<Node<SymbolOf<D>, D::Output>>,
+    // the transitions of the most recent hypothesis
+    transitions: math::Map<(usize, SymbolOf<D>), usize>,
+}
+
+impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> KearnsVazirani<D, T> {
+    /// Creates a new learner for the given alphabet that poses queries to `oracle`.
+    pub fn new(alphabet: D::Alphabet, oracle: T) -> Self {
+        let mandatory: Experiments<D> = D::mandatory_experiments(&alphabet).into_iter().collect();
+        assert!(
+            !mandatory.is_empty(),
+            "at least one mandatory experiment is required"
+        );
+        Self {
+            tree: vec![Node::Inner {
+                discriminator: mandatory[0].0.clone(),
+                children: math::Map::default(),
+            }],
+            mandatory,
+            alphabet,
+            oracle,
+            queries: RefCell::new(math::OrderedMap::default()),
+            membership_queries: Cell::new(0),
+            equivalence_queries: 0,
+            access: vec![],
+            leaves: vec![],
+            transitions: math::Map::default(),
+        }
+    }
+
+    /// Returns the number of membership queries that were posed to the oracle so far, queries
+    /// whose answer is already known are not counted.
+    pub fn membership_queries(&self) -> usize {
+        self.membership_queries.get()
+    }
+
+    /// Returns the number of equivalence queries that were posed to the oracle so far.
+    pub fn equivalence_queries(&self) -> usize {
+        self.equivalence_queries
+    }
+
+    /// Returns the number of states of the current hypothesis.
+    pub fn size(&self) -> usize {
+        self.access.len()
+    }
+
+    fn output(&self, w: &Word<D>) -> D::Output {
+        if !self.queries.borrow().contains_key(w) {
+            self.membership_queries
+                .set(self.membership_queries.get() + 1);
+            let c = self.oracle.output(w);
+            assert!(self.queries.borrow_mut().insert(w.to_owned(), c).is_none());
+        }
+        self.queries.borrow().get(w).unwrap().clone()
+    }
+
+    /// Runs the learner until the oracle accepts a hypothesis, which is then returned.
+    pub fn infer(&mut self) -> D {
+        let start = std::time::Instant::now();
+        if self.access.is_empty() {
+            self.sift_or_add(vec![]);
+        }
+
+        loop {
+            let hypothesis = self.hypothesis();
+            self.equivalence_queries += 1;
+            let Err((witness, expected)) = self.oracle.equivalence(&hypothesis) else {
+                info!(
+                    "Execution of Kearns-Vazirani took {}ms, {} membership and {} equivalence queries",
+                    start.elapsed().as_millis(),
+                    self.membership_queries(),
+                    self.equivalence_queries
+                );
+                return hypothesis;
+            };
+            assert!(hypothesis.output(&witness) != expected);
+
+            // a counterexample may reveal more than one state
+            let mut hypothesis = hypothesis;
+            while hypothesis.output(&witness) != expected {
+                self.process_counterexample(&witness, &expected);
+                hypothesis = self.hypothesis();
+            }
+        }
+    }
+
+    /// Sifts `word` through the tree and returns the state at the leaf it ends up in. If some
+    /// inner node has no child for the output on `word`, a new state with access word `word`
+    /// is created there.
+    fn sift_or_add(&mut self, word: Word<D>) -> usize {
+        let mut node = 0;
+        loop {
+            let (discriminator, children) = match &self.tree[node] {
+                Node::Leaf(state) => return *state,
+                Node::Inner {
+                    discriminator,
+                    children,
+                } => (discriminator, children),
+            };
+            let output = self.output(&Concat(&word, discriminator).collect_vec());
+            match children.get(&output) {
+                Some(child) => node = *child,
+                None => return self.add_state(word, node, output),
+            }
+        }
+    }
+
+    /// Adds a new state with the given access word below `node` for `output`. The mandatory
+    /// experiments that are not on the path to `node` are inserted on the way.
+    fn add_state(&mut self, word: Word<D>, mut node: usize, mut output: D::Output) -> usize {
+        let Node::Inner { discriminator, .. } = &self.tree[node] else {
+            unreachable!("states are only added below inner nodes");
+        };
+        let depth = self
+            .mandatory
+            .iter()
+            .position(|e| &e.0 == discriminator)
+            .map_or(self.mandatory.len(), |i| i + 1);
+
+        for i in depth..self.mandatory.len() {
+            let discriminator = self.mandatory[i].0.clone();
+            let next = self.output(&Concat(&word, &discriminator).collect_vec());
+            let child = self.push_node(Node::Inner {
+                discriminator,
+                children: math::Map::default(),
+            });
+            self.set_child(node, output, child);
+            (node, output) = (child, next);
+        }
+
+        let state = self.access.len();
+        trace!("adding state {state} with access word {}", word.as_string());
+        let leaf = self.push_node(Node::Leaf(state));
+        self.set_child(node, output, leaf);
+        self.access.push(word);
+        self.leaves.push(leaf);
+        state
+    }
+
+    fn push_node(&mut self, node: Node<SymbolOf<D>, D::Output>) -> usize {
+        self.tree.push(node);
+        self.tree.len() - 1
+    }
+
+    fn set_child(&mut self, node: usize, output: D::Output, child: usize) {
+        let Node::Inner { children, .. } = &mut self.tree[node] else {
+            unreachable!("leaves do not have children");
+        };
+        assert!(children.insert(output, child).is_none());
+    }
+
+    /// Computes the successor of every state on every symbol, which may discover new states,
+    /// and builds the hypothesis from that.
+    fn hypothesis(&mut self) -> D {
+        self.transitions.clear();
+        let symbols = self.alphabet.universe().collect_vec();
+        let mut state = 0;
+        while state < self.access.len() {
+            for &a in &symbols {
+                let target = self.sift_or_add(Concat(&self.access[state], [a]).into_vec());
+                self.transitions.insert((state, a), target);
+            }
+            state += 1;
+        }
+
+        let rows = self
+            .access
+            .iter()
+            .map(|mr| {
+                self.mandatory
+                    .iter()
+                    .map(|e| self.output(&Concat(mr, e).collect_vec()))
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let mut ts: DTS<_, _, _> =
+            DTS::for_alphabet_size_hint(self.alphabet.clone(), self.access.len());
+        let ids = self
+            .access
+            .iter()
+            .zip(&rows)
+            .map(|(mr, row)| ts.add_state(D::give_state_color(mr, &self.mandatory, row)))
+            .collect_vec();
+        for (&(source, a), &target) in &self.transitions {
+            let color = D::give_transition_color(
+                &self.access[source],
+                a,
+                &self.access[target],
+                &self.mandatory,
+                &rows[source],
+                &rows[target],
+            );
+            let added = ts.add_edge((
+                ids[source],
+                self.alphabet.make_expression(a),
+                color,
+                ids[target],
+            ));
+            assert!(added.is_none());
+        }
+
+        D::from_transition_system(ts, ids[0])
+    }
+
+    /// Finds a decomposition `u a v` of the counterexample such that the outputs on `[u] a v`
+    /// and `[u a] v` differ, where `[u]` is the access word of the state reached on `u`.
+    /// Then `[u] a` is the access word of a new state, which is separated from `[u a]` by `v`.
+    fn process_counterexample(&mut self, word: &Word<D>, expected: &D::Output) {
+        trace!("processing counterexample {}", word.as_string());
+        assert!(!word.is_empty(), "counterexample must not be empty");
+
+        let mut run = vec![0];
+        for a in word {
+            run.push(self.transitions[&(*run.last().unwrap(), *a)]);
+        }
+        let output_at = |i: usize| -> D::Output {
+            self.output(&Concat(&self.access[run[i]], &word[i..]).collect_vec())
+        };
+
+        // invariant: the output at `low` is the expected one and the one at `high` is not
+        let (mut low, mut high) = (0, word.len() - 1);
+        assert!(
+            output_at(high) != *expected,
+            "hypothesis must agree with the last decomposition"
+        );
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if output_at(mid) == *expected {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        let (source, a) = (run[low], word[low]);
+        let discriminator = word[low + 1..].to_vec();
+        let old = run[low + 1];
+        let new_word = Concat(&self.access[source], [a]).into_vec();
+        let old_output = self.output(&Concat(&self.access[old], &discriminator).collect_vec());
+        let new_output = self.output(&Concat(&new_word, &discriminator).collect_vec());
+        assert!(old_output != new_output);
+
+        // the leaf of `old` becomes an inner node that separates it from the new state
+        let state = self.access.len();
+        trace!(
+            "splitting state {old} with discriminator {}, new state {state} has access word {}",
+            discriminator.as_string(),
+            new_word.as_string()
+        );
+        let old_leaf = self.push_node(Node::Leaf(old));
+        let new_leaf = self.push_node(Node::Leaf(state));
+        self.tree[self.leaves[old]] = Node::Inner {
+            discriminator,
+            children: [(old_output, old_leaf), (new_output, new_leaf)]
+                .into_iter()
+                .collect(),
+        };
+        self.leaves[old] = old_leaf;
+        self.access.push(new_word);
+        self.leaves.push(new_leaf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::active::{DFAOracle, LStar, MealyOracle, MooreOracle, Oracle};
+    use automata::TransitionSystem;
+    use automata::automaton::{DFA, MealyMachine, MooreMachine};
+    use automata::core::alphabet::CharAlphabet;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::KearnsVazirani;
+
+    #[test]
+    fn kv_random_mealy_and_moore() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let symbols = rng.gen_range(1..5);
+            let max_color = rng.gen_range(1..10);
+            let size = rng.gen_range(1..25);
+
+            let ts = automata::random::generate_random_mealy(symbols, max_color, size);
+            let oracle = MealyOracle::new(&ts);
+            let mm: MealyMachine = KearnsVazirani::new(oracle.alphabet().clone(), oracle).infer();
+            assert_eq!(mm.size(), ts.size());
+
+            let ts = automata::random::generate_random_moore(symbols, max_color, size);
+            let oracle = MooreOracle::new(ts.clone());
+            let mm: MooreMachine =
+                KearnsVazirani::new(CharAlphabet::of_size(symbols), oracle).infer();
+            assert_eq!(mm.size(), ts.size());
+        }
+    }
+
+    #[test]
+    fn kv_random_dfa() {
+        for _ in 0..100 {
+            let dfa = automata::random::generate_random_dfa(2, 0.9);
+            let oracle = DFAOracle::new(dfa.clone());
+            let learned: DFA = KearnsVazirani::new(CharAlphabet::of_size(2), oracle).infer();
+            assert_eq!(learned.size(), dfa.clone().minimize().size());
+            assert!(learned.equivalent(&dfa));
+        }
+    }
+
+    #[test]
+    fn kv_poses_fewer_membership_queries_than_lstar() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (mut kv, mut lstar) = (0, 0);
+        for size in 5..25 {
+            let ts = automata::random::generate_random_moore_with(&mut rng, 3, 4, size);
+
+            let mut learner =
+                KearnsVazirani::new(CharAlphabet::of_size(3), MooreOracle::new(ts.clone()));
+            let _: MooreMachine = learner.infer();
+            kv += learner.membership_queries();
+
+            let mut learner = LStar::new(CharAlphabet::of_size(3), MooreOracle::new(ts));
+            let _: MooreMachine = learner.infer();
+            lstar += learner.statistics().membership_queries;
+        }
+        assert!(
+            kv < lstar,
+            "{kv} membership queries for KV, {lstar} for LStar"
+        );
+    }
+}
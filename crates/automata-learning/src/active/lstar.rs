@@ -1,4 +1,7 @@
-use std::{cell::RefCell, fmt::Debug};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+};
 
 use automata::DTS;
 use automata::core::alphabet::Alphabet;
@@ -17,6 +20,45 @@ const ITERATION_THRESHOLD: usize = if cfg!(debug_assertions) { 50 } else { 20000
 type Word<D> = Vec<SymbolOf<D>>;
 pub type Experiments<D> = Vec<Experiment<SymbolOf<D>>>;
 
+/// Determines how [`LStar`] extracts new experiments from a counterexample.
+///
+/// Let `w` be a counterexample and for a prefix `u` of `w`, let `[u]` be the access word of
+/// the state that the hypothesis reaches on `u`. Apart from [`Self::MalerPnueli`] and
+/// [`Self::ShahbazGroz`], the strategies look for a decomposition `w = u a v` such that the
+/// outputs on `[u] a v` and `[u a] v` differ, in which case `v` separates `[u] a` from `[u a]`
+/// and it is added as the only new experiment. They differ in how such a decomposition is
+/// found, which is reflected in the number of membership queries that are posed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CounterexampleStrategy {
+    /// Adds every suffix of the counterexample as an experiment.
+    #[default]
+    MalerPnueli,
+    /// Finds a single distinguishing suffix by binary search, which takes a logarithmic
+    /// number of membership queries in the length of the counterexample.
+    RivestSchapire,
+    /// Removes the longest prefix of the counterexample that is a row of the observation
+    /// table and adds every suffix of the remainder as an experiment.
+    ShahbazGroz,
+    /// Finds a single distinguishing suffix by scanning the decompositions from the front,
+    /// like the counterexample analysis of Kearns and Vazirani does.
+    KearnsVazirani,
+}
+
+/// Counts the queries that were posed by a run of [`LStar`], which allows comparing the
+/// different [`CounterexampleStrategy`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LStarStatistics {
+    /// The strategy that was used for processing counterexamples.
+    pub strategy: CounterexampleStrategy,
+    /// The number of membership queries that were posed to the oracle, queries whose answer is
+    /// already known are not counted.
+    pub membership_queries: usize,
+    /// The number of equivalence queries that were posed to the oracle.
+    pub equivalence_queries: usize,
+    /// The number of experiments, i.e. columns of the observation table.
+    pub experiments: usize,
+}
+
 /// An implementation of the L* algorithm.
 pub struct LStar<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet>> {
     // the alphabet of what we are learning
@@ -33,6 +75,11 @@ pub struct LStar<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet>> {
     // the oracle
     oracle: T,
     observations: ObservationTable<SymbolOf<D>, T::Output>,
+    // how counterexamples are turned into experiments
+    strategy: CounterexampleStrategy,
+    // the number of membership and equivalence queries posed to the oracle
+    membership_queries: Cell<usize>,
+    equivalence_queries: usize,
 }
 
 impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> LStar<D, T> {
@@ -48,11 +95,37 @@ impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> LStar
             base: vec![vec![]],
             table: math::OrderedMap::default(),
             oracle,
+            strategy: CounterexampleStrategy::default(),
+            membership_queries: Cell::new(0),
+            equivalence_queries: 0,
+        }
+    }
+
+    /// Sets the strategy that is used for processing counterexamples.
+    pub fn with_counterexample_strategy(mut self, strategy: CounterexampleStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the strategy that is used for processing counterexamples.
+    pub fn counterexample_strategy(&self) -> CounterexampleStrategy {
+        self.strategy
+    }
+
+    /// Returns the number of queries that were posed so far.
+    pub fn statistics(&self) -> LStarStatistics {
+        LStarStatistics {
+            strategy: self.strategy,
+            membership_queries: self.membership_queries.get(),
+            equivalence_queries: self.equivalence_queries,
+            experiments: self.experiments.len(),
         }
     }
 
     fn output(&self, w: &Word<D>) -> D::Output {
         if !self.queries.borrow().contains_key(w) {
+            self.membership_queries
+                .set(self.membership_queries.get() + 1);
             let c = self.oracle.output(w);
             assert!(self.queries.borrow_mut().insert(w.to_owned(), c).is_none());
         }
@@ -134,9 +207,13 @@ impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> LStar
 
             let hypothesis = self.hypothesis();
 
+            self.equivalence_queries += 1;
             let Err((witness, expected_color)) = self.oracle.equivalence(&hypothesis) else {
                 let duration = start.elapsed().as_millis();
-                info!("Execution of LStar took {duration}ms");
+                info!(
+                    "Execution of LStar took {duration}ms, {:?}",
+                    self.statistics()
+                );
                 return hypothesis;
             };
             assert!(hypothesis.output(&witness) != expected_color);
@@ -147,7 +224,39 @@ impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> LStar
     }
 
     fn process_counterexample(&mut self, word: Word<D>, color: D::Output) {
-        trace!("Processing counterexample {}", word.as_string());
+        trace!(
+            "Processing counterexample {} with {:?}",
+            word.as_string(),
+            self.strategy
+        );
+        match self.strategy {
+            CounterexampleStrategy::MalerPnueli => self.add_suffixes(&word),
+            CounterexampleStrategy::ShahbazGroz => {
+                let known = (0..=word.len())
+                    .rev()
+                    .find(|i| self.table.contains_key(&word[..*i]))
+                    .expect("the empty word is always a row");
+                self.add_suffixes(&word[known..]);
+            }
+            CounterexampleStrategy::RivestSchapire | CounterexampleStrategy::KearnsVazirani => {
+                match self.distinguishing_suffix(&word, color) {
+                    Some(suffix) if !self.experiments.contains(&suffix) => {
+                        trace!("Adding experiment {}", suffix.as_string());
+                        self.experiments.push(suffix);
+                    }
+                    _ => {
+                        warn!(
+                            "could not find new distinguishing suffix in {}, adding all suffixes",
+                            word.as_string()
+                        );
+                        self.add_suffixes(&word);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_suffixes(&mut self, word: &[SymbolOf<D>]) {
         for i in 0..(word.len()) {
             let suffix = Experiment(word[i..].to_vec());
             assert!(!suffix.is_empty());
@@ -158,6 +267,64 @@ impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet, Output = D::Output>> LStar
         }
     }
 
+    /// Looks for a position `i` such that the outputs on `[w[..i]] w[i..]` and
+    /// `[w[..i + 1]] w[i + 1..]` differ and returns the suffix `w[i + 1..]`. As the output
+    /// for `i = 0` is the expected `color` and the output for `i = w.len() - 1` is the one of
+    /// the hypothesis, such a position exists if `w` is a counterexample.
+    fn distinguishing_suffix(
+        &self,
+        word: &[SymbolOf<D>],
+        color: D::Output,
+    ) -> Option<Experiment<SymbolOf<D>>> {
+        if word.is_empty() {
+            return None;
+        }
+        let access = self.access_words(word);
+        let output_at = |i: usize| -> D::Output {
+            let query = Concat(&access[i], &word[i..]).collect_vec();
+            self.output(&query)
+        };
+
+        let last = word.len() - 1;
+        let position = match self.strategy {
+            CounterexampleStrategy::RivestSchapire => {
+                // invariant: the output at `low` is `color` and the one at `high` is not
+                let (mut low, mut high) = (0, last);
+                if output_at(high) == color {
+                    return None;
+                }
+                while high - low > 1 {
+                    let mid = (low + high) / 2;
+                    if output_at(mid) == color {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                low
+            }
+            _ => (0..last).find(|i| output_at(i + 1) != color)?,
+        };
+        Some(Experiment(word[position + 1..].to_vec()))
+    }
+
+    /// Computes the access words `[u]` for every prefix `u` of `word`, which requires the
+    /// table to be closed.
+    fn access_words(&self, word: &[SymbolOf<D>]) -> Vec<Word<D>> {
+        let base_of_row: math::Map<_, _> = self
+            .base
+            .iter()
+            .map(|b| (self.table.get(b).expect("base rows must be present"), b))
+            .collect();
+        let mut out = vec![vec![]];
+        for a in word {
+            let extended = Concat(out.last().unwrap(), [*a]).into_vec();
+            let row = self.table.get(&extended).expect("table must be closed");
+            out.push((*base_of_row.get(row).expect("table must be closed")).clone());
+        }
+        out
+    }
+
     fn state_color(&self, mr: &Word<D>) -> D::StateColor {
         D::give_state_color(
             mr,
@@ -347,13 +514,77 @@ impl<D: Hypothesis, T: Oracle<Alphabet = D::Alphabet>> std::fmt::Debug for LStar
 
 #[cfg(test)]
 mod tests {
+    use super::CounterexampleStrategy;
     use crate::active::{MealyOracle, MooreOracle, Oracle};
     use automata::TransitionSystem;
     use automata::automaton::{MealyMachine, MooreMachine};
     use automata::core::alphabet::CharAlphabet;
+    use automata::representation::IntoTs;
+    use automata::ts::TSBuilder;
     use rand::Rng;
     use tracing::trace;
 
+    const STRATEGIES: [CounterexampleStrategy; 4] = [
+        CounterexampleStrategy::MalerPnueli,
+        CounterexampleStrategy::RivestSchapire,
+        CounterexampleStrategy::ShahbazGroz,
+        CounterexampleStrategy::KearnsVazirani,
+    ];
+
+    #[test]
+    fn lstar_counterexample_strategies() {
+        let mut rng = rand::thread_rng();
+        for strategy in STRATEGIES {
+            for _ in 0..50 {
+                let symbols = rng.gen_range(1..4);
+                let max_color = rng.gen_range(1..5);
+                let size = rng.gen_range(1..15);
+
+                let ts = automata::random::generate_random_mealy(symbols, max_color, size);
+                let oracle = MealyOracle::new(&ts);
+                let mut learner = super::LStar::new(oracle.alphabet().clone(), oracle)
+                    .with_counterexample_strategy(strategy);
+                let mm: MealyMachine = learner.infer();
+                assert_eq!(mm.size(), ts.size(), "{strategy:?} failed");
+
+                let ts = automata::random::generate_random_moore(symbols, max_color, size);
+                let oracle = MooreOracle::new(ts.clone());
+                let mut learner = super::LStar::new(CharAlphabet::of_size(symbols), oracle)
+                    .with_counterexample_strategy(strategy);
+                let mm: MooreMachine = learner.infer();
+                assert_eq!(mm.size(), ts.size(), "{strategy:?} failed");
+
+                let statistics = learner.statistics();
+                assert_eq!(statistics.strategy, strategy);
+                assert!(statistics.equivalence_queries >= 1);
+                assert!(statistics.membership_queries >= statistics.experiments);
+            }
+        }
+    }
+
+    #[test]
+    fn lstar_strategy_statistics() {
+        // counts the `a`s modulo 6, which needs a long counterexample
+        let target: MooreMachine = TSBuilder::default()
+            .with_state_colors([1, 0, 0, 0, 0, 0])
+            .with_edges((0..6).flat_map(|q| [(q, 'a', (q + 1) % 6), (q, 'b', q)]))
+            .into_dts_with_initial(0)
+            .into_moore();
+        let statistics = STRATEGIES.map(|strategy| {
+            let oracle = MooreOracle::new(target.clone());
+            let mut learner = super::LStar::new(CharAlphabet::of_size(2), oracle)
+                .with_counterexample_strategy(strategy);
+            let mm: MooreMachine = learner.infer();
+            assert_eq!(mm.size(), 6);
+            learner.statistics()
+        });
+        let [maler_pnueli, rivest_schapire, _, kearns_vazirani] = statistics;
+        for single_suffix in [rivest_schapire, kearns_vazirani] {
+            assert!(single_suffix.experiments < maler_pnueli.experiments);
+            assert!(single_suffix.membership_queries < maler_pnueli.membership_queries);
+        }
+    }
+
     #[test]
     fn lstar_random_mealy() {
         let mut rng = rand::thread_rng();
@@ -1,10 +1,13 @@
 use std::cell::RefCell;
 
-use crate::passive::SetSample;
+use crate::passive::{OmegaSample, SetSample};
 use automata::automaton::{DFA, IntoMooreMachine, MealyLike, MealyMachine};
 use automata::core::alphabet::{Alphabet, Symbol};
-use automata::core::word::{FiniteWord, Word};
-use automata::core::{Color, Int, Lattice, Void, math::Set};
+use automata::core::word::{FiniteWord, OmegaWord, ReducedOmegaWord, Word};
+use automata::core::{
+    Color, Int, Lattice, Void,
+    math::{Map, Set},
+};
 use automata::representation::CollectTs;
 use automata::ts::operations::Product;
 use automata::ts::{Deterministic, EdgeColor, StateColor};
@@ -52,10 +55,11 @@ where
 /// An oracle/minimally adequate teacher based on a [`SetSample`]. It answers membership queries by looking up the
 /// word in the sample and returning the corresponding color. If the word is not in the sample, it returns the
 /// default color. Equivalence queries are perfomed by checking if the hypothesis produces the same output as the
-/// sample for all words in the sample.
+/// sample for all words in the sample, the shortest word on which they disagree is returned as counterexample.
 #[derive(Debug, Clone)]
 pub struct SampleOracle<A: Alphabet, W: Word<Symbol = A::Symbol>> {
     sample: SetSample<A, W>,
+    classification: Map<Vec<A::Symbol>, bool>,
     default: bool,
 }
 
@@ -68,7 +72,10 @@ impl<A: Alphabet, X: FiniteWord<Symbol = A::Symbol>> Oracle for SampleOracle<A,
         &self,
         word: W,
     ) -> Self::Output {
-        todo!()
+        self.classification
+            .get(&word.collect_vec())
+            .copied()
+            .unwrap_or(self.default)
     }
 
     fn equivalence<H>(
@@ -78,7 +85,12 @@ impl<A: Alphabet, X: FiniteWord<Symbol = A::Symbol>> Oracle for SampleOracle<A,
     where
         H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
     {
-        todo!()
+        shortest_counterexample(
+            self.classification
+                .iter()
+                .map(|(word, expected)| (word.clone(), *expected)),
+            hypothesis,
+        )
     }
 
     fn alphabet(&self) -> &Self::Alphabet {
@@ -96,7 +108,15 @@ impl<A: Alphabet, W: Word<Symbol = A::Symbol>> SampleOracle<A, W> {
 impl<A: Alphabet, W: FiniteWord<Symbol = A::Symbol>> SampleOracle<A, W> {
     /// Creates a new instance of a [`SampleOracle`] with the given sample and default color.
     pub fn new(sample: SetSample<A, W>, default: bool) -> Self {
-        Self { sample, default }
+        let classification = sample
+            .entries()
+            .map(|(word, classification)| (word.collect_vec(), classification))
+            .collect();
+        Self {
+            sample,
+            classification,
+            default,
+        }
     }
 }
 
@@ -108,6 +128,123 @@ impl<A: Alphabet, W: FiniteWord<Symbol = A::Symbol>> From<(SetSample<A, W>, bool
     }
 }
 
+/// An oracle based on an [`OmegaSample`], which allows learning an omega-language through
+/// a finite-word encoding. A non-empty finite word `v` encodes the ultimately periodic word
+/// `u v^ω`, where `u` is a fixed prefix, which is empty unless it is set with
+/// [`Self::with_prefix`]. With the empty prefix, this encodes the periodic words of the
+/// language, with a non-empty prefix `u` it encodes the periodic continuations of `u`,
+/// which is what the progress components of a family of DFAs are learned from.
+///
+/// Membership queries look up the encoded word in the sample and return the default color
+/// if it is not present or the queried word is empty. Equivalence queries check the
+/// hypothesis on the encodings of all sample words of the form `u v^ω` and return the
+/// shortest encoding on which it disagrees with the sample.
+#[derive(Debug, Clone)]
+pub struct OmegaSampleOracle<A: Alphabet> {
+    sample: OmegaSample<A>,
+    prefix: Vec<A::Symbol>,
+    default: bool,
+}
+
+impl<A: Alphabet> OmegaSampleOracle<A> {
+    /// Creates a new instance with the given sample and default color, which encodes the
+    /// periodic words of the sample.
+    pub fn new(sample: OmegaSample<A>, default: bool) -> Self {
+        Self {
+            sample,
+            prefix: vec![],
+            default,
+        }
+    }
+
+    /// Sets the prefix `u` such that a finite word `v` encodes the word `u v^ω`.
+    pub fn with_prefix<W: FiniteWord<Symbol = A::Symbol>>(mut self, prefix: W) -> Self {
+        self.prefix = prefix.collect_vec();
+        self
+    }
+
+    /// Returns the prefix that precedes the periodic part of every encoded word.
+    pub fn prefix(&self) -> &[A::Symbol] {
+        &self.prefix
+    }
+
+    /// Returns a reference to the underlying alphabet, as provided by [`SetSample::alphabet()`].
+    pub fn alphabet(&self) -> &A {
+        self.sample.alphabet()
+    }
+
+    /// Returns the shortest finite word `v` such that `word` is `u v^ω` for the prefix `u`,
+    /// or `None` if `word` does not have this form.
+    fn encode(&self, word: &ReducedOmegaWord<A::Symbol>) -> Option<Vec<A::Symbol>> {
+        let mut suffix = word.clone();
+        for &symbol in &self.prefix {
+            if suffix.pop_front() != symbol {
+                return None;
+            }
+        }
+        // the suffix is still reduced, so its cycle is the shortest period
+        (suffix.loop_index() == 0).then(|| suffix.cycle().to_vec())
+    }
+}
+
+impl<A: Alphabet> Oracle for OmegaSampleOracle<A> {
+    type Alphabet = A;
+
+    type Output = bool;
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        if word.is_empty() {
+            return self.default;
+        }
+        let encoded = ReducedOmegaWord::ultimately_periodic(self.prefix.as_slice(), word);
+        self.sample.classify(&encoded).unwrap_or(self.default)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        shortest_counterexample(
+            self.sample
+                .entries()
+                .filter_map(|(word, expected)| Some((self.encode(word)?, expected))),
+            hypothesis,
+        )
+    }
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.sample.alphabet()
+    }
+}
+
+/// Returns the shortest (and among those the lexicographically smallest) of the given words
+/// on which the hypothesis does not produce the expected output.
+fn shortest_counterexample<S, I, H>(
+    words: I,
+    hypothesis: &H,
+) -> Result<(), Counterexample<H::Alphabet, bool>>
+where
+    S: Symbol,
+    I: IntoIterator<Item = (Vec<S>, bool)>,
+    H: Hypothesis<Output = bool>,
+    H::Alphabet: Alphabet<Symbol = S>,
+{
+    match words
+        .into_iter()
+        .filter(|(word, expected)| hypothesis.output(word) != *expected)
+        .min_by(|(u, _), (v, _)| u.len().cmp(&v.len()).then_with(|| u.cmp(v)))
+    {
+        Some(counterexample) => Err(counterexample),
+        None => Ok(()),
+    }
+}
+
 /// An oracle base on a [`DFA`] instance. It answers membership queries by running the word through the
 /// automaton and returning the result. Equivalence queries are performed by intersecting the hypothesis with
 /// the negated input automaton and returning a counterexample if the intersection is non-empty.
@@ -344,12 +481,85 @@ where
 
 #[cfg(test)]
 mod tests {
-    use automata::automaton::MealyMachine;
+    use automata::automaton::{MealyMachine, MooreMachine};
+    use automata::core::alphabet::CharAlphabet;
+    use automata::core::upw;
+    use automata::representation::IntoTs;
+    use automata::ts::TSBuilder;
     use automata::{DTS, TransitionSystem};
 
-    use crate::active::LStar;
+    use crate::active::{Hypothesis, LStar};
+    use crate::passive::{FiniteSample, OmegaSample};
+
+    use super::{CompletingMealyOracle, OmegaSampleOracle, Oracle, SampleOracle};
+
+    #[test]
+    fn sample_oracle() {
+        // words with an even number of `a`s
+        let words = [
+            "", "a", "b", "aa", "ab", "ba", "bb", "aab", "aba", "bab", "abba",
+        ];
+        let sample = FiniteSample::new_finite(
+            CharAlphabet::of_size(2),
+            words.map(|w| (w.chars(), w.chars().filter(|c| *c == 'a').count() % 2 == 0)),
+        );
+        assert!(SampleOracle::new(sample.clone(), true).output("bbbb"));
+        let oracle = SampleOracle::new(sample, false);
+        assert!(oracle.output("aab"));
+        assert!(!oracle.output("ba"));
+        assert!(!oracle.output("bbbb"));
+
+        let all_true: MooreMachine<CharAlphabet, bool> = TSBuilder::default()
+            .with_state_colors([true])
+            .with_edges([(0, 'a', 0), (0, 'b', 0)])
+            .into_dts_with_initial(0)
+            .into_moore();
+        assert_eq!(oracle.equivalence(&all_true), Err((vec!['a'], false)));
+
+        let mut learner = LStar::new(oracle.alphabet().clone(), oracle.clone());
+        let learned: MooreMachine<CharAlphabet, bool> = learner.infer();
+        assert_eq!(oracle.equivalence(&learned), Ok(()));
+        for w in words {
+            assert_eq!(learned.output(w), oracle.output(w));
+        }
+    }
 
-    use super::CompletingMealyOracle;
+    #[test]
+    fn omega_sample_oracle() {
+        // infinitely many `a`s
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            CharAlphabet::of_size(2),
+            [upw!("a"), upw!("ab"), upw!("bba"), upw!("b", "a")],
+            [upw!("b"), upw!("a", "b"), upw!("ab", "b")],
+        );
+        let oracle = OmegaSampleOracle::new(sample.clone(), false);
+        assert!(oracle.output("a"));
+        assert!(oracle.output("abab"));
+        assert!(oracle.output("bbabba"));
+        assert!(!oracle.output("bb"));
+        assert!(!oracle.output(""));
+        // (ba)^ω is not in the sample
+        assert!(!oracle.output("ba"));
+
+        let all_true: MooreMachine<CharAlphabet, bool> = TSBuilder::default()
+            .with_state_colors([true])
+            .with_edges([(0, 'a', 0), (0, 'b', 0)])
+            .into_dts_with_initial(0)
+            .into_moore();
+        assert_eq!(oracle.equivalence(&all_true), Err((vec!['b'], false)));
+
+        // the sample words starting with `b` continue with (bab)^ω, a^ω and b^ω
+        let oracle = OmegaSampleOracle::new(sample, false).with_prefix("b");
+        assert_eq!(oracle.prefix(), &['b']);
+        assert!(oracle.output("bab"));
+        assert!(oracle.output("a"));
+        assert!(!oracle.output("b"));
+        assert_eq!(oracle.equivalence(&all_true), Err((vec!['b'], false)));
+
+        let learned: MooreMachine<CharAlphabet, bool> =
+            LStar::new(oracle.alphabet().clone(), oracle.clone()).infer();
+        assert_eq!(oracle.equivalence(&learned), Ok(()));
+    }
 
     #[test]
     fn mealy_al() {
@@ -1,16 +1,20 @@
-use automata::automaton::{DBA, DFA, DPA, MealyMachine};
+use automata::automaton::{Automaton, BuchiCondition, DBA, DFA, DPA, MealyMachine, WithInitial};
+use automata::core::Void;
 use automata::core::alphabet::{Alphabet, CharAlphabet};
 use automata::representation::{CollectTs, IntoTs};
-use automata::ts::Deterministic;
 use automata::ts::operations::Product;
+use automata::ts::{Deterministic, IsEdge, Sproutable};
 use automata::{
-    TransitionSystem,
+    DTS, Pointed, TransitionSystem,
     ts::{
         IndexedAlphabet,
         operations::{DefaultIfMissing, MapStateColor},
     },
 };
+use itertools::Itertools;
 use owo_colors::OwoColorize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, trace};
 
 /// Contains definitions for samples, which are collections of positive and
@@ -27,6 +31,7 @@ use crate::{
 };
 
 use self::precise::PreciseDPA;
+use self::sprout::ConsistencyCheck;
 
 pub use self::sample::{FiniteSample, OmegaSample};
 
@@ -62,8 +67,133 @@ pub fn dfa_rpni<A: Alphabet>(sample: &FiniteSample<A>) -> DFA<A> {
 }
 
 /// Executes a variant of the RPNI algorithm for omega-words, producing a DBA.
-pub fn dba_rpni<A: Alphabet>(sample: &OmegaSample<A>) -> DBA<A> {
-    todo!()
+///
+/// Starting from the [`OmegaSample::prefix_tree`], the states are considered in the order in
+/// which they were created. Each state is merged into the first of the states that are kept
+/// so far, for which the merge (followed by folding, which restores determinism) results in
+/// a transition system that is Büchi-consistent with the sample in the sense of
+/// [`sprout::ConsistencyCheck`]. If there is no such state, the state is kept. Finally, the
+/// accepting transitions are derived as in [`sprout::sprout`] and the resulting DBA, which is
+/// completed with a rejecting sink, is consistent with the sample.
+pub fn dba_rpni<A: IndexedAlphabet>(sample: &OmegaSample<A>) -> DBA<A> {
+    let alphabet = sample.alphabet();
+    if sample.words().next().is_none() {
+        let ts: WithInitial<DTS<A>> = Automaton::new_with_initial_color(alphabet.clone(), Void);
+        return BuchiCondition.consistent_automaton(&ts, sample, vec![], vec![]);
+    }
+
+    let pta = sample.prefix_tree();
+    let edges = pta
+        .state_indices()
+        .flat_map(|q| {
+            pta.edges_from(q)
+                .into_iter()
+                .flatten()
+                .map(move |e| (q, e.expression().clone(), e.target()))
+        })
+        .collect_vec();
+    let states = pta.state_indices().sorted().collect_vec();
+    let size = states.last().map_or(0, |q| *q as usize + 1);
+
+    let mut partition: Vec<u32> = (0..size as u32).collect();
+    let mut kept = vec![pta.initial()];
+    for &q in &states {
+        if kept
+            .iter()
+            .any(|&p| find_class(&partition, p) == find_class(&partition, q))
+        {
+            continue;
+        }
+        let merged = kept.iter().find_map(|&p| {
+            let mut candidate = partition.clone();
+            merge_and_fold(&mut candidate, &edges, p, q);
+            let quotient = quotient(alphabet, &candidate, &edges, pta.initial());
+            BuchiCondition
+                .consistent(&quotient, sample, vec![], vec![])
+                .0
+                .then_some(candidate)
+        });
+        match merged {
+            Some(candidate) => {
+                trace!("merged state {q} with one of {kept:?}");
+                partition = candidate;
+            }
+            None => kept.push(q),
+        }
+    }
+    debug!("dba_rpni kept {} states of the prefix tree", kept.len());
+
+    let ts = quotient(alphabet, &partition, &edges, pta.initial());
+    BuchiCondition.consistent_automaton(&ts, sample, vec![], vec![])
+}
+
+/// Returns the representative of the class of `q` in the given partition, which is the
+/// smallest state of the class.
+fn find_class(partition: &[u32], mut q: u32) -> u32 {
+    while partition[q as usize] != q {
+        q = partition[q as usize];
+    }
+    q
+}
+
+/// Merges the classes of `p` and `q` and subsequently merges the classes of the targets of
+/// all edges that leave the same class with the same expression, until the quotient with
+/// respect to the partition is deterministic.
+fn merge_and_fold<E: Eq + std::hash::Hash>(
+    partition: &mut [u32],
+    edges: &[(u32, E, u32)],
+    p: u32,
+    q: u32,
+) {
+    let union = |partition: &mut [u32], p: u32, q: u32| {
+        let (p, q) = (find_class(partition, p), find_class(partition, q));
+        partition[p.max(q) as usize] = p.min(q);
+        p != q
+    };
+    union(partition, p, q);
+    loop {
+        let mut successors: HashMap<(u32, &E), u32> = HashMap::new();
+        let mut merged = false;
+        for (source, expression, target) in edges {
+            let source = find_class(partition, *source);
+            let target = find_class(partition, *target);
+            match successors.entry((source, expression)) {
+                Entry::Occupied(successor) => merged |= union(partition, *successor.get(), target),
+                Entry::Vacant(successor) => {
+                    successor.insert(target);
+                }
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+}
+
+/// Builds the quotient of the transition system with the given edges with respect to the
+/// given partition, which has to be folded already.
+fn quotient<A: Alphabet>(
+    alphabet: &A,
+    partition: &[u32],
+    edges: &[(u32, A::Expression, u32)],
+    initial: u32,
+) -> WithInitial<DTS<A>> {
+    let mut ts: WithInitial<DTS<A>> = Automaton::new_with_initial_color(alphabet.clone(), Void);
+    let mut states = HashMap::from([(find_class(partition, initial), ts.initial())]);
+    let mut state = |ts: &mut WithInitial<DTS<A>>, q: u32| {
+        *states
+            .entry(find_class(partition, q))
+            .or_insert_with(|| ts.add_state(Void))
+    };
+    let mut seen = HashSet::new();
+    for (source, expression, target) in edges {
+        let source = state(&mut ts, *source);
+        let target = state(&mut ts, *target);
+        if seen.insert((source, expression)) {
+            ts.add_edge((source, expression.clone(), target));
+        }
+    }
+    ts
 }
 
 /// Takes a reference to an [`OmegaSample`], which classifies infinite words over the alphabet `A`
@@ -136,7 +266,7 @@ fn characterize_dpa(dpa: DPA) -> OmegaSample {
 
 #[cfg(test)]
 mod tests {
-    use crate::passive::dpa_rpni;
+    use crate::passive::{dba_rpni, dpa_rpni};
     use automata::TransitionSystem;
     use automata::core::alphabet::CharAlphabet;
     use automata::core::upw;
@@ -145,6 +275,26 @@ mod tests {
 
     use super::{OmegaSample, sample};
 
+    #[test]
+    fn dba_rpni_infinitely_many_a() {
+        let alphabet = CharAlphabet::of_size(2);
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            alphabet,
+            [upw!("a"), upw!("ab"), upw!("ba"), upw!("abb"), upw!("bba")],
+            [upw!("b"), upw!("a", "b"), upw!("ab", "b"), upw!("aa", "b")],
+        );
+
+        let dba = dba_rpni(&sample);
+        for w in sample.positive_words() {
+            assert!(dba.accepts(w), "{w:?} should be accepted");
+        }
+        for w in sample.negative_words() {
+            assert!(!dba.accepts(w), "{w:?} should be rejected");
+        }
+        assert!(dba.accepts(upw!("aab")));
+        assert!(!dba.accepts(upw!("bab", "b")));
+    }
+
     #[test]
     fn infer_precise_dpa_with_al_inf_aa() {
         let alphabet = CharAlphabet::of_size(3);
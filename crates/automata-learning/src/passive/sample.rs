@@ -55,6 +55,14 @@ pub trait Sample<A: Alphabet> {
 /// Represents a finite sample, which is a pair of positive and negative instances.
 #[derive(Clone, Eq, PartialEq)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "A: serde::Serialize, W: serde::Serialize",
+        deserialize = "A: serde::Deserialize<'de>, W: serde::Deserialize<'de> + Eq"
+    ))
+)]
 pub struct SetSample<A: Alphabet, W: Word<Symbol = A::Symbol> + Hash> {
     pub alphabet: A,
     pub positive: math::Set<W>,
@@ -266,6 +274,34 @@ mod tests {
         assert_eq!(sample.classify(&upw!("ab")), Some(false));
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        use crate::passive::{FiniteSample, OmegaSample};
+
+        let omega = OmegaSample::new_omega_from_pos_neg(
+            CharAlphabet::of_size(2),
+            [upw!("a"), upw!("b", "a")],
+            [upw!("b"), upw!("ab", "b")],
+        );
+        let json = serde_json::to_string(&omega).unwrap();
+        assert_eq!(serde_json::from_str::<OmegaSample>(&json).unwrap(), omega);
+        let bytes = bincode::serialize(&omega).unwrap();
+        assert_eq!(bincode::deserialize::<OmegaSample>(&bytes).unwrap(), omega);
+
+        let finite = FiniteSample::new_finite(
+            CharAlphabet::of_size(2),
+            [("ab".chars(), true), ("b".chars(), false)],
+        );
+        let json = serde_json::to_string(&finite).unwrap();
+        assert_eq!(serde_json::from_str::<FiniteSample>(&json).unwrap(), finite);
+        let bytes = bincode::serialize(&finite).unwrap();
+        assert_eq!(
+            bincode::deserialize::<FiniteSample>(&bytes).unwrap(),
+            finite
+        );
+    }
+
     #[test]
     fn to_periodic_sample() {
         let alphabet = CharAlphabet::of_size(2);
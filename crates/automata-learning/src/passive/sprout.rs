@@ -9,24 +9,162 @@ use tracing::{error, info, trace, warn};
 use crate::prefixtree::prefix_tree;
 use automata::automaton::{Automaton, WithInitial};
 use automata::core::Void;
-use automata::core::alphabet::CharAlphabet;
+use automata::core::alphabet::{Alphabet, CharAlphabet};
 use automata::core::word::OmegaWord;
 use automata::ts::{Deterministic, Shrinkable, Sproutable, run};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use std::{collections::HashSet, fmt::Debug, path::Iter};
 
 use super::OmegaSample;
 
+/// The timeout that [`SproutConfig::default`] uses.
+pub const DEFAULT_SPROUT_TIMEOUT: Duration = Duration::from_secs(60 * 30);
+
+/// Can be shared with a running [`sprout_with_config`] to ask it to stop. Clones of a token
+/// refer to the same flag, so it can be handed to another thread that cancels the run.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token, which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every run that uses this token or a clone of it.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if [`Self::cancel`] was called on this token or a clone of it.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The state of a running [`sprout_with_config`], which is handed to the progress callback
+/// of a [`SproutConfig`] every time an escaping word is processed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SproutProgress {
+    /// The number of states of the transition system that is constructed.
+    pub states: usize,
+    /// The number of sample words that are still escaping the transition system.
+    pub escaping: usize,
+}
+
+/// Configures when [`sprout_with_config`] gives up and how it reports progress. The default
+/// configuration gives up after [`DEFAULT_SPROUT_TIMEOUT`], uses the escape prefix threshold
+/// that is computed from the sample and does not bound the number of states.
+///
+/// # Example
+/// ```
+/// use automata_learning::passive::sprout::{CancellationToken, SproutConfig};
+/// use std::time::Duration;
+///
+/// let token = CancellationToken::new();
+/// let config = SproutConfig::default()
+///     .with_timeout(Duration::from_secs(60))
+///     .with_max_states(20)
+///     .with_cancellation(token.clone())
+///     .with_progress(|progress| println!("{} states", progress.states));
+/// ```
+pub struct SproutConfig<'a> {
+    timeout: Option<Duration>,
+    threshold: Option<usize>,
+    max_states: Option<usize>,
+    cancellation: Option<CancellationToken>,
+    progress: Option<Box<dyn FnMut(SproutProgress) + 'a>>,
+}
+
+impl Default for SproutConfig<'_> {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_SPROUT_TIMEOUT),
+            threshold: None,
+            max_states: None,
+            cancellation: None,
+            progress: None,
+        }
+    }
+}
+
+impl<'a> SproutConfig<'a> {
+    /// Gives up once `timeout` has elapsed, see [`SproutError::Timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Never gives up because of the elapsed time.
+    pub fn without_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Uses the given escape prefix threshold instead of the one that is computed from the
+    /// sample, see [`SproutError::Threshold`].
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Gives up once the constructed transition system would need more than `max_states`
+    /// states, see [`SproutError::TooManyStates`]. Note that the learned automaton may have
+    /// an additional sink state, which is added when it is completed.
+    pub fn with_max_states(mut self, max_states: usize) -> Self {
+        self.max_states = Some(max_states);
+        self
+    }
+
+    /// Gives up once `token` is cancelled, see [`SproutError::Cancelled`].
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Calls `progress` every time an escaping word is processed.
+    pub fn with_progress<F: FnMut(SproutProgress) + 'a>(mut self, progress: F) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+impl Debug for SproutConfig<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SproutConfig")
+            .field("timeout", &self.timeout)
+            .field("threshold", &self.threshold)
+            .field("max_states", &self.max_states)
+            .field("cancellation", &self.cancellation)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// The reasons for which [`sprout_with_config`] may give up, each of which carries the
+/// automaton or transition system that was constructed up to that point.
 #[derive(thiserror::Error)]
-pub enum SproutError<A: ConsistencyCheck<WithInitial<DTS>>> {
+pub enum SproutError<C: ConsistencyCheck<WithInitial<DTS<A>>>, A: Alphabet = CharAlphabet> {
     #[error("timeout was exceeded, bailing with ts of size {}", aut.size())]
     Timeout {
-        aut: Automaton<CharAlphabet, WithoutCondition, Void, Void>,
+        aut: Automaton<A, WithoutCondition, Void, Void>,
+    },
+    #[error("escape prefix threshold `{thres}` exceeded, bailing with ts of size {}", aut.size())]
+    Threshold { thres: usize, aut: C::Aut },
+    #[error("state limit `{limit}` reached, bailing with ts of size {}", aut.size())]
+    TooManyStates {
+        limit: usize,
+        aut: Automaton<A, WithoutCondition, Void, Void>,
+    },
+    #[error("cancelled, bailing with ts of size {}", aut.size())]
+    Cancelled {
+        aut: Automaton<A, WithoutCondition, Void, Void>,
     },
-    #[error("escape prefix threshold `{0}` exceeded, bailing with ts of size {}", aut.size())]
-    Threshold { thres: usize, aut: A::Aut },
 }
 
-impl<A: ConsistencyCheck<WithInitial<DTS>>> Debug for SproutError<A> {
+impl<C: ConsistencyCheck<WithInitial<DTS<A>>>, A: Alphabet> Debug for SproutError<C, A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use SproutError::*;
         write!(
@@ -38,20 +176,34 @@ impl<A: ConsistencyCheck<WithInitial<DTS>>> Debug for SproutError<A> {
                     "exceeded threshold {thres} with automaton of size {}",
                     aut.size()
                 ),
+                TooManyStates { limit, aut } => format!("reached state limit {limit}"),
+                Cancelled { aut } => "cancelled".to_string(),
             }
         )
     }
 }
 
 #[allow(type_alias_bounds)]
-pub type SproutResult<A: ConsistencyCheck<WithInitial<DTS>>> = Result<A::Aut, SproutError<A>>;
+pub type SproutResult<C: ConsistencyCheck<WithInitial<DTS<A>>>, A: Alphabet = CharAlphabet> =
+    Result<C::Aut, SproutError<C, A>>;
 
 /// gives a deterministic acc_type omega automaton that is consistent with the given sample
 /// implements the sprout passive learning algorithm for omega automata from <https://arxiv.org/pdf/2108.03735.pdf>
-pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
-    sample: OmegaSample,
-    acc_type: A,
-) -> SproutResult<A> {
+///
+/// Uses the default [`SproutConfig`], see [`sprout_with_config`] for more control.
+pub fn sprout<A: Alphabet, C: ConsistencyCheck<WithInitial<DTS<A>>>>(
+    sample: OmegaSample<A>,
+    acc_type: C,
+) -> SproutResult<C, A> {
+    sprout_with_config(sample, acc_type, SproutConfig::default())
+}
+
+/// Works like [`sprout`], but gives up and reports progress as specified by `config`.
+pub fn sprout_with_config<A: Alphabet, C: ConsistencyCheck<WithInitial<DTS<A>>>>(
+    sample: OmegaSample<A>,
+    acc_type: C,
+    mut config: SproutConfig<'_>,
+) -> SproutResult<C, A> {
     let time_start = std::time::Instant::now();
 
     // make ts with initial state
@@ -62,7 +214,7 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
         .words()
         .map(|w| (w.spoke().len(), w.cycle().len()))
         .fold((0, 0), |(a0, a1), (b0, b1)| (a0.max(b0), a1.max(b1)));
-    let thresh = (lb + le.pow(2) + 1) as isize;
+    let thresh = config.threshold.unwrap_or(lb + le.pow(2) + 1) as isize;
     info!("starting sprout with threshold {thresh}");
 
     // while there are positive sample words that are escaping
@@ -80,14 +232,30 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
         };
 
         trace!("found escape prefix {escape_prefix:?}");
-        // WARN TODO should find a way to either pass or globally set timeout
-        if time_start.elapsed() >= std::time::Duration::from_secs(60 * 30) {
+        if let Some(progress) = config.progress.as_mut() {
+            progress(SproutProgress {
+                states: ts.size(),
+                escaping: mut_sample.count_words(),
+            });
+        }
+        if config
+            .timeout
+            .is_some_and(|timeout| time_start.elapsed() >= timeout)
+        {
             error!(
                 "task exceeded timeout, aborting with automaton of size {}",
                 ts.size()
             );
             return Err(SproutError::Timeout { aut: ts });
         }
+        if config
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            info!("task was cancelled with automaton of size {}", ts.size());
+            return Err(SproutError::Cancelled { aut: ts });
+        }
         // check thresh
         if (escape_prefix.len() as isize) - 2 > thresh {
             error!(
@@ -103,10 +271,11 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
 
         let source = ts.reached_state_index(&escape_prefix).unwrap();
         let sym = escape_prefix.escape_symbol();
+        let expression = sample.alphabet().make_expression(sym);
 
         for q in ts.state_indices_vec() {
             // try adding transition
-            ts.add_edge((source, sym, q));
+            ts.add_edge((source, expression.clone(), q));
             // continue if consistent
             let (is_consistent, pos_sets_new, neg_sets_new) =
                 acc_type.consistent(&ts, &mut_sample, pos_sets.clone(), neg_sets.clone());
@@ -121,8 +290,12 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
             }
         }
         // if none consistent add new state
+        if let Some(limit) = config.max_states.filter(|limit| ts.size() >= *limit) {
+            error!("task reached state limit {limit}, aborting");
+            return Err(SproutError::TooManyStates { limit, aut: ts });
+        }
         let new_state = ts.add_state(Void);
-        ts.add_edge((source, sym, new_state));
+        ts.add_edge((source, expression, new_state));
     }
 
     info!(
@@ -132,11 +305,11 @@ pub fn sprout<A: ConsistencyCheck<WithInitial<DTS>>>(
     Ok(acc_type.consistent_automaton(&ts, &mut_sample, pos_sets, neg_sets))
 }
 
-impl OmegaSample {
+impl<A: Alphabet> OmegaSample<A> {
     /// Remove all words from the sample that are not escaping in the given transition system
     pub fn remove_non_escaping<T>(&mut self, ts: &T)
     where
-        T: TransitionSystem<Alphabet = CharAlphabet, StateIndex = u32> + Deterministic + Pointed,
+        T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
         // <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
     {
         // run transition system on sample words and
@@ -153,8 +326,12 @@ mod tests {
     use super::*;
     use crate::passive::OmegaSample;
     use automata::DTS;
-    use automata::automaton::{BuchiCondition, MinEvenParityCondition};
+    use automata::automaton::{
+        BuchiCondition, CoBuchiCondition, MinEvenParityCondition, MullerCondition, RabinCondition,
+        StreettCondition,
+    };
     use automata::core::alphabet::CharAlphabet;
+    use automata::core::word::ReducedOmegaWord;
     use automata::core::{Void, upw};
     use automata::ts::Sproutable;
     use std::collections::HashSet;
@@ -291,4 +468,133 @@ mod tests {
         };
         assert_eq!(res, dpa);
     }
+
+    #[test]
+    fn sprout_rabin_streett_cobuchi_muller() {
+        let sigma = CharAlphabet::of_size(2);
+        let separates =
+            |sample: &OmegaSample, accepts: &dyn Fn(&ReducedOmegaWord<char>) -> bool| {
+                sample.positive_words().all(accepts) && !sample.negative_words().any(accepts)
+            };
+
+        // finitely many `a` or finitely many `b`
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            sigma.clone(),
+            [upw!("a"), upw!("b"), upw!("ab", "b")],
+            [upw!("ab"), upw!("aab")],
+        );
+        let dra = sprout(sample.clone(), RabinCondition::default()).unwrap();
+        assert!(separates(&sample, &|w| dra.accepts(w)));
+        let dma = sprout(sample.clone(), MullerCondition::default()).unwrap();
+        assert!(separates(&sample, &|w| dma.accepts(w)));
+
+        // infinitely many `a` and infinitely many `b`
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            sigma.clone(),
+            [upw!("ab"), upw!("aab")],
+            [upw!("a"), upw!("b"), upw!("ab", "b")],
+        );
+        let dsa = sprout(sample.clone(), StreettCondition::default()).unwrap();
+        assert!(separates(&sample, &|w| dsa.accepts(w)));
+
+        // finitely many `b`
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            sigma,
+            [upw!("a"), upw!("b", "a"), upw!("bab", "a")],
+            [upw!("b"), upw!("ab"), upw!("a", "b")],
+        );
+        let dca = sprout(sample.clone(), CoBuchiCondition).unwrap();
+        assert!(separates(&sample, &|w| dca.accepts(w)));
+    }
+
+    #[test]
+    fn sprout_propositional() {
+        use automata::core::alphabet::PropAlphabet;
+        use automata::hoa::{WriteHoa, input::hoa_to_ts};
+
+        let sigma = PropAlphabet::from_apnames(["p"]);
+        let (p, not_p) = (sigma.char_to_symbol('b'), sigma.char_to_symbol('a'));
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            sigma,
+            [
+                ReducedOmegaWord::periodic([p]),
+                ReducedOmegaWord::periodic([not_p, p]),
+            ],
+            [ReducedOmegaWord::periodic([not_p])],
+        );
+
+        let dba = sprout(sample.clone(), BuchiCondition).unwrap();
+        let hoa = dba.to_hoa();
+        assert!(hoa.contains("AP: 1 \"p\""));
+        let [parsed] = hoa_to_ts::<true>(&hoa).unwrap().try_into().unwrap();
+        let parsed = parsed.try_into_deterministic().unwrap().into_dpa();
+        for pos in sample.positive_words() {
+            assert!(dba.accepts(pos));
+            assert!(parsed.accepts(pos));
+        }
+        for neg in sample.negative_words() {
+            assert!(!dba.accepts(neg));
+            assert!(!parsed.accepts(neg));
+        }
+    }
+
+    #[test]
+    fn sprout_config_limits() {
+        let sigma = CharAlphabet::of_size(2);
+        let sample = OmegaSample::new_omega_from_pos_neg(
+            sigma,
+            [upw!("a"), upw!("aab")],
+            [upw!("b"), upw!("abb")],
+        );
+
+        let mut reported = vec![];
+        let res = sprout_with_config(
+            sample.clone(),
+            MinEvenParityCondition,
+            SproutConfig::default().with_progress(|progress| reported.push(progress)),
+        );
+        assert_eq!(res.unwrap().size(), 3);
+        assert_eq!(
+            reported.first(),
+            Some(&SproutProgress {
+                states: 1,
+                escaping: 4
+            })
+        );
+        assert!(reported.windows(2).all(|w| w[0].escaping >= w[1].escaping));
+
+        // the learned automaton is completed with a sink, which is not counted
+        assert!(
+            sprout_with_config(
+                sample.clone(),
+                MinEvenParityCondition,
+                SproutConfig::default().with_max_states(2),
+            )
+            .is_ok()
+        );
+        let Err(SproutError::TooManyStates { limit: 1, aut }) = sprout_with_config(
+            sample.clone(),
+            MinEvenParityCondition,
+            SproutConfig::default().with_max_states(1),
+        ) else {
+            panic!("expected to reach the state limit");
+        };
+        assert_eq!(aut.size(), 1);
+
+        let token = CancellationToken::new();
+        token.clone().cancel();
+        let res = sprout_with_config(
+            sample.clone(),
+            MinEvenParityCondition,
+            SproutConfig::default().with_cancellation(token),
+        );
+        assert!(matches!(res, Err(SproutError::Cancelled { .. })));
+
+        let res = sprout_with_config(
+            sample,
+            MinEvenParityCondition,
+            SproutConfig::default().with_threshold(0),
+        );
+        assert!(matches!(res, Err(SproutError::Threshold { thres: 0, .. })));
+    }
 }
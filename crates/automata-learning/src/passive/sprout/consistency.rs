@@ -4,68 +4,76 @@ use std::iter;
 use std::ops::Not;
 
 use crate::prefixtree::prefix_tree;
-use automata::automaton::{BuchiCondition, DBA, DPA, MinEvenParityCondition};
-use automata::core::Void;
-use automata::core::alphabet::{Alphabet, CharAlphabet};
+use automata::automaton::{
+    BuchiCondition, CoBuchiCondition, DBA, DCBA, DMA, DPA, DRA, DSA, MinEvenParityCondition,
+    MullerCondition, RabinCondition, RabinPair, StreettCondition,
+};
+use automata::core::alphabet::{Alphabet, Symbol};
 use automata::core::math::OrderedSet;
+use automata::core::{Color, Int, Void};
 use automata::representation::CollectTs;
 use automata::ts::run::InfiniteRunOutput::Successful;
-use automata::ts::{Deterministic, EdgeColor, Sproutable, run};
+use automata::ts::{
+    DefaultIdType, Deterministic, EdgeColor, IndexedAlphabet, Sproutable, SymbolOf, run,
+};
 use automata::{
-    Pointed, TransitionSystem,
+    DTS, Pointed, TransitionSystem,
     ts::path::{self, LassoIn},
 };
 
 use super::OmegaSample;
 
-type EdgeSet = OrderedSet<(u32, char)>;
+/// A set of transitions, each identified by its source state and symbol.
+type EdgeSet<S = char> = OrderedSet<(u32, S)>;
 
 /// Used to define consistency checks on various types of omega acceptance conditions
 /// required by the sprout algorithm for passively learning omega automata
-pub trait ConsistencyCheck<T> {
+pub trait ConsistencyCheck<T: TransitionSystem> {
     /// the type of the automaton to be returned
     type Aut: TransitionSystem;
     /// Checks if the given transition system is consistent with the sample
+    #[allow(clippy::type_complexity)]
     fn consistent(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
-    ) -> (bool, Vec<EdgeSet>, Vec<EdgeSet>);
+        sample: &OmegaSample<T::Alphabet>,
+        pos_sets: Vec<EdgeSet<SymbolOf<T>>>,
+        neg_sets: Vec<EdgeSet<SymbolOf<T>>>,
+    ) -> (bool, Vec<EdgeSet<SymbolOf<T>>>, Vec<EdgeSet<SymbolOf<T>>>);
     /// If the transition system is consistent with the sample,
     /// returns an automaton with underlying transition system ts
     /// that is consistent with the sample
     fn consistent_automaton(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
+        sample: &OmegaSample<T::Alphabet>,
+        pos_sets: Vec<EdgeSet<SymbolOf<T>>>,
+        neg_sets: Vec<EdgeSet<SymbolOf<T>>>,
     ) -> Self::Aut;
     /// Automaton that accepts precisely the positive example words
     /// in case no other solution can be found
-    fn default_automaton(&self, sample: &OmegaSample) -> Self::Aut;
+    fn default_automaton(&self, sample: &OmegaSample<T::Alphabet>) -> Self::Aut;
 }
 
-impl<T> ConsistencyCheck<T> for BuchiCondition
+impl<A, T> ConsistencyCheck<T> for BuchiCondition
 where
-    T: TransitionSystem<Alphabet = CharAlphabet, StateIndex = u32> + Deterministic + Pointed,
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
     <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
 {
-    type Aut = DBA;
+    type Aut = DBA<A>;
     fn consistent(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
-    ) -> (bool, Vec<EdgeSet>, Vec<EdgeSet>) {
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
         if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
             // check if the infinity set of a positive word is subset of
             // the union of all infinity sets of negative words (see paper for details)
             neg_sets_new.extend(neg_sets);
-            let neg_union: EdgeSet = neg_sets_new.iter().flatten().cloned().collect();
+            let neg_union: EdgeSet<_> = neg_sets_new.iter().flatten().cloned().collect();
             pos_sets_new.extend(pos_sets);
 
             let is_consistent = pos_sets_new.iter().any(|s| s.is_subset(&neg_union)).not();
@@ -79,9 +87,9 @@ where
     fn consistent_automaton(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
     ) -> Self::Aut {
         // check consistency
         assert!(self.consistent(ts, sample, pos_sets, neg_sets.clone()).0);
@@ -92,18 +100,18 @@ where
             to_infinity_sets(ts, sample).expect("ts cannot be consistent with sample");
         neg_sets_new.extend(neg_sets);
 
-        let neg_union: EdgeSet = neg_sets_new.iter().flatten().cloned().collect();
+        let neg_union: EdgeSet<_> = neg_sets_new.iter().flatten().cloned().collect();
 
-        let all_transitions: EdgeSet = ts
-            .state_indices()
-            .cartesian_product(ts.alphabet().universe())
-            .collect();
+        let all_transitions = all_transitions(ts);
 
-        let accepting: EdgeSet = all_transitions.difference(&neg_union).cloned().collect();
+        let accepting: EdgeSet<_> = all_transitions.difference(&neg_union).cloned().collect();
 
         // make DBA
+        let alphabet = ts.alphabet().clone();
         let mut dba = ts
-            .map_edge_colors_full(move |a, b, _, _| accepting.contains(&(a, *b)))
+            .map_edge_colors_full(move |a, b, _, _| {
+                accepting.contains(&(a, alphabet.expression_to_symbol(b)))
+            })
             .erase_state_colors()
             .collect_dba();
 
@@ -112,7 +120,7 @@ where
         dba
     }
 
-    fn default_automaton(&self, sample: &OmegaSample) -> Self::Aut {
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
         let mut dba = prefix_tree(sample.alphabet().clone(), sample.positive_words())
             .map_edge_colors(|_| true)
             .erase_state_colors()
@@ -123,28 +131,26 @@ where
     }
 }
 
-impl<T> ConsistencyCheck<T> for MinEvenParityCondition
+impl<A, T> ConsistencyCheck<T> for MinEvenParityCondition
 where
-    T: TransitionSystem<Alphabet = CharAlphabet, StateIndex = u32> + Deterministic + Pointed,
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
     <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
 {
-    type Aut = DPA;
+    type Aut = DPA<A>;
 
     fn consistent(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
-    ) -> (bool, Vec<EdgeSet>, Vec<EdgeSet>) {
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
         if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
             pos_sets_new.extend(pos_sets);
             neg_sets_new.extend(neg_sets);
             // check how set with all transitions should be handled
-            let all_transitions: EdgeSet = ts
-                .state_indices()
-                .cartesian_product(ts.alphabet().universe())
-                .collect();
+            let all_transitions = all_transitions(ts);
             let is_consistent = match (
                 pos_sets_new.contains(&all_transitions),
                 neg_sets_new.contains(&all_transitions),
@@ -178,9 +184,9 @@ where
     fn consistent_automaton(
         &self,
         ts: &T,
-        sample: &OmegaSample,
-        pos_sets: Vec<EdgeSet>,
-        neg_sets: Vec<EdgeSet>,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
     ) -> Self::Aut {
         // check consistency
         assert!(
@@ -194,11 +200,8 @@ where
         pos_sets_new.extend(pos_sets);
         neg_sets_new.extend(neg_sets);
 
-        let all_transitions: EdgeSet = ts
-            .state_indices()
-            .cartesian_product(ts.alphabet().universe())
-            .collect();
-        let z_path: Vec<EdgeSet>;
+        let all_transitions = all_transitions(ts);
+        let z_path: Vec<EdgeSet<_>>;
         let lowest: bool;
         match (
             pos_sets_new.contains(&all_transitions),
@@ -246,7 +249,7 @@ where
         }
         // build dpa from Zielonka path
 
-        let mut prio_map: HashMap<(u32, char), u8> = HashMap::new();
+        let mut prio_map: HashMap<(u32, A::Symbol), u8> = HashMap::new();
         let mut prio = if lowest { 0 } else { 1 };
         for i in 0..z_path.len() - 1 {
             for t in z_path[i].difference(&z_path[i + 1]) {
@@ -254,10 +257,11 @@ where
             }
             prio += 1;
         }
+        let alphabet = ts.alphabet().clone();
         let mut dpa = ts
             .map_edge_colors_full(move |a, b, c, d| {
                 *prio_map
-                    .get(&(a, *b))
+                    .get(&(a, alphabet.expression_to_symbol(b)))
                     .expect("transition missing in Zielonka path")
             })
             .erase_state_colors()
@@ -269,7 +273,7 @@ where
         dpa
     }
 
-    fn default_automaton(&self, sample: &OmegaSample) -> Self::Aut {
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
         let mut dpa = prefix_tree(sample.alphabet().clone(), sample.positive_words())
             .map_edge_colors(|_| 0)
             .erase_state_colors()
@@ -280,12 +284,356 @@ where
     }
 }
 
+/// Learns a [`DRA`] whose edges are colored with the index of their transition. Since the
+/// sets of transitions that are rejected by a Rabin condition are closed under union, the
+/// transition system is consistent with the sample if and only if no positive infinity set
+/// is the union of the negative infinity sets that it contains. The value of `self` is not
+/// used, so `RabinCondition::default()` may be passed.
+impl<A, T> ConsistencyCheck<T> for RabinCondition<usize>
+where
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
+    <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
+{
+    type Aut = DRA<A>;
+
+    fn consistent(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
+        if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
+            pos_sets_new.extend(pos_sets);
+            neg_sets_new.extend(neg_sets);
+            let is_consistent = pos_sets_new
+                .iter()
+                .all(|s| !is_union_of_subsets(s, &neg_sets_new));
+            (is_consistent, pos_sets_new, neg_sets_new)
+        } else {
+            // bad pair was found when running sample words on transition system
+            (false, pos_sets, neg_sets)
+        }
+    }
+
+    fn consistent_automaton(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> Self::Aut {
+        // check consistency
+        let (is_consistent, pos_sets, neg_sets) = self.consistent(ts, sample, pos_sets, neg_sets);
+        assert!(is_consistent);
+
+        // every positive set gets a pair, which forbids all transitions outside of it and
+        // demands one of its transitions that no negative set contained in it covers
+        let all_transitions = all_transitions(ts);
+        let index = transition_indices(&all_transitions);
+        let condition: RabinCondition<usize> = pos_sets
+            .iter()
+            .unique()
+            .map(|s| {
+                let covered = union_of_subsets(s, &neg_sets);
+                RabinPair::from_iters(
+                    all_transitions.difference(s).map(|t| index[t]),
+                    s.difference(&covered).map(|t| index[t]),
+                )
+            })
+            .into();
+
+        let (dts, initial) = color_transitions(ts, Void, |t| index[&t]);
+        let mut dra = DRA::from_parts_with_acceptance(dts, initial, condition);
+
+        // complete with sink state, whose color appears in no pair
+        dra.complete_with_colors(Void, all_transitions.len());
+        dra
+    }
+
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
+        let (dts, initial) = prefix_tree(sample.alphabet().clone(), sample.positive_words())
+            .map_edge_colors(|_| 0)
+            .erase_state_colors()
+            .with_initial(0)
+            .collect_dts_and_initial();
+        let condition = [RabinPair::from_iters([], [0])].into();
+        let mut dra = DRA::from_parts_with_acceptance(dts, initial, condition);
+        dra.complete_with_colors(Void, 1);
+        dra
+    }
+}
+
+/// Learns a [`DMA`] whose edges are colored with the index of their transition and whose
+/// condition consists of precisely the positive infinity sets. So the transition system is
+/// consistent with the sample if and only if no infinity set is both positive and negative.
+/// The value of `self` is not used, so `MullerCondition::default()` may be passed.
+impl<A, T> ConsistencyCheck<T> for MullerCondition<usize>
+where
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
+    <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
+{
+    type Aut = DMA<A>;
+
+    fn consistent(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
+        if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
+            pos_sets_new.extend(pos_sets);
+            neg_sets_new.extend(neg_sets);
+            let is_consistent = pos_sets_new.iter().all(|s| !neg_sets_new.contains(s));
+            (is_consistent, pos_sets_new, neg_sets_new)
+        } else {
+            // bad pair was found when running sample words on transition system
+            (false, pos_sets, neg_sets)
+        }
+    }
+
+    fn consistent_automaton(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> Self::Aut {
+        // check consistency
+        let (is_consistent, pos_sets, _) = self.consistent(ts, sample, pos_sets, neg_sets);
+        assert!(is_consistent);
+
+        let all_transitions = all_transitions(ts);
+        let index = transition_indices(&all_transitions);
+        let condition = MullerCondition::from_iter_iter(
+            pos_sets
+                .iter()
+                .unique()
+                .map(|s| s.iter().map(|t| index[t]).collect_vec()),
+        );
+
+        let (dts, initial) = color_transitions(ts, Void, |t| index[&t]);
+        let mut dma = DMA::from_parts_with_acceptance(dts, initial, condition);
+
+        // complete with sink state, whose color appears in no accepting set
+        dma.complete_with_colors(Void, all_transitions.len());
+        dma
+    }
+
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
+        let (dts, initial) = prefix_tree(sample.alphabet().clone(), sample.positive_words())
+            .map_edge_colors(|_| 0)
+            .erase_state_colors()
+            .with_initial(0)
+            .collect_dts_and_initial();
+        let condition = MullerCondition::from_iter_iter([[0]]);
+        let mut dma = DMA::from_parts_with_acceptance(dts, initial, condition);
+        dma.complete_with_colors(Void, 1);
+        dma
+    }
+}
+
+/// Learns a [`DCBA`] that marks the transitions which are not contained in any positive
+/// infinity set. So the transition system is consistent with the sample if and only if no
+/// negative infinity set is a subset of the union of the positive ones.
+impl<A, T> ConsistencyCheck<T> for CoBuchiCondition
+where
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
+    <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
+{
+    type Aut = DCBA<A>;
+
+    fn consistent(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
+        if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
+            pos_sets_new.extend(pos_sets);
+            neg_sets_new.extend(neg_sets);
+            let pos_union: EdgeSet<_> = pos_sets_new.iter().flatten().cloned().collect();
+            let is_consistent = neg_sets_new.iter().all(|s| !s.is_subset(&pos_union));
+            (is_consistent, pos_sets_new, neg_sets_new)
+        } else {
+            // bad pair was found when running sample words on transition system
+            (false, pos_sets, neg_sets)
+        }
+    }
+
+    fn consistent_automaton(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> Self::Aut {
+        // check consistency
+        let (is_consistent, pos_sets, _) = self.consistent(ts, sample, pos_sets, neg_sets);
+        assert!(is_consistent);
+
+        // mark all transitions besides the union of positive infinity sets
+        let pos_union: EdgeSet<_> = pos_sets.iter().flatten().cloned().collect();
+        let (dts, initial) = color_transitions(ts, Void, |t| !pos_union.contains(&t));
+        let mut dcba = DCBA::from_parts(dts, initial);
+
+        // complete with sink state
+        dcba.complete_with_colors(Void, true);
+        dcba
+    }
+
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
+        let (dts, initial) = prefix_tree(sample.alphabet().clone(), sample.positive_words())
+            .map_edge_colors(|_| false)
+            .erase_state_colors()
+            .with_initial(0)
+            .collect_dts_and_initial();
+        let mut dcba = DCBA::from_parts(dts, initial);
+        dcba.complete_with_colors(Void, true);
+        dcba
+    }
+}
+
+/// Learns a [`DSA`] whose edges are colored with the index of their transition. Dually to
+/// [`RabinCondition`], the sets of transitions that are accepted by a Streett condition are
+/// closed under union. Thus the transition system is consistent with the sample if and only
+/// if no negative infinity set is the union of the positive infinity sets that it contains.
+/// The value of `self` is not used, so `StreettCondition::default()` may be passed.
+impl<A, T> ConsistencyCheck<T> for StreettCondition<usize>
+where
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
+    <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
+{
+    type Aut = DSA<A>;
+
+    fn consistent(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> (bool, Vec<EdgeSet<A::Symbol>>, Vec<EdgeSet<A::Symbol>>) {
+        if let Some([mut pos_sets_new, mut neg_sets_new]) = to_infinity_sets(ts, sample) {
+            pos_sets_new.extend(pos_sets);
+            neg_sets_new.extend(neg_sets);
+            let is_consistent = neg_sets_new
+                .iter()
+                .all(|s| !is_union_of_subsets(s, &pos_sets_new));
+            (is_consistent, pos_sets_new, neg_sets_new)
+        } else {
+            // bad pair was found when running sample words on transition system
+            (false, pos_sets, neg_sets)
+        }
+    }
+
+    fn consistent_automaton(
+        &self,
+        ts: &T,
+        sample: &OmegaSample<A>,
+        pos_sets: Vec<EdgeSet<A::Symbol>>,
+        neg_sets: Vec<EdgeSet<A::Symbol>>,
+    ) -> Self::Aut {
+        // check consistency
+        let (is_consistent, pos_sets, neg_sets) = self.consistent(ts, sample, pos_sets, neg_sets);
+        assert!(is_consistent);
+
+        // every negative set gets a pair, whose satisfaction leads to rejection, which
+        // forbids all transitions outside of it and demands one of its transitions that no
+        // positive set contained in it covers
+        let all_transitions = all_transitions(ts);
+        let index = transition_indices(&all_transitions);
+        let sink = all_transitions.len();
+        let condition: StreettCondition<usize> = neg_sets
+            .iter()
+            .unique()
+            .map(|s| {
+                let covered = union_of_subsets(s, &pos_sets);
+                RabinPair::from_iters(
+                    all_transitions.difference(s).map(|t| index[t]),
+                    s.difference(&covered).map(|t| index[t]),
+                )
+            })
+            // the sink satisfies an additional pair
+            .chain([RabinPair::from_iters([], [sink])])
+            .into();
+
+        let (dts, initial) = color_transitions(ts, Void, |t| index[&t]);
+        let mut dsa = DSA::from_parts_with_acceptance(dts, initial, condition);
+
+        // complete with sink state
+        dsa.complete_with_colors(Void, sink);
+        dsa
+    }
+
+    fn default_automaton(&self, sample: &OmegaSample<A>) -> Self::Aut {
+        let (dts, initial) = prefix_tree(sample.alphabet().clone(), sample.positive_words())
+            .map_edge_colors(|_| 0)
+            .erase_state_colors()
+            .with_initial(0)
+            .collect_dts_and_initial();
+        let condition = [RabinPair::from_iters([], [1])].into();
+        let mut dsa = DSA::from_parts_with_acceptance(dts, initial, condition);
+        dsa.complete_with_colors(Void, 1);
+        dsa
+    }
+}
+
+/// Collects the union of all sets in `sets` that are a subset of `set`.
+fn union_of_subsets<S: Symbol>(set: &EdgeSet<S>, sets: &[EdgeSet<S>]) -> EdgeSet<S> {
+    sets.iter()
+        .filter(|s| s.is_subset(set))
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// Checks if `set` is the union of the sets in `sets` that are a subset of it.
+fn is_union_of_subsets<S: Symbol>(set: &EdgeSet<S>, sets: &[EdgeSet<S>]) -> bool {
+    union_of_subsets(set, sets) == *set
+}
+
+/// Associates each of the given transitions with its position.
+fn transition_indices<S: Symbol>(transitions: &EdgeSet<S>) -> HashMap<(u32, S), usize> {
+    transitions
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (*t, i))
+        .collect()
+}
+
+/// Collects the given transition system into a [`DTS`] in which every state has the color
+/// `state_color` and the edge of every transition `(q, a)` has the color `color((q, a))`.
+/// Returns the [`DTS`] together with its initial state.
+fn color_transitions<A, T, Q, C, F>(
+    ts: &T,
+    state_color: Q,
+    color: F,
+) -> (DTS<A, Q, C>, DefaultIdType)
+where
+    A: IndexedAlphabet,
+    T: TransitionSystem<Alphabet = A, StateIndex = u32> + Deterministic + Pointed,
+    Q: Color,
+    C: Color,
+    F: Fn((u32, A::Symbol)) -> C,
+{
+    let alphabet = ts.alphabet().clone();
+    ts.map_edge_colors_full(move |q, e, _, _| color((q, alphabet.expression_to_symbol(e))))
+        .map_state_colors(move |_| state_color.clone())
+        .collect_dts_and_initial()
+}
+
 /// Check if it is possible to construct a valid zielonka path from the given classified sets.
 /// `class` is the classification to use for the set of all transitions.
-fn has_zielonka_path(
-    pos_sets: &Vec<EdgeSet>,
-    neg_sets: &Vec<EdgeSet>,
-    all_transitions: &EdgeSet,
+fn has_zielonka_path<S: Symbol>(
+    pos_sets: &Vec<EdgeSet<S>>,
+    neg_sets: &Vec<EdgeSet<S>>,
+    all_transitions: &EdgeSet<S>,
     mut class: bool,
 ) -> bool {
     // check if class of set with all transitions is valid
@@ -300,7 +648,7 @@ fn has_zielonka_path(
     let mut z = all_transitions.clone();
     while !z.is_empty() {
         // set new Z to union of subsets with different classification
-        let z_new: EdgeSet = if class {
+        let z_new: EdgeSet<S> = if class {
             // Z accepting
             neg_sets.retain(|s| s.is_subset(&z));
             neg_sets.iter().flatten().cloned().collect()
@@ -321,12 +669,12 @@ fn has_zielonka_path(
 /// For given sets compute Zielonka path consistent with given classification.
 /// `class` is the classification of the set of all transitions
 /// returns `None` if no consistent Zielonka path exists
-fn zielonka_path(
-    mut pos_sets: Vec<EdgeSet>,
-    mut neg_sets: Vec<EdgeSet>,
-    all_transitions: EdgeSet,
+fn zielonka_path<S: Symbol>(
+    mut pos_sets: Vec<EdgeSet<S>>,
+    mut neg_sets: Vec<EdgeSet<S>>,
+    all_transitions: EdgeSet<S>,
     mut class: bool,
-) -> Option<Vec<EdgeSet>> {
+) -> Option<Vec<EdgeSet<S>>> {
     // check if class of set with all transitions is valid
     if class {
         assert!(!neg_sets.contains(&all_transitions));
@@ -338,7 +686,7 @@ fn zielonka_path(
     let mut i = 0;
     while !z_path[i].is_empty() {
         // set new Z to union of subsets with different classification
-        let z_new: EdgeSet = if class {
+        let z_new: EdgeSet<S> = if class {
             // Z accepting
             neg_sets.retain(|s| s.is_subset(&z_path[i]));
             neg_sets.iter().flatten().cloned().collect()
@@ -357,13 +705,24 @@ fn zielonka_path(
     Some(z_path)
 }
 
+/// Collects the transitions of the given transition system, one for each pair of a state
+/// and a symbol of its alphabet.
+fn all_transitions<T: TransitionSystem<StateIndex = u32>>(ts: &T) -> EdgeSet<SymbolOf<T>> {
+    ts.state_indices()
+        .flat_map(|q| ts.alphabet().universe().map(move |a| (q, a)))
+        .collect()
+}
+
 use run::InfiniteRunOutput::*;
 /// Run positive and negative sample words on the given transition system.
 /// If there is a pair of words escaping with the same escape string from the same state, return None.
 /// Otherwise return non-escaping runs of positive and negative example words
-fn to_infinity_sets<T>(ts: T, sample: &OmegaSample) -> Option<[Vec<EdgeSet>; 2]>
+fn to_infinity_sets<T>(
+    ts: T,
+    sample: &OmegaSample<T::Alphabet>,
+) -> Option<[Vec<EdgeSet<SymbolOf<T>>>; 2]>
 where
-    T: TransitionSystem<Alphabet = CharAlphabet, StateIndex = u32> + Deterministic + Pointed,
+    T: TransitionSystem<StateIndex = u32> + Deterministic + Pointed,
     <T as TransitionSystem>::EdgeColor: Eq + std::hash::Hash,
 {
     let time_start = std::time::Instant::now();
@@ -438,7 +797,9 @@ where
 mod tests {
     use super::*;
     use crate::passive::OmegaSample;
-    use automata::automaton::{BuchiCondition, MinEvenParityCondition, WithInitial};
+    use automata::automaton::{
+        BuchiCondition, MinEvenParityCondition, MullerCondition, RabinCondition, WithInitial,
+    };
     use automata::core::alphabet::CharAlphabet;
     use automata::core::{Void, upw};
     use automata::{
@@ -573,6 +934,53 @@ mod tests {
         assert_eq!(res2, dba);
     }
 
+    #[test]
+    fn rabin_streett_cobuchi_muller_consistency() {
+        let ts = DTS::builder()
+            .with_transitions([(0, 'a', Void, 0), (0, 'b', Void, 0)])
+            .default_color(Void)
+            .into_dts_with_initial(0);
+        // the negative infinity set is the union of the positive ones
+        let sample =
+            OmegaSample::new_omega_from_pos_neg(sigma(), [upw!("a"), upw!("b")], [upw!("ab")]);
+        let dual =
+            OmegaSample::new_omega_from_pos_neg(sigma(), [upw!("ab")], [upw!("a"), upw!("b")]);
+
+        assert!(
+            RabinCondition::default()
+                .consistent(&ts, &sample, vec![], vec![])
+                .0
+        );
+        assert!(
+            !RabinCondition::default()
+                .consistent(&ts, &dual, vec![], vec![])
+                .0
+        );
+        let streett = StreettCondition::default();
+        assert!(!streett.consistent(&ts, &sample, vec![], vec![]).0);
+        assert!(streett.consistent(&ts, &dual, vec![], vec![]).0);
+        // the negative infinity sets are contained in the union of the positive ones
+        let co_buchi = CoBuchiCondition;
+        assert!(!co_buchi.consistent(&ts, &sample, vec![], vec![]).0);
+        assert!(!co_buchi.consistent(&ts, &dual, vec![], vec![]).0);
+        let sample2 = OmegaSample::new_omega_from_pos_neg(sigma(), [upw!("a")], [upw!("ab")]);
+        assert!(co_buchi.consistent(&ts, &sample2, vec![], vec![]).0);
+        for sample in [&sample, &dual] {
+            assert!(
+                MullerCondition::default()
+                    .consistent(&ts, sample, vec![], vec![])
+                    .0
+            );
+        }
+
+        let sample = OmegaSample::new_omega_from_pos_neg(sigma(), [upw!("a")], [upw!("a")]);
+        assert!(
+            !MullerCondition::default()
+                .consistent(&ts, &sample, vec![], vec![])
+                .0
+        );
+    }
+
     #[test]
     fn parity_consistency() {
         // build transition systems
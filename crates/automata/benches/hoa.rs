@@ -0,0 +1,47 @@
+use automata::hoa::{HoaAutomatonStream, HoaString};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+const AUTOMATON: &str = r#"HOA: v1
+States: 2
+Start: 0
+AP: 1 "a"
+acc-name: Buchi
+Acceptance: 1 Inf(0)
+--BODY--
+State: 0
+[0] 1 {0}
+[!0] 0
+State: 1
+[t] 0
+--END--
+"#;
+
+/// Popping from a [`HoaString`] and streaming should both be linear in the number of
+/// automata, so the throughput is expected to be the same for all sizes.
+fn read_many_automata(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hoa_input");
+    for count in [10, 100, 1000] {
+        let input = AUTOMATON.repeat(count);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("pop", count), &input, |b, input| {
+            b.iter(|| {
+                let mut hoa = HoaString::from(input.clone());
+                let mut read = 0;
+                while hoa.pop::<true>().is_some() {
+                    read += 1;
+                }
+                assert_eq!(read, count);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("stream", count), &input, |b, input| {
+            b.iter(|| {
+                let stream = HoaAutomatonStream::<_, true>::new(input.as_bytes());
+                assert_eq!(stream.filter(Result::is_ok).count(), count);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, read_many_automata);
+criterion_main!(benches);
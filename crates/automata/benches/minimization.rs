@@ -0,0 +1,64 @@
+use automata::TransitionSystem;
+use automata::automaton::{DFA, MealyMachine};
+use automata::core::Int;
+use automata::minimization::partition_refinement::{
+    mealy_greatest_bisimulation, mealy_hopcroft_bisimulation, moore_greatest_bisimulation,
+    moore_hopcroft_bisimulation,
+};
+use automata::random::generate_random_ts_sized;
+use automata::representation::CollectTs;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::{Rng, thread_rng};
+
+fn random_dfa(size: usize) -> DFA {
+    let (ts, initial) = generate_random_ts_sized(2, size);
+    ts.map_state_colors(|_| thread_rng().gen_bool(0.5))
+        .with_initial(initial)
+        .collect_dfa()
+}
+
+fn random_mealy(size: usize) -> MealyMachine {
+    let (ts, initial) = generate_random_ts_sized(2, size);
+    ts.map_edge_colors(|_| thread_rng().gen_range(0..2) as Int)
+        .with_initial(initial)
+        .collect_mealy()
+}
+
+fn hopcroft(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hopcroft");
+    group.sample_size(10);
+    for size in [1_000, 10_000, 100_000] {
+        let dfa = random_dfa(size);
+        group.bench_with_input(BenchmarkId::new("moore", size), &dfa, |b, dfa| {
+            b.iter(|| moore_hopcroft_bisimulation(dfa))
+        });
+        let mealy = random_mealy(size);
+        group.bench_with_input(BenchmarkId::new("mealy", size), &mealy, |b, mealy| {
+            b.iter(|| mealy_hopcroft_bisimulation(mealy))
+        });
+        group.bench_with_input(BenchmarkId::new("dfa_minimize", size), &dfa, |b, dfa| {
+            b.iter(|| dfa.clone().minimize())
+        });
+    }
+    group.finish();
+}
+
+fn naive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("naive");
+    group.sample_size(10);
+    // the naive refinement is quadratic, so we only compare on small automata
+    for size in [100, 1_000] {
+        let dfa = random_dfa(size);
+        group.bench_with_input(BenchmarkId::new("moore", size), &dfa, |b, dfa| {
+            b.iter(|| moore_greatest_bisimulation(dfa))
+        });
+        let mealy = random_mealy(size);
+        group.bench_with_input(BenchmarkId::new("mealy", size), &mealy, |b, mealy| {
+            b.iter(|| mealy_greatest_bisimulation(mealy))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hopcroft, naive);
+criterion_main!(benches);
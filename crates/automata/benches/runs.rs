@@ -0,0 +1,35 @@
+use automata::core::alphabet::CharAlphabet;
+use automata::random::{generate_random_omega_words, generate_random_ts_sized};
+use automata::ts::packed::{Id, Packed};
+use automata::ts::{Deterministic, ScalarIndexType};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+fn recurrent_states(c: &mut Criterion) {
+    let mut group = c.benchmark_group("recurrent_state_indices");
+    let words: Vec<_> = generate_random_omega_words(&CharAlphabet::of_size(2), 4, 32, 4, 32, 200)
+        .into_iter()
+        .collect();
+    for size in [4, 64, 1024] {
+        let (dts, _) = generate_random_ts_sized(2, size);
+        let packed = Packed::from(&dts);
+
+        group.bench_with_input(BenchmarkId::new("dts", size), &dts, |b, dts| {
+            b.iter(|| {
+                for (i, word) in words.iter().enumerate() {
+                    dts.recurrent_state_indices_from(ScalarIndexType::from_usize(i % size), word);
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("packed", size), &packed, |b, packed| {
+            b.iter(|| {
+                for (i, word) in words.iter().enumerate() {
+                    packed.recurrent_state_indices_from(Id(i % size), word);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, recurrent_states);
+criterion_main!(benches);
@@ -19,13 +19,17 @@ pub use reachability::{DFA, IntoDFA, ReachabilityCondition};
 
 mod omega;
 pub use omega::{
-    AcceptanceMask, BuchiCondition, DBA, DMA, DPA, DRA, DeterministicOmegaAutomaton, IntoDBA,
-    IntoDMA, IntoDPA, IntoDRA, MaxEvenParityCondition, MaxOddParityCondition,
-    MinEvenParityCondition, MinOddParityCondition, MullerCondition, NondeterministicOmegaAutomaton,
-    OmegaAcceptanceCondition, OmegaAutomaton, RabinCondition, RabinPair,
+    AcceptanceMask, AlternatingEdge, AlternatingOmegaAutomaton, AlternationError, BuchiCondition,
+    CoBuchiCondition, DBA, DCBA, DELA, DMA, DPA, DRA, DSA, DeterministicOmegaAutomaton,
+    DeterminizationError, EmersonLeiAtom, EmersonLeiClass, EmersonLeiCondition, IntoDBA, IntoDCBA,
+    IntoDELA, IntoDMA, IntoDPA, IntoDRA, IntoDSA, IntoNBA, MaxEvenParityCondition,
+    MaxOddParityCondition, MinEvenParityCondition, MinOddParityCondition, MullerCondition, NBA,
+    NondeterministicOmegaAutomaton, OmegaAcceptanceCondition, OmegaAutomaton, RabinCondition,
+    RabinPair, StreettCondition,
 };
 
 mod with_initial;
+use crate::ts::operations::{RestrictByStateIndex, StateIndexFilter};
 use crate::ts::run::{InfiniteObserver, Observer};
 pub use with_initial::{WithInitial, WithoutCondition};
 
@@ -35,6 +39,9 @@ pub use semantics::Semantics;
 
 mod deterministic;
 
+mod metadata;
+pub use metadata::AutomatonMetadata;
+
 mod priority_mapping;
 use crate::ts::predecessors::PredecessorIterable;
 use crate::ts::{
@@ -65,7 +72,11 @@ pub type FiniteWordAutomaton<A, Z, Q, C, const DET: bool = true, D = TS<A, Q, C,
 /// In order for the automaton to be able to accept words, the acceptance condition
 /// must implement the [`Semantics`] trait, depending on the value of `OMEGA` (in the
 /// former case `OMEGA` should be false, and in the latter case `OMEGA` should be true).
-#[derive(Clone, Eq, PartialEq, Copy)]
+///
+/// Additionally, an automaton carries [`AutomatonMetadata`] such as names for its states,
+/// which does not influence its semantics and is therefore ignored when comparing automata.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Automaton<
     A: Alphabet,
     Z,
@@ -78,6 +89,28 @@ pub struct Automaton<
     pub(crate) ts: D,
     initial: D::StateIndex,
     acceptance: Z,
+    #[cfg_attr(feature = "serde", serde(default))]
+    metadata: AutomatonMetadata<D::StateIndex>,
+}
+
+impl<A, Z, Q, C, D, const OMEGA: bool, const DET: bool> PartialEq
+    for Automaton<A, Z, Q, C, D, OMEGA, DET>
+where
+    A: Alphabet,
+    D: TransitionSystem<Alphabet = A, StateColor = Q, EdgeColor = C> + PartialEq,
+    Z: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ts == other.ts && self.initial == other.initial && self.acceptance == other.acceptance
+    }
+}
+
+impl<A, Z, Q, C, D, const OMEGA: bool, const DET: bool> Eq for Automaton<A, Z, Q, C, D, OMEGA, DET>
+where
+    A: Alphabet,
+    D: TransitionSystem<Alphabet = A, StateColor = Q, EdgeColor = C> + Eq,
+    Z: Eq,
+{
 }
 
 impl<Z, Q: Color, C: Color + std::hash::Hash + Eq, const OMEGA: bool>
@@ -160,6 +193,7 @@ where
             initial,
             ts,
             acceptance,
+            metadata: AutomatonMetadata::default(),
         }
     }
 
@@ -211,6 +245,69 @@ where
     pub fn acceptance(&self) -> &Z {
         &self.acceptance
     }
+
+    /// Returns the [`AutomatonMetadata`] of `self`.
+    pub fn metadata(&self) -> &AutomatonMetadata<D::StateIndex> {
+        &self.metadata
+    }
+
+    /// Gives mutable access to the [`AutomatonMetadata`] of `self`.
+    pub fn metadata_mut(&mut self) -> &mut AutomatonMetadata<D::StateIndex> {
+        &mut self.metadata
+    }
+
+    /// Replaces the [`AutomatonMetadata`] of `self` with the given one.
+    pub fn with_metadata(mut self, metadata: AutomatonMetadata<D::StateIndex>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Applies `f` to the transition system of `self`, while keeping the initial state,
+    /// the acceptance condition and the [`AutomatonMetadata`]. This is useful for operations
+    /// that do not change the state indices, such as mapping colors. As the state indices are
+    /// not changed, `f` should not remove the initial state.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::{automaton::DBA, ts::{TSBuilder, TransitionSystem}};
+    ///
+    /// let mut dba = TSBuilder::without_state_colors()
+    ///     .with_edges([(0, 'a', true, 0), (0, 'b', false, 0)])
+    ///     .into_dba(0);
+    /// dba.metadata_mut().set_state_name(0, "start");
+    /// let flipped = dba.map_ts(|ts| ts.map_edge_colors(|c: bool| !c));
+    /// assert_eq!(flipped.metadata().state_name(0), Some("start"));
+    /// ```
+    pub fn map_ts<E, F>(self, f: F) -> Automaton<A, Z, E::StateColor, E::EdgeColor, E, OMEGA, DET>
+    where
+        E: TransitionSystem<Alphabet = A, StateIndex = D::StateIndex>,
+        F: FnOnce(D) -> E,
+    {
+        Automaton {
+            ts: f(self.ts),
+            initial: self.initial,
+            acceptance: self.acceptance,
+            metadata: self.metadata,
+        }
+    }
+
+    /// Restricts `self` to the states for which `filter` returns true, see
+    /// [`TransitionSystem::restrict_state_indices`]. The names of the remaining states are
+    /// kept. Returns `None` if the initial state is removed.
+    #[allow(clippy::type_complexity)]
+    pub fn restrict_states<F>(
+        mut self,
+        filter: F,
+    ) -> Option<Automaton<A, Z, Q, C, RestrictByStateIndex<D, F>, OMEGA, DET>>
+    where
+        F: StateIndexFilter<D::StateIndex>,
+    {
+        if !filter.is_unmasked(self.initial) {
+            return None;
+        }
+        self.metadata.retain_states(|q| filter.is_unmasked(*q));
+        Some(self.map_ts(|ts| ts.restrict_state_indices(filter)))
+    }
 }
 
 impl<A, Z, Q, C, D> Automaton<A, Z, Q, C, D, false, true>
@@ -442,6 +539,10 @@ where
         self.ts.state_indices()
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        self.ts.contains_state_index(index)
+    }
+
     fn edges_from(&self, state: StateIndex<Self>) -> Option<Self::EdgesFromIter<'_>> {
         self.ts.edges_from(state)
     }
@@ -580,6 +681,108 @@ mod tests {
         assert!(!dba.is_empty());
     }
 
+    #[cfg(feature = "serde")]
+    fn roundtrip<T>(value: &T) -> [T; 2]
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let json = serde_json::to_string(value).expect("can serialize to json");
+        let bytes = bincode::serialize(value).expect("can serialize to bincode");
+        [
+            serde_json::from_str(&json).expect("can deserialize from json"),
+            bincode::deserialize(&bytes).expect("can deserialize from bincode"),
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        use crate::automaton::{DPA, MealyMachine, NBA};
+        use crate::core::{Void, alphabet::CharAlphabet};
+        use crate::ts::packed::{Id, Packed};
+        use crate::ts::{Deterministic, TSBuilder};
+        use crate::{NTS, Pointed, RightCongruence, TransitionSystem};
+
+        let dfa = DFA::builder()
+            .with_state_colors([true, false, false])
+            .with_edges([
+                (0, 'a', 1),
+                (0, 'b', 0),
+                (1, 'a', 2),
+                (1, 'b', 0),
+                (2, 'a', 2),
+            ])
+            .into_dfa(0);
+        for back in roundtrip(&dfa) {
+            assert!(back.equivalent(&dfa));
+            assert_eq!(back.initial(), dfa.initial());
+        }
+
+        let packed: DFA<CharAlphabet, _, Packed<CharAlphabet, bool, _>> =
+            DFA::from_parts(Packed::from(&dfa), Id(0));
+        for back in roundtrip(&packed) {
+            assert!(back.equivalent(&dfa));
+        }
+
+        let dpa: DPA = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 1),
+                (0, 'b', 1, 0),
+                (1, 'a', 3, 1),
+                (1, 'b', 2, 0),
+            ])
+            .into_dpa(0);
+        for back in roundtrip(&dpa) {
+            assert!(back.language_equivalent(&dpa));
+        }
+
+        let mealy = MealyMachine::builder()
+            .with_transitions([
+                (0, 'a', 2, 1),
+                (0, 'b', 0, 0),
+                (1, 'a', 1, 0),
+                (1, 'b', 2, 1),
+            ])
+            .into_mealy(0);
+        for back in roundtrip(&mealy) {
+            assert!(back.bisimilar(&mealy));
+        }
+
+        let nba: NBA = NBA::from_parts(
+            NTS::builder()
+                .with_state_colors([false, true])
+                .with_transitions([
+                    (0, 'a', false, 0),
+                    (0, 'b', false, 0),
+                    (0, 'a', false, 1),
+                    (1, 'a', true, 1),
+                ])
+                .into_nts(),
+            0,
+        );
+        for back in roundtrip(&nba) {
+            assert!(back.accepts(upw!("b", "a")));
+            assert!(!back.accepts(upw!("ab")));
+        }
+
+        let cong: RightCongruence = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', Void, 1),
+                (0, 'b', Void, 0),
+                (1, 'a', Void, 0),
+                (1, 'b', Void, 1),
+            ])
+            .into_right_congruence_bare(0);
+        for back in roundtrip(&cong) {
+            assert_eq!(back.size(), 2);
+            assert_eq!(
+                back.reached_state_index("aab"),
+                cong.reached_state_index("aab")
+            );
+            assert_eq!(back.minimal_representatives().len(), 2);
+        }
+    }
+
     #[test]
     fn dfas_and_boolean_operations() {
         let dfa = DFA::builder()
@@ -15,6 +15,7 @@ use crate::{DTS, Pointed, TransitionSystem};
 /// a finite run, the last transition color that is taken. It panics if the run has
 /// no transitions at all.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MealySemantics<C>(PhantomData<C>);
 
 pub trait MealyLike: TransitionSystem<EdgeColor: Lattice> + Deterministic + Pointed {}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+/// Annotations of an [`super::Automaton`] that do not influence its semantics, namely a name
+/// for the automaton, the tool that produced it, names for some of its states and, if the
+/// `hoa` feature is enabled, additional header items that should be written when the automaton
+/// is output in the HOA format.
+///
+/// When an automaton is read from the HOA format, the `name:` and `tool:` header items as
+/// well as the comments of the states (as in `State: 0 "comment"`) are stored here, so they
+/// survive a round trip. As most automata do not carry any annotations, the annotations are
+/// only allocated once the first one is set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "Idx: Ord + serde::Deserialize<'de>"))
+)]
+pub struct AutomatonMetadata<Idx>(Option<Box<Annotations<Idx>>>);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "Idx: Ord + serde::Deserialize<'de>"))
+)]
+struct Annotations<Idx> {
+    name: Option<String>,
+    tool: Option<(String, Option<String>)>,
+    state_names: BTreeMap<Idx, String>,
+    #[cfg(feature = "hoa")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    header_items: Vec<hoars::HeaderItem>,
+}
+
+impl<Idx> Default for AutomatonMetadata<Idx> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<Idx: Ord> AutomatonMetadata<Idx> {
+    fn annotations_mut(&mut self) -> &mut Annotations<Idx> {
+        self.0.get_or_insert_with(|| {
+            Box::new(Annotations {
+                name: None,
+                tool: None,
+                state_names: BTreeMap::new(),
+                #[cfg(feature = "hoa")]
+                header_items: Vec::new(),
+            })
+        })
+    }
+
+    /// Returns true if no annotations are present.
+    pub fn is_empty(&self) -> bool {
+        let Some(annotations) = &self.0 else {
+            return true;
+        };
+        #[cfg(feature = "hoa")]
+        if !annotations.header_items.is_empty() {
+            return false;
+        }
+        annotations.name.is_none()
+            && annotations.tool.is_none()
+            && annotations.state_names.is_empty()
+    }
+
+    /// Returns the name of the automaton, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.0.as_ref()?.name.as_deref()
+    }
+
+    /// Sets the name of the automaton.
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.annotations_mut().name = Some(name.into());
+    }
+
+    /// Returns the name and optionally the version of the tool that produced the automaton.
+    pub fn tool(&self) -> Option<(&str, Option<&str>)> {
+        self.0
+            .as_ref()?
+            .tool
+            .as_ref()
+            .map(|(name, version)| (name.as_str(), version.as_deref()))
+    }
+
+    /// Sets the name and version of the tool that produced the automaton.
+    pub fn set_tool<S: Into<String>>(&mut self, name: S, version: Option<String>) {
+        self.annotations_mut().tool = Some((name.into(), version));
+    }
+
+    /// Returns the name of the given state, if it has one.
+    pub fn state_name(&self, state: Idx) -> Option<&str> {
+        self.0.as_ref()?.state_names.get(&state).map(String::as_str)
+    }
+
+    /// Names the given state, returns the name it had before.
+    pub fn set_state_name<S: Into<String>>(&mut self, state: Idx, name: S) -> Option<String> {
+        self.annotations_mut()
+            .state_names
+            .insert(state, name.into())
+    }
+
+    /// Removes the name of the given state and returns it.
+    pub fn remove_state_name(&mut self, state: Idx) -> Option<String> {
+        self.0.as_mut()?.state_names.remove(&state)
+    }
+
+    /// Iterates over all named states together with their names, ordered by state.
+    pub fn state_names(&self) -> impl Iterator<Item = (&Idx, &str)> {
+        self.0
+            .iter()
+            .flat_map(|annotations| annotations.state_names.iter())
+            .map(|(state, name)| (state, name.as_str()))
+    }
+
+    /// Only keeps the names of the states for which `filter` returns true.
+    pub fn retain_states<F: Fn(&Idx) -> bool>(&mut self, filter: F) {
+        if let Some(annotations) = &mut self.0 {
+            annotations.state_names.retain(|state, _| filter(state));
+        }
+    }
+
+    /// Returns the additional header items that are written when the automaton is output in
+    /// the HOA format.
+    #[cfg(feature = "hoa")]
+    pub fn header_items(&self) -> &[hoars::HeaderItem] {
+        self.0
+            .as_ref()
+            .map_or(&[], |annotations| &annotations.header_items)
+    }
+
+    /// Adds a header item that is written when the automaton is output in the HOA format. It
+    /// is written as is, so it should not contradict the header items that are produced from
+    /// the automaton itself, such as `States:` or `Acceptance:`.
+    #[cfg(feature = "hoa")]
+    pub fn push_header_item(&mut self, item: hoars::HeaderItem) {
+        self.annotations_mut().header_items.push(item);
+    }
+}
@@ -13,6 +13,7 @@ use crate::{Congruence, DTS, Pointed, TransitionSystem};
 /// state that is reached during a run on a word. If the input is empty, it
 /// produces the color of the initial state.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MooreSemantics<Q>(std::marker::PhantomData<Q>);
 
 pub trait MooreLike: TransitionSystem<StateColor: Lattice> + Deterministic + Pointed {}
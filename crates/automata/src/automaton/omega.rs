@@ -8,19 +8,36 @@ pub use parity::*;
 mod rabin;
 pub use rabin::*;
 
+mod streett;
+pub use streett::*;
+
 mod muller;
 pub use muller::*;
 
+mod emerson_lei;
+pub use emerson_lei::*;
+
+mod nba;
+pub use nba::*;
+
+mod determinization;
+pub use determinization::*;
+
+mod alternating;
+pub use alternating::*;
+
 #[allow(missing_docs)]
 mod acceptance_mask;
 use super::InfiniteWordAutomaton;
 use crate::representation::IntoTs;
 use crate::ts::{DefaultIdType, ForAlphabet, IsEdge, ScalarIndexType, Sproutable, TSBuilder};
-use crate::{DTS, Pointed, TS, TransitionSystem};
+use crate::{DTS, TS, TransitionSystem};
 pub use acceptance_mask::AcceptanceMask;
-use automata_core::Int;
 use automata_core::alphabet::{Alphabet, CharAlphabet, PropAlphabet};
-use automata_core::math::OrderedSet;
+use automata_core::math::{self, OrderedSet};
+use automata_core::{Int, Void};
+use std::collections::VecDeque;
+use std::hash::Hash;
 use tracing::{error, trace};
 
 /// Type alias for an omega automaton (i.e. an [`InfiniteWordAutomaton`]) that is guaranteed to be
@@ -41,8 +58,24 @@ pub type OmegaAutomaton<A, const DET: bool = true> =
 /// used in conjunction with [`OmegaAutomaton`]/[`DeterministicOmegaAutomaton`] when
 /// the exact type is not known beforehand (such as when parsing an automaton). Usually
 /// one should prefer using specific automaton types such as [`DBA`]/[`DPA`] etc.
-#[derive(Debug, Clone, Eq, Copy, PartialEq, Ord, PartialOrd)]
+///
+/// The conditions that do not carry any data interpret the [`AcceptanceMask`] of an edge
+/// as follows:
+/// - For [`Self::Rabin`] and [`Self::Streett`], the colors `2i` and `2i + 1` make up the
+///   `i`-th pair. Following the HOA convention, a Rabin pair is `Fin(2i) & Inf(2i + 1)` and
+///   a Streett pair is `Fin(2i) | Inf(2i + 1)`.
+/// - For [`Self::Buchi`] and [`Self::Reachability`], an edge is marked if it carries color `0`.
+///   The former accepts if marked edges are taken infinitely often, the latter if a marked
+///   edge is taken at least once.
+/// - For [`Self::CoBuchi`] and [`Self::Safety`], an edge is marked if it carries color `0`.
+///   The former accepts if marked edges are taken only finitely often, the latter if no
+///   marked edge is ever taken.
+///
+/// Any other condition that can be expressed over the infinity set of a run is captured by
+/// [`Self::EmersonLei`], which stores the full [`EmersonLeiCondition`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OmegaAcceptanceCondition {
     Parity(Int, Int),
     Buchi,
@@ -52,12 +85,21 @@ pub enum OmegaAcceptanceCondition {
     CoBuchi,
     Reachability,
     Safety,
+    EmersonLei(EmersonLeiCondition),
 }
 
 impl OmegaAcceptanceCondition {
-    /// Returns true if and only if the condition is satisfied by the given set of
-    /// [`AcceptanceMask`]s.
-    pub fn satisfied(&self, infset: &OrderedSet<AcceptanceMask>) -> bool {
+    /// Returns true if and only if the condition is satisfied by a run that sees the
+    /// [`AcceptanceMask`]s in `visited` at least once and those in `infset` infinitely often,
+    /// so `infset` should be a subset of `visited`. Only [`Self::Reachability`] and
+    /// [`Self::Safety`] depend on `visited`, all other conditions are determined by `infset`.
+    pub fn satisfied(
+        &self,
+        visited: &OrderedSet<AcceptanceMask>,
+        infset: &OrderedSet<AcceptanceMask>,
+    ) -> bool {
+        let colors = || infset.iter().flat_map(|mask| mask.iter());
+        let seen = |color: Int| infset.iter().any(|mask| mask.contains(color));
         match self {
             OmegaAcceptanceCondition::Parity(_low, _high) => infset
                 .iter()
@@ -65,7 +107,19 @@ impl OmegaAcceptanceCondition {
                 .min()
                 .map(|x| x % 2 == 0)
                 .unwrap_or(false),
-            _ => unimplemented!(),
+            OmegaAcceptanceCondition::MaxParity(_low, _high) => infset
+                .iter()
+                .map(|x| x.as_priority())
+                .max()
+                .map(|x| x % 2 == 0)
+                .unwrap_or(false),
+            OmegaAcceptanceCondition::Buchi => infset.iter().any(|mask| mask.as_bool()),
+            OmegaAcceptanceCondition::CoBuchi => !infset.iter().any(|mask| mask.as_bool()),
+            OmegaAcceptanceCondition::Rabin => colors().any(|c| c % 2 == 1 && !seen(c - 1)),
+            OmegaAcceptanceCondition::Streett => colors().all(|c| c % 2 == 1 || seen(c + 1)),
+            OmegaAcceptanceCondition::EmersonLei(condition) => condition.satisfied(infset),
+            OmegaAcceptanceCondition::Reachability => visited.iter().any(|mask| mask.as_bool()),
+            OmegaAcceptanceCondition::Safety => !visited.iter().any(|mask| mask.as_bool()),
         }
     }
 }
@@ -78,11 +132,11 @@ impl<A: Alphabet, const DET: bool> OmegaAutomaton<A, DET> {
         initial: DefaultIdType,
         acceptance: OmegaAcceptanceCondition,
     ) -> OmegaAutomaton<A, DET> {
-        OmegaAutomaton {
-            ts,
-            initial,
-            acceptance,
-        }
+        OmegaAutomaton::from_parts_with_acceptance(ts, initial, acceptance)
+    }
+    /// Returns the initial state of `self`.
+    pub fn initial(&self) -> DefaultIdType {
+        self.initial
     }
     /// Attempts to convert `self` into a [`DeterministicOmegaAutomaton`]. Returns
     /// `None` if this is not possible because the transition system underlying `self`
@@ -105,18 +159,24 @@ impl<A: Alphabet, const DET: bool> OmegaAutomaton<A, DET> {
     /// Attempts to convert `self` into a [`DeterministicOmegaAutomaton`]. Returns
     /// `None` if this is not possible because the transition system underlying `self`
     /// is not deterministic.
+    /// The [`super::AutomatonMetadata`] is kept in either case.
     pub fn try_into_deterministic(self) -> Result<DeterministicOmegaAutomaton<A>, Self> {
         let OmegaAutomaton {
             ts,
             initial,
             acceptance,
+            metadata,
         } = self;
         match ts.try_into_deterministic() {
-            Ok(dts) => Ok(DeterministicOmegaAutomaton::new(dts, initial, acceptance)),
+            Ok(dts) => {
+                Ok(DeterministicOmegaAutomaton::new(dts, initial, acceptance)
+                    .with_metadata(metadata))
+            }
             Err(ts) => Err(Self {
                 ts,
                 initial,
                 acceptance,
+                metadata,
             }),
         }
     }
@@ -126,7 +186,7 @@ impl<A: Alphabet> DeterministicOmegaAutomaton<A> {
     /// Consumes and converts `self` into a [`DPA`]. Since [`DPA`]s can capture the
     /// full class of omega-regular languages, this operation never fails.
     pub fn into_dpa(self) -> DPA<A> {
-        match self.acceptance {
+        match &self.acceptance {
             OmegaAcceptanceCondition::Parity(_, _) => self
                 .ts
                 .map_edge_colors(|mask| mask.as_priority())
@@ -137,12 +197,14 @@ impl<A: Alphabet> DeterministicOmegaAutomaton<A> {
                 .map_edge_colors(|mask| if mask.as_bool() { 0 } else { 1 })
                 .with_initial(self.initial)
                 .into_dpa(),
+            // marked edges must be seen only finitely often, so under the min even
+            // semantics they have to dominate the unmarked ones
             OmegaAcceptanceCondition::CoBuchi => self
                 .ts
-                .map_edge_colors(|mask| if mask.as_bool() { 1 } else { 0 })
+                .map_edge_colors(|mask| if mask.as_bool() { 1 } else { 2 })
                 .with_initial(self.initial)
                 .into_dpa(),
-            OmegaAcceptanceCondition::MaxParity(low, high) => {
+            &OmegaAcceptanceCondition::MaxParity(low, high) => {
                 let k = (high - low) + if low % 2 == 0 { 0 } else { 1 };
                 let to_new = |mask: AcceptanceMask| {
                     let c = mask.as_priority();
@@ -157,12 +219,205 @@ impl<A: Alphabet> DeterministicOmegaAutomaton<A> {
                     .with_initial(self.initial)
                     .into_dpa()
             }
-            OmegaAcceptanceCondition::Rabin => todo!(),
-            OmegaAcceptanceCondition::Streett => todo!(),
-            OmegaAcceptanceCondition::Reachability => todo!(),
-            OmegaAcceptanceCondition::Safety => todo!(),
+            OmegaAcceptanceCondition::Rabin => self.index_appearance_record(false),
+            OmegaAcceptanceCondition::Streett => self.index_appearance_record(true),
+            OmegaAcceptanceCondition::Reachability => self.with_sink(0, 1),
+            OmegaAcceptanceCondition::Safety => self.with_sink(1, 0),
+            OmegaAcceptanceCondition::EmersonLei(condition) => {
+                self.latest_appearance_record(condition)
+            }
+        }
+    }
+
+    /// Returns the number of pairs that the masks of `self` make up, when they are
+    /// interpreted as a Rabin or Streett condition.
+    fn pair_count(&self) -> usize {
+        self.ts
+            .state_indices()
+            .flat_map(|q| self.ts.edges_from(q).unwrap())
+            .filter_map(|edge| AcceptanceMask::max(&edge.color()))
+            .max()
+            .map(|c| c as usize / 2 + 1)
+            .unwrap_or(0)
+    }
+
+    /// Builds a [`DPA`] through an index appearance record (IAR) over the pairs of a Rabin
+    /// condition. A state of the resulting automaton consists of a state of `self` and a
+    /// permutation of the pairs. Taking an edge moves all pairs whose `Fin` color is seen
+    /// to the front, and the priority is determined by the largest position of a pair whose
+    /// `Fin` (odd) or `Inf` (even) color is seen, in the sense of a max even condition.
+    ///
+    /// A Streett condition is the negation of a Rabin condition in which the roles of `Fin`
+    /// and `Inf` are swapped, so if `streett` is true, we do precisely that and complement
+    /// the resulting parity condition.
+    fn index_appearance_record(&self, streett: bool) -> DPA<A> {
+        let pairs = self.pair_count();
+        assert!(
+            2 * pairs + 2 <= Int::MAX as usize,
+            "too many pairs to represent priorities"
+        );
+        let fin = |pair: usize| (2 * pair + usize::from(streett)) as Int;
+        let inf = |pair: usize| (2 * pair + usize::from(!streett)) as Int;
+        // the largest max even priority is at most `2 * pairs + 2`, which we use
+        // to flip the priorities into a min even condition while preserving parity
+        let flip = 2 * pairs as Int + 2;
+
+        explore_dpa(
+            self.ts.alphabet(),
+            (self.initial, (0..pairs).collect::<Vec<_>>()),
+            |(q, record)| {
+                self.ts
+                    .edges_from(*q)
+                    .unwrap()
+                    .map(|edge| {
+                        let mask = edge.color();
+                        let mut priority = 1;
+                        for (position, &pair) in record.iter().enumerate() {
+                            let position = position as Int + 1;
+                            if mask.contains(fin(pair)) {
+                                priority = 2 * position + 1;
+                            } else if mask.contains(inf(pair)) {
+                                priority = 2 * position;
+                            }
+                        }
+                        let (mut moved, rest): (Vec<_>, Vec<_>) =
+                            record.iter().partition(|&&pair| mask.contains(fin(pair)));
+                        moved.extend(rest);
+
+                        let priority = if streett { priority + 1 } else { priority };
+                        (
+                            edge.expression().clone(),
+                            flip - priority,
+                            (edge.target(), moved),
+                        )
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    /// Builds a [`DPA`] through a latest appearance record (LAR) over the atoms of the given
+    /// [`EmersonLeiCondition`]. A state of the resulting automaton consists of a state of
+    /// `self` and a permutation of the atoms. Taking an edge moves all atoms that it visits
+    /// to the front and the priority is determined by the largest position `h` of such an
+    /// atom. If the atoms in the first `h` positions satisfy the condition, the priority is
+    /// even, otherwise it is odd, in the sense of a max even condition.
+    fn latest_appearance_record(&self, condition: &EmersonLeiCondition) -> DPA<A> {
+        let atoms = condition.atoms().into_iter().collect::<Vec<_>>();
+        assert!(
+            2 * atoms.len() + 2 <= Int::MAX as usize,
+            "too many atoms to represent priorities"
+        );
+        let flip = 2 * atoms.len() as Int + 2;
+
+        explore_dpa(
+            self.ts.alphabet(),
+            (self.initial, (0..atoms.len()).collect::<Vec<_>>()),
+            |(q, record)| {
+                self.ts
+                    .edges_from(*q)
+                    .unwrap()
+                    .map(|edge| {
+                        let mask = edge.color();
+                        let visited = |atom: &usize| atoms[*atom].visited_by(&mask);
+                        let hit = record.iter().rposition(visited).map_or(0, |h| h + 1);
+                        let satisfied = condition.evaluate_with(&|atom| {
+                            record[..hit].iter().any(|i| atoms[*i] == *atom)
+                        });
+                        let priority = 2 * hit as Int + if satisfied { 0 } else { 1 };
+
+                        let (mut moved, rest): (Vec<_>, Vec<_>) =
+                            record.iter().partition(|&atom| visited(atom));
+                        moved.extend(rest);
+                        (
+                            edge.expression().clone(),
+                            flip - priority,
+                            (edge.target(), moved),
+                        )
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    /// Builds a [`DPA`] in which every edge that carries a mark leads into a sink state. The
+    /// edges leading into the sink and the loops on it are colored with `sink`, all other
+    /// edges are colored with `other`.
+    fn with_sink(&self, sink: Int, other: Int) -> DPA<A> {
+        let alphabet = self.ts.alphabet();
+        explore_dpa(alphabet, Some(self.initial), |state| match state {
+            Some(q) => self
+                .ts
+                .edges_from(*q)
+                .unwrap()
+                .map(|edge| {
+                    if edge.color().as_bool() {
+                        (edge.expression().clone(), sink, None)
+                    } else {
+                        (edge.expression().clone(), other, Some(edge.target()))
+                    }
+                })
+                .collect(),
+            None => alphabet
+                .expression_map()
+                .into_values()
+                .map(|expression| (expression, sink, None))
+                .collect(),
+        })
+    }
+}
+
+/// Explores the states that are reachable from `initial` in the transition structure
+/// given by `successors` and collects them into a [`DPA`].
+fn explore_dpa<A, S, F>(alphabet: &A, initial: S, successors: F) -> DPA<A>
+where
+    A: Alphabet,
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Vec<(A::Expression, Int, S)>,
+{
+    explore_dpa_bounded(alphabet, initial, usize::MAX, successors)
+        .expect("exploration is not bounded")
+}
+
+/// Works like [`explore_dpa`], but gives up and returns `None` as soon as more than
+/// `limit` states have been discovered.
+fn explore_dpa_bounded<A, S, F>(
+    alphabet: &A,
+    initial: S,
+    limit: usize,
+    mut successors: F,
+) -> Option<DPA<A>>
+where
+    A: Alphabet,
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Vec<(A::Expression, Int, S)>,
+{
+    let mut ts = DTS::for_alphabet(alphabet.clone());
+    let mut indices = math::Map::default();
+    let mut queue = VecDeque::from([initial.clone()]);
+    let initial = ts.add_state(Void);
+    indices.insert(queue[0].clone(), initial);
+
+    while let Some(state) = queue.pop_front() {
+        let source = indices[&state];
+        for (expression, priority, target) in successors(&state) {
+            let target = match indices.get(&target) {
+                Some(&idx) => idx,
+                None => {
+                    if indices.len() >= limit {
+                        return None;
+                    }
+                    let idx = ts.add_state(Void);
+                    indices.insert(target.clone(), idx);
+                    queue.push_back(target);
+                    idx
+                }
+            };
+            ts.add_edge((source, expression, priority, target));
         }
     }
+
+    Some(DPA::from_parts(ts, initial))
 }
 
 impl From<DeterministicOmegaAutomaton<PropAlphabet>> for DeterministicOmegaAutomaton<CharAlphabet> {
@@ -221,3 +476,124 @@ impl TryFrom<DeterministicOmegaAutomaton<CharAlphabet>>
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AcceptanceMask, DeterministicOmegaAutomaton, EmersonLeiAtom, EmersonLeiCondition,
+        OmegaAcceptanceCondition,
+    };
+    use crate::TransitionSystem;
+    use crate::random::{generate_random_omega_words, generate_random_ts_sized};
+    use crate::representation::IntoTs;
+    use crate::ts::{Deterministic, IsEdge, TSBuilder};
+    use automata_core::alphabet::CharAlphabet;
+    use automata_core::word::{OmegaWord, ReducedOmegaWord, Word};
+    use automata_core::{Int, math::OrderedSet, upw};
+    use rand::{Rng, thread_rng};
+
+    fn random_omega_automaton(
+        acceptance: OmegaAcceptanceCondition,
+        colors: Int,
+    ) -> DeterministicOmegaAutomaton<CharAlphabet> {
+        let (ts, initial) = generate_random_ts_sized(2, 6);
+        let ts = ts
+            .map_edge_colors(|_| {
+                (0..colors)
+                    .filter(|_| thread_rng().gen_bool(0.3))
+                    .collect::<AcceptanceMask>()
+            })
+            .map_state_colors(|_| 0 as Int)
+            .into_dts();
+        DeterministicOmegaAutomaton::new(ts, initial, acceptance)
+    }
+
+    fn source_accepts(
+        aut: &DeterministicOmegaAutomaton<CharAlphabet>,
+        word: &ReducedOmegaWord<char>,
+    ) -> bool {
+        // after this many symbols, every edge of the run has been seen
+        let length = word.spoke_len() + word.cycle_len() * (aut.size() + 1);
+        let mut state = aut.initial();
+        let mut visited = OrderedSet::default();
+        for i in 0..length {
+            let edge = aut.edge(state, word.nth(i).unwrap()).unwrap();
+            visited.insert(edge.color().clone());
+            state = edge.target();
+        }
+        aut.acceptance.satisfied(
+            &visited,
+            &aut.recurrent_edge_colors(word)
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn random_conversions_to_dpa() {
+        let alphabet = CharAlphabet::of_size(2);
+        for (acceptance, colors) in [
+            (OmegaAcceptanceCondition::Rabin, 4),
+            (OmegaAcceptanceCondition::Rabin, 6),
+            (OmegaAcceptanceCondition::Streett, 4),
+            (OmegaAcceptanceCondition::Streett, 6),
+            (OmegaAcceptanceCondition::Reachability, 1),
+            (OmegaAcceptanceCondition::Safety, 1),
+            (
+                OmegaAcceptanceCondition::EmersonLei(EmersonLeiCondition::generalized_buchi(3)),
+                3,
+            ),
+            (
+                OmegaAcceptanceCondition::EmersonLei(EmersonLeiCondition::Or(vec![
+                    EmersonLeiCondition::And(vec![
+                        EmersonLeiCondition::inf(0),
+                        EmersonLeiCondition::Fin(EmersonLeiAtom::Negative(1)),
+                    ]),
+                    EmersonLeiCondition::And(vec![
+                        EmersonLeiCondition::fin(2),
+                        EmersonLeiCondition::Inf(EmersonLeiAtom::Negative(0)),
+                    ]),
+                ])),
+                3,
+            ),
+        ] {
+            for _ in 0..20 {
+                let aut = random_omega_automaton(acceptance.clone(), colors);
+                let dpa = aut.clone().into_dpa();
+                for word in generate_random_omega_words(&alphabet, 0, 5, 1, 5, 30) {
+                    assert_eq!(
+                        source_accepts(&aut, &word),
+                        dpa.accepts(&word),
+                        "{acceptance:?} conversion differs on {word:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rabin_to_dpa() {
+        // pair 0 demands finitely many `a` and infinitely many `b`, pair 1 demands
+        // finitely many `b` and infinitely many `a`
+        let ts = TSBuilder::default()
+            .with_state_colors([0 as Int])
+            .with_transitions([
+                (0, 'a', AcceptanceMask::from_iter([0, 3]), 0),
+                (0, 'b', AcceptanceMask::from_iter([1, 2]), 0),
+            ])
+            .into_dts();
+        let dra = DeterministicOmegaAutomaton::new(ts, 0, OmegaAcceptanceCondition::Rabin);
+        let dpa = dra.clone().into_dpa();
+        assert!(dpa.accepts(upw!("a")));
+        assert!(dpa.accepts(upw!("bba", "b")));
+        assert!(!dpa.accepts(upw!("ab")));
+
+        let dsa = DeterministicOmegaAutomaton::new(dra.ts, 0, OmegaAcceptanceCondition::Streett);
+        let dpa = dsa.into_dpa();
+        assert!(!dpa.accepts(upw!("a")));
+        assert!(!dpa.accepts(upw!("b")));
+        assert!(dpa.accepts(upw!("ab")));
+    }
+}
@@ -4,7 +4,7 @@ use bit_set::BitSet;
 use itertools::Itertools;
 use tracing::error;
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct AcceptanceMask(BitSet);
 
 impl AcceptanceMask {
@@ -20,6 +20,14 @@ impl AcceptanceMask {
         self.iter().min()
     }
 
+    pub fn contains(&self, color: Int) -> bool {
+        self.0.contains(color as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn as_priority(&self) -> Int {
         let mut it = self.iter();
         let Some(priority) = it.next() else {
@@ -63,6 +71,27 @@ impl From<&hoars::AcceptanceSignature> for AcceptanceMask {
     }
 }
 
+impl FromIterator<Int> for AcceptanceMask {
+    fn from_iter<I: IntoIterator<Item = Int>>(iter: I) -> Self {
+        Self(BitSet::from_iter(iter.into_iter().map(|i| i as usize)))
+    }
+}
+
+/// An [`AcceptanceMask`] is serialized as the sequence of colors that it contains.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AcceptanceMask {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AcceptanceMask {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Int>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
 impl std::fmt::Debug for AcceptanceMask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.0)
@@ -0,0 +1,564 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::NTS;
+use crate::automaton::{AcceptanceMask, NBA, OmegaAcceptanceCondition};
+use crate::core::{
+    Int,
+    alphabet::{Alphabet, Expression},
+    math,
+    word::OmegaWord,
+};
+use crate::ts::{DefaultIdType, ForAlphabet, Sproutable};
+
+/// Errors that can occur when working with an [`AlternatingOmegaAutomaton`].
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum AlternationError {
+    /// The operation is not supported for the given acceptance condition.
+    #[error("acceptance condition {0:?} is not supported")]
+    UnsupportedAcceptance(OmegaAcceptanceCondition),
+}
+
+/// A transition of an [`AlternatingOmegaAutomaton`], which leads to a conjunction of
+/// target states. Acceptance is transition-based, so each edge carries an
+/// [`AcceptanceMask`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlternatingEdge<E> {
+    expression: E,
+    targets: BTreeSet<DefaultIdType>,
+    color: AcceptanceMask,
+}
+
+impl<E> AlternatingEdge<E> {
+    /// Returns the expression that labels `self`.
+    pub fn expression(&self) -> &E {
+        &self.expression
+    }
+
+    /// Returns the conjunction of states that `self` leads to.
+    pub fn targets(&self) -> &BTreeSet<DefaultIdType> {
+        &self.targets
+    }
+
+    /// Returns the [`AcceptanceMask`] of `self`.
+    pub fn color(&self) -> &AcceptanceMask {
+        &self.color
+    }
+}
+
+/// An alternating automaton over infinite words. Every transition leads to a conjunction
+/// of states, and several transitions leaving a state whose expressions match the same
+/// symbol constitute a disjunction. A word is accepted if there is a choice of transitions
+/// such that every branch of the resulting run satisfies the [`OmegaAcceptanceCondition`].
+///
+/// Such automata are typically produced by translators from LTL and are read from the
+/// HOA format if some edge targets more than one state, see
+/// [`crate::hoa::input::hoa_to_alternating`]. Membership of ultimately periodic words is
+/// decided by solving a parity game and for Büchi acceptance, [`Self::to_nba`] removes the
+/// alternation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlternatingOmegaAutomaton<A: Alphabet> {
+    alphabet: A,
+    edges: Vec<Vec<AlternatingEdge<A::Expression>>>,
+    initial: BTreeSet<DefaultIdType>,
+    acceptance: OmegaAcceptanceCondition,
+}
+
+impl<A: Alphabet> AlternatingOmegaAutomaton<A> {
+    /// Creates a new automaton without any states over the given alphabet.
+    pub fn new(alphabet: A, acceptance: OmegaAcceptanceCondition) -> Self {
+        Self {
+            alphabet,
+            edges: vec![],
+            initial: BTreeSet::new(),
+            acceptance,
+        }
+    }
+
+    /// Adds a new state and returns its index.
+    pub fn add_state(&mut self) -> DefaultIdType {
+        self.edges.push(vec![]);
+        (self.edges.len() - 1) as DefaultIdType
+    }
+
+    /// Adds an edge from `source` to the conjunction of `targets`, which is labeled with the
+    /// given expression and carries the given [`AcceptanceMask`].
+    ///
+    /// Panics if `source` or one of the `targets` does not exist.
+    pub fn add_edge<I: IntoIterator<Item = DefaultIdType>>(
+        &mut self,
+        source: DefaultIdType,
+        expression: A::Expression,
+        targets: I,
+        color: AcceptanceMask,
+    ) {
+        let targets: BTreeSet<_> = targets.into_iter().collect();
+        assert!(
+            targets.iter().all(|t| (*t as usize) < self.edges.len()),
+            "target state does not exist"
+        );
+        self.edges[source as usize].push(AlternatingEdge {
+            expression,
+            targets,
+            color,
+        });
+    }
+
+    /// Sets the conjunction of initial states.
+    pub fn set_initial<I: IntoIterator<Item = DefaultIdType>>(&mut self, initial: I) {
+        self.initial = initial.into_iter().collect();
+    }
+
+    /// Returns the conjunction of initial states.
+    pub fn initial(&self) -> &BTreeSet<DefaultIdType> {
+        &self.initial
+    }
+
+    /// Returns the alphabet of `self`.
+    pub fn alphabet(&self) -> &A {
+        &self.alphabet
+    }
+
+    /// Returns the acceptance condition of `self`.
+    pub fn acceptance(&self) -> &OmegaAcceptanceCondition {
+        &self.acceptance
+    }
+
+    /// Returns the number of states.
+    pub fn size(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns an iterator over the indices of all states.
+    pub fn state_indices(&self) -> impl Iterator<Item = DefaultIdType> {
+        0..self.edges.len() as DefaultIdType
+    }
+
+    /// Returns the edges leaving the given state, or `None` if it does not exist.
+    pub fn edges_from(&self, state: DefaultIdType) -> Option<&[AlternatingEdge<A::Expression>]> {
+        self.edges.get(state as usize).map(Vec::as_slice)
+    }
+
+    fn matching_edges(
+        &self,
+        state: DefaultIdType,
+        symbol: A::Symbol,
+    ) -> impl Iterator<Item = &AlternatingEdge<A::Expression>> {
+        self.edges[state as usize]
+            .iter()
+            .filter(move |edge| edge.expression.matched_by(symbol))
+    }
+
+    /// Maps an [`AcceptanceMask`] to a priority such that a branch satisfies the acceptance
+    /// condition if and only if the least priority it sees infinitely often is even.
+    ///
+    /// For parity conditions, an edge without a mark does not influence which priority is
+    /// decisive, so it is mapped to a priority that is less significant than all others. Its
+    /// parity is chosen such that a branch on which only unmarked edges recur is accepted if
+    /// and only if it satisfies the HOA acceptance formula.
+    fn priority(&self, mask: &AcceptanceMask) -> Result<Int, AlternationError> {
+        Ok(match &self.acceptance {
+            OmegaAcceptanceCondition::Buchi => Int::from(!mask.contains(0)),
+            OmegaAcceptanceCondition::CoBuchi => {
+                if mask.contains(0) {
+                    1
+                } else {
+                    2
+                }
+            }
+            OmegaAcceptanceCondition::Parity(_, high) => mask.iter().min().unwrap_or(high + 1),
+            OmegaAcceptanceCondition::MaxParity(_, high) => {
+                let even = high + (high % 2);
+                mask.iter()
+                    .max()
+                    .map_or(even + 1, |priority| even - priority)
+            }
+            other => return Err(AlternationError::UnsupportedAcceptance(other.clone())),
+        })
+    }
+
+    /// Decides whether `self` accepts the given word. This amounts to solving a parity game
+    /// on the product of `self` with the lasso representing `word`, in which one player
+    /// picks the transitions and the other one picks a branch of each conjunction.
+    ///
+    /// Fails with [`AlternationError::UnsupportedAcceptance`] unless the acceptance
+    /// condition is Büchi, co-Büchi or a parity condition.
+    pub fn accepts<W: OmegaWord<Symbol = A::Symbol>>(
+        &self,
+        word: W,
+    ) -> Result<bool, AlternationError> {
+        let len = word.combined_len();
+        assert!(len > 0, "an omega word must have a non-empty cycle");
+
+        // vertices 0 and 1 are sinks that are won by the verifier and the refuter, respectively,
+        // and vertex 2 is the root from which the refuter picks one of the initial states
+        let mut arena = Arena::default();
+        let win = arena.add(false, 0);
+        let lose = arena.add(true, 1);
+        arena.successors[win].push(win);
+        arena.successors[lose].push(lose);
+        let root = arena.add(false, Int::MAX);
+
+        let mut indices: math::Map<(DefaultIdType, usize), usize> = math::Map::default();
+        let mut queue = VecDeque::new();
+        let mut choices = vec![];
+        for &q in &self.initial {
+            let vertex = arena.add(true, Int::MAX);
+            indices.insert((q, 0), vertex);
+            queue.push_back((q, 0));
+            choices.push(vertex);
+        }
+        arena.successors[root] = choices;
+
+        while let Some((q, i)) = queue.pop_front() {
+            let vertex = indices[&(q, i)];
+            let symbol = word.nth(i).expect("position lies within the lasso");
+            let next = if i + 1 == len {
+                word.loop_index()
+            } else {
+                i + 1
+            };
+
+            for edge in self.matching_edges(q, symbol) {
+                let branch = arena.add(false, self.priority(&edge.color)?);
+                arena.successors[vertex].push(branch);
+                for &target in &edge.targets {
+                    let successor = *indices.entry((target, next)).or_insert_with(|| {
+                        queue.push_back((target, next));
+                        arena.add(true, Int::MAX)
+                    });
+                    arena.successors[branch].push(successor);
+                }
+            }
+        }
+
+        // a player that cannot move loses, and the neutral priority of vertices that do not
+        // correspond to a transition must not influence the outcome
+        let neutral = arena
+            .priority
+            .iter()
+            .copied()
+            .filter(|p| *p != Int::MAX)
+            .max()
+            .unwrap_or(0);
+        for vertex in 0..arena.size() {
+            if arena.priority[vertex] == Int::MAX {
+                arena.priority[vertex] = neutral;
+            }
+            if arena.successors[vertex].is_empty() {
+                let sink = if arena.verifier[vertex] { lose } else { win };
+                arena.successors[vertex].push(sink);
+            }
+        }
+
+        Ok(arena.solve().contains(&root))
+    }
+
+    /// Removes the alternation from `self` using the construction of Miyano and Hayashi,
+    /// which yields an equivalent [`NBA`]. Its states are pairs of sets of states `(S, O)`,
+    /// where `S` contains all states that the branches of a run are currently in and
+    /// `O ⊆ S` tracks the branches which have not taken an accepting transition since the
+    /// last breakpoint, that is the last time `O` was empty. A state of the [`NBA`] is
+    /// accepting if its `O` component is empty.
+    ///
+    /// Fails with [`AlternationError::UnsupportedAcceptance`] if the acceptance condition of
+    /// `self` is not Büchi.
+    pub fn to_nba(&self) -> Result<NBA<A>, AlternationError> {
+        if self.acceptance != OmegaAcceptanceCondition::Buchi {
+            return Err(AlternationError::UnsupportedAcceptance(
+                self.acceptance.clone(),
+            ));
+        }
+        type MacroState = (BTreeSet<DefaultIdType>, BTreeSet<DefaultIdType>);
+
+        let symbols: Vec<_> = self.alphabet.universe().collect();
+        let mut nts: NTS<A, bool, bool> = NTS::for_alphabet(self.alphabet.clone());
+        let mut indices: math::Map<MacroState, DefaultIdType> = math::Map::default();
+        let mut queue = VecDeque::new();
+
+        let origin: MacroState = (self.initial.clone(), BTreeSet::new());
+        let initial = nts.add_state(true);
+        indices.insert(origin.clone(), initial);
+        queue.push_back(origin);
+
+        while let Some((states, obligations)) = queue.pop_front() {
+            let source = indices[&(states.clone(), obligations.clone())];
+            for &symbol in &symbols {
+                let options: Vec<Vec<_>> = states
+                    .iter()
+                    .map(|q| self.matching_edges(*q, symbol).collect())
+                    .collect();
+
+                let mut successors = BTreeSet::new();
+                if options.is_empty() {
+                    successors.insert((BTreeSet::new(), BTreeSet::new()));
+                }
+                for choice in options.into_iter().multi_cartesian_product() {
+                    let mut next_states = BTreeSet::new();
+                    let mut next_obligations = BTreeSet::new();
+                    for (q, edge) in states.iter().zip(choice) {
+                        next_states.extend(edge.targets.iter().copied());
+                        let owing = obligations.is_empty() || obligations.contains(q);
+                        if owing && !edge.color.contains(0) {
+                            next_obligations.extend(edge.targets.iter().copied());
+                        }
+                    }
+                    successors.insert((next_states, next_obligations));
+                }
+
+                for successor in successors {
+                    let target = match indices.get(&successor) {
+                        Some(target) => *target,
+                        None => {
+                            let target = nts.add_state(successor.1.is_empty());
+                            indices.insert(successor.clone(), target);
+                            queue.push_back(successor);
+                            target
+                        }
+                    };
+                    nts.add_edge((source, self.alphabet.make_expression(symbol), false, target));
+                }
+            }
+        }
+
+        Ok(NBA::from_parts(nts, initial))
+    }
+}
+
+/// A parity game in which the verifier tries to ensure that the least priority occurring
+/// infinitely often is even. Every vertex must have at least one successor.
+#[derive(Debug, Default)]
+struct Arena {
+    verifier: Vec<bool>,
+    priority: Vec<Int>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl Arena {
+    fn add(&mut self, verifier: bool, priority: Int) -> usize {
+        self.verifier.push(verifier);
+        self.priority.push(priority);
+        self.successors.push(vec![]);
+        self.size() - 1
+    }
+
+    fn size(&self) -> usize {
+        self.verifier.len()
+    }
+
+    /// Computes the winning region of the verifier using Zielonka's algorithm.
+    fn solve(&self) -> BTreeSet<usize> {
+        self.zielonka((0..self.size()).collect()).0
+    }
+
+    /// Returns the vertices in `game` from which `player` (`true` for the verifier) can
+    /// force a visit to `target`.
+    fn attractor(
+        &self,
+        game: &BTreeSet<usize>,
+        target: &BTreeSet<usize>,
+        player: bool,
+    ) -> BTreeSet<usize> {
+        let mut attractor = target.clone();
+        loop {
+            let added: Vec<_> = game
+                .difference(&attractor)
+                .copied()
+                .filter(|v| {
+                    let mut successors = self.successors[*v].iter().filter(|w| game.contains(w));
+                    if self.verifier[*v] == player {
+                        successors.any(|w| attractor.contains(w))
+                    } else {
+                        successors.all(|w| attractor.contains(w))
+                    }
+                })
+                .collect();
+            if added.is_empty() {
+                return attractor;
+            }
+            attractor.extend(added);
+        }
+    }
+
+    /// Returns the winning regions of the verifier and the refuter in the subgame `game`.
+    fn zielonka(&self, game: BTreeSet<usize>) -> (BTreeSet<usize>, BTreeSet<usize>) {
+        let Some(min) = game.iter().map(|v| self.priority[*v]).min() else {
+            return (BTreeSet::new(), BTreeSet::new());
+        };
+        let player = min % 2 == 0;
+        let target = game
+            .iter()
+            .copied()
+            .filter(|v| self.priority[*v] == min)
+            .collect();
+        let attractor = self.attractor(&game, &target, player);
+
+        let (verifier, refuter) = self.zielonka(game.difference(&attractor).copied().collect());
+        let opponent_region = if player { refuter } else { verifier };
+        if opponent_region.is_empty() {
+            return if player {
+                (game, BTreeSet::new())
+            } else {
+                (BTreeSet::new(), game)
+            };
+        }
+
+        let attractor = self.attractor(&game, &opponent_region, !player);
+        let (mut verifier, mut refuter) =
+            self.zielonka(game.difference(&attractor).copied().collect());
+        if player {
+            refuter.extend(attractor);
+        } else {
+            verifier.extend(attractor);
+        }
+        (verifier, refuter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlternatingOmegaAutomaton, AlternationError};
+    use crate::automaton::{AcceptanceMask, OmegaAcceptanceCondition};
+    use crate::core::{
+        alphabet::{Alphabet, CharAlphabet},
+        upw,
+        word::ReducedOmegaWord,
+    };
+    use crate::hoa::input::hoa_to_alternating;
+
+    /// Accepts all words over `a` and `b` that contain infinitely many `a` and infinitely
+    /// many `b` by spawning one branch for each requirement.
+    fn infinitely_often_a_and_b() -> AlternatingOmegaAutomaton<CharAlphabet> {
+        let mut aut = AlternatingOmegaAutomaton::new(
+            CharAlphabet::of_size(2),
+            OmegaAcceptanceCondition::Buchi,
+        );
+        let [spawn, a, b] = [aut.add_state(), aut.add_state(), aut.add_state()];
+        let marked = || AcceptanceMask::from_iter([0]);
+        for sym in ['a', 'b'] {
+            aut.add_edge(spawn, sym, [a, b], AcceptanceMask::default());
+        }
+        aut.add_edge(a, 'a', [a], marked());
+        aut.add_edge(a, 'b', [a], AcceptanceMask::default());
+        aut.add_edge(b, 'b', [b], marked());
+        aut.add_edge(b, 'a', [b], AcceptanceMask::default());
+        aut.set_initial([spawn]);
+        aut
+    }
+
+    #[test]
+    fn alternating_membership() {
+        let aut = infinitely_often_a_and_b();
+        assert_eq!(aut.accepts(upw!("ab")), Ok(true));
+        assert_eq!(aut.accepts(upw!("bbb", "aab")), Ok(true));
+        assert_eq!(aut.accepts(upw!("a")), Ok(false));
+        assert_eq!(aut.accepts(upw!("ab", "b")), Ok(false));
+
+        let mut cobuchi = aut.clone();
+        cobuchi.acceptance = OmegaAcceptanceCondition::CoBuchi;
+        assert_eq!(cobuchi.accepts(upw!("ab")), Ok(false));
+        assert_eq!(cobuchi.accepts(upw!("a")), Ok(false));
+
+        let mut rabin = aut;
+        rabin.acceptance = OmegaAcceptanceCondition::Rabin;
+        assert_eq!(
+            rabin.accepts(upw!("ab")),
+            Err(AlternationError::UnsupportedAcceptance(
+                OmegaAcceptanceCondition::Rabin
+            ))
+        );
+    }
+
+    #[test]
+    fn alternating_parity_without_marks() {
+        let mut aut = infinitely_often_a_and_b();
+        for (acceptance, accepts_unmarked) in [
+            (OmegaAcceptanceCondition::Parity(0, 1), true),
+            (OmegaAcceptanceCondition::Parity(0, 2), false),
+            (OmegaAcceptanceCondition::MaxParity(0, 1), false),
+            (OmegaAcceptanceCondition::MaxParity(0, 2), false),
+        ] {
+            aut.acceptance = acceptance;
+            // the branch for `b` only sees unmarked edges
+            assert_eq!(aut.accepts(upw!("a")), Ok(accepts_unmarked));
+        }
+
+        // min even parity with three sets, priority 1 is seen infinitely often on `b`
+        aut.acceptance = OmegaAcceptanceCondition::Parity(0, 2);
+        aut.edges[2]
+            .iter_mut()
+            .for_each(|edge| edge.color = AcceptanceMask::from_iter([1]));
+        assert_eq!(aut.accepts(upw!("ab")), Ok(false));
+        aut.edges[2]
+            .iter_mut()
+            .for_each(|edge| edge.color = AcceptanceMask::from_iter([0, 1]));
+        assert_eq!(aut.accepts(upw!("ab")), Ok(true));
+    }
+
+    #[test]
+    fn miyano_hayashi() {
+        let aut = infinitely_often_a_and_b();
+        let nba = aut.to_nba().unwrap();
+        for (spoke, cycle) in [
+            ("", "ab"),
+            ("bbb", "aab"),
+            ("", "a"),
+            ("ab", "b"),
+            ("a", "abbb"),
+            ("ba", "bba"),
+        ] {
+            let word = ReducedOmegaWord::ultimately_periodic(spoke, cycle);
+            assert_eq!(
+                aut.accepts(&word),
+                Ok(nba.accepts(&word)),
+                "mismatch on {word:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn alternating_hoa_roundtrip() {
+        let hoa = r#"HOA: v1
+AP: 1 "a"
+States: 3
+Start: 0
+acc-name: Buchi
+Acceptance: 1 Inf(0)
+properties: trans-labels explicit-labels trans-acc univ-branch
+--BODY--
+State: 0
+[t] 1&2
+State: 1
+[0] 1 {0}
+[!0] 1
+State: 2
+[!0] 2 {0}
+[0] 2
+--END--
+"#;
+        let aut = hoa_to_alternating(hoa)
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        assert_eq!(aut.size(), 3);
+        let written = aut.to_hoa();
+        assert_eq!(written, hoa);
+        let reparsed = hoa_to_alternating(&written)
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        assert_eq!(reparsed.to_hoa(), written);
+
+        let nba = aut.to_nba().unwrap();
+        let symbols: Vec<_> = aut.alphabet().universe().collect();
+        for &x in &symbols {
+            for &y in &symbols {
+                let word = ReducedOmegaWord::ultimately_periodic(vec![x], vec![x, y]);
+                assert_eq!(aut.accepts(&word), Ok(x != y));
+                assert_eq!(nba.accepts(&word), x != y);
+            }
+        }
+    }
+}
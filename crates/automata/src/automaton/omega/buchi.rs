@@ -16,6 +16,7 @@ use automata_core::word::ReducedOmegaWord;
 /// This type will rarely be used on its own, for the automaton that makes
 /// use of it, see [`DBA`].
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuchiCondition;
 
 impl<T: Deterministic<EdgeColor = bool>> Semantics<T, true> for BuchiCondition {
@@ -93,3 +94,29 @@ where
         self.give_word().is_none()
     }
 }
+
+/// Defines the [`Semantics`] of a deterministic co-Büchi automaton (DCBA), which is dual
+/// to the [`BuchiCondition`]. It accepts if all of the transitions that are taken
+/// infinitely often are colored with `false`, and rejects if one of them is colored
+/// with `true`.
+///
+/// For the automaton that makes use of it, see [`DCBA`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoBuchiCondition;
+
+impl<T: Deterministic<EdgeColor = bool>> Semantics<T, true> for CoBuchiCondition {
+    type Observer = run::GreatestEdgeColor<T>;
+    type Output = bool;
+    fn evaluate(&self, observed: <Self::Observer as run::Observer<T>>::Current) -> Self::Output {
+        !observed
+    }
+}
+
+/// A deterministic co-Büchi automaton (DCBA) is a deterministic automaton with co-Büchi
+/// acceptance condition. It accepts a word if it has a successful infinite run that takes
+/// rejecting transitions (i.e. ones that are labeled with `true`) only finitely often.
+pub type DCBA<A = CharAlphabet, Q = Void, D = DTS<A, Q, bool>> =
+    InfiniteWordAutomaton<A, CoBuchiCondition, Q, bool, true, D>;
+/// Helper trait for creating a [`DCBA`] from a given transition system.
+pub type IntoDCBA<T> = DCBA<<T as TransitionSystem>::Alphabet, StateColor<T>, T>;
@@ -0,0 +1,326 @@
+use std::collections::BTreeSet;
+
+use thiserror::Error;
+
+use crate::TransitionSystem;
+use crate::automaton::{DPA, IntoNBA};
+use crate::core::{
+    Int,
+    alphabet::{Alphabet, Expression},
+};
+use crate::ts::{IsEdge, StateIndex, SymbolOf};
+
+use super::explore_dpa_bounded;
+
+/// Errors that can occur when determinizing an omega automaton.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum DeterminizationError {
+    /// The deterministic automaton would have more than the given number of states.
+    #[error("determinization exceeds the limit of {0} states")]
+    StateLimitExceeded(usize),
+}
+
+/// A node in a [`HistoryTree`]. The name of a node is its position in the tree, nodes
+/// are ordered by age, so the parent of a node is always older than the node itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HistoryNode<Q> {
+    parent: Option<usize>,
+    label: BTreeSet<Q>,
+}
+
+/// A history tree (or compact Safra tree) as used in Piterman's determinization construction.
+/// Each node is labeled with a set of states of the nondeterministic automaton. The labels
+/// of the children of a node are pairwise disjoint and their union is a strict subset of
+/// the label of the parent. The empty tree (without any nodes) represents the rejecting sink.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HistoryTree<Q> {
+    nodes: Vec<HistoryNode<Q>>,
+}
+
+impl<Q: Copy + Ord> HistoryTree<Q> {
+    fn new(initial: Q) -> Self {
+        Self {
+            nodes: vec![HistoryNode {
+                parent: None,
+                label: BTreeSet::from([initial]),
+            }],
+        }
+    }
+
+    /// Computes the successor tree and the emitted (min even) priority. The function
+    /// `post` gives for every state the successors of that state together with a flag
+    /// indicating whether the corresponding transition is accepting. The value
+    /// `max_nodes` is an upper bound on the number of nodes that a tree may have.
+    fn successor<F>(&self, post: F, max_nodes: usize) -> (Int, Self)
+    where
+        F: Fn(Q) -> Vec<(Q, bool)>,
+    {
+        let old = self.nodes.len();
+        let mut nodes = Vec::with_capacity(2 * old);
+        let mut spawned = Vec::new();
+
+        // update the labels and spawn a new youngest child for every node, which holds
+        // the states that are reached via an accepting transition
+        for (name, node) in self.nodes.iter().enumerate() {
+            let mut label = BTreeSet::new();
+            let mut accepting = BTreeSet::new();
+            for &q in &node.label {
+                for (p, acc) in post(q) {
+                    label.insert(p);
+                    if acc {
+                        accepting.insert(p);
+                    }
+                }
+            }
+            nodes.push(HistoryNode {
+                parent: node.parent,
+                label,
+            });
+            if !accepting.is_empty() {
+                spawned.push(HistoryNode {
+                    parent: Some(name),
+                    label: accepting,
+                });
+            }
+        }
+        nodes.extend(spawned);
+
+        let mut children = vec![vec![]; nodes.len()];
+        for (name, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                children[parent].push(name);
+            }
+        }
+
+        // horizontal merge: a state is only kept in the oldest branch containing it
+        if !nodes.is_empty() {
+            Self::horizontal_merge(&mut nodes, &children, 0, &BTreeSet::new());
+        }
+
+        // remove nodes with an empty label and collapse nodes whose label is covered by
+        // their children, the latter is a breakpoint and marks the node as good
+        let mut removed = vec![false; nodes.len()];
+        let mut priority = 2 * max_nodes as Int + 1;
+        for name in 0..nodes.len() {
+            if removed[name] || !nodes[name].label.is_empty() {
+                continue;
+            }
+            removed[name] = true;
+        }
+        for name in 0..nodes.len() {
+            if removed[name] || children[name].iter().all(|c| removed[*c]) {
+                continue;
+            }
+            let covered: usize = children[name]
+                .iter()
+                .filter(|c| !removed[**c])
+                .map(|c| nodes[*c].label.len())
+                .sum();
+            if covered == nodes[name].label.len() {
+                Self::remove_descendants(&children, &mut removed, name);
+                if name < old {
+                    priority = priority.min(2 * name as Int + 2);
+                }
+            }
+        }
+        if let Some(name) = (0..old).find(|name| removed[*name]) {
+            priority = priority.min(2 * name as Int + 1);
+        }
+
+        // rename the remaining nodes while preserving their relative age
+        let mut renaming = vec![None; nodes.len()];
+        let mut out = Vec::with_capacity(nodes.len());
+        for (name, node) in nodes.into_iter().enumerate() {
+            if removed[name] {
+                continue;
+            }
+            renaming[name] = Some(out.len());
+            out.push(HistoryNode {
+                parent: node
+                    .parent
+                    .map(|p| renaming[p].expect("parent is older and kept")),
+                label: node.label,
+            });
+        }
+
+        (priority, Self { nodes: out })
+    }
+
+    fn horizontal_merge(
+        nodes: &mut [HistoryNode<Q>],
+        children: &[Vec<usize>],
+        name: usize,
+        forbidden: &BTreeSet<Q>,
+    ) {
+        nodes[name].label.retain(|q| !forbidden.contains(q));
+        let mut forbidden = forbidden.clone();
+        for &child in &children[name] {
+            Self::horizontal_merge(nodes, children, child, &forbidden);
+            forbidden.extend(nodes[child].label.iter().copied());
+        }
+    }
+
+    fn remove_descendants(children: &[Vec<usize>], removed: &mut [bool], name: usize) {
+        for &child in &children[name] {
+            removed[child] = true;
+            Self::remove_descendants(children, removed, child);
+        }
+    }
+}
+
+impl<D> IntoNBA<D>
+where
+    D: TransitionSystem<StateColor = bool, EdgeColor = bool>,
+{
+    /// Determinizes `self` into an equivalent [`DPA`] using Piterman's construction, which
+    /// tracks the runs of `self` in history trees. Acceptance of `self` is first made
+    /// transition-based by considering every edge leaving an accepting state as accepting.
+    ///
+    /// The deterministic automaton has an edge for every symbol in the universe of the
+    /// alphabet, so in case of a [`crate::core::alphabet::PropAlphabet`], there is one edge
+    /// for every valuation of the atomic propositions. As the construction may lead to
+    /// an exponential blowup, consider using [`Self::determinize_with_limit`].
+    pub fn determinize(&self) -> DPA<D::Alphabet> {
+        self.determinize_with_limit(usize::MAX)
+            .expect("determinization is not limited")
+    }
+
+    /// Works like [`Self::determinize`], but aborts with
+    /// [`DeterminizationError::StateLimitExceeded`] if the resulting [`DPA`] would have
+    /// more than `limit` states.
+    ///
+    /// Panics if `self` has too many states for the priorities of the resulting [`DPA`] to be
+    /// representable as an [`Int`].
+    pub fn determinize_with_limit(
+        &self,
+        limit: usize,
+    ) -> Result<DPA<D::Alphabet>, DeterminizationError> {
+        let alphabet = self.ts.alphabet();
+        let max_nodes = self.ts.size();
+        assert!(
+            2 * max_nodes < Int::MAX as usize,
+            "priorities of the resulting DPA do not fit into Int"
+        );
+        let symbols: Vec<SymbolOf<D>> = alphabet.universe().collect();
+
+        let post = |q: StateIndex<D>, symbol: SymbolOf<D>| {
+            let accepting = self.ts.state_color(q).expect("state must exist");
+            self.ts
+                .edges_from(q)
+                .expect("state must exist")
+                .filter(|edge| edge.expression().matched_by(symbol))
+                .map(|edge| (edge.target(), accepting || edge.color()))
+                .collect::<Vec<_>>()
+        };
+
+        explore_dpa_bounded(
+            alphabet,
+            HistoryTree::new(self.initial),
+            limit,
+            |tree: &HistoryTree<StateIndex<D>>| {
+                symbols
+                    .iter()
+                    .map(|&symbol| {
+                        let (priority, successor) = tree.successor(|q| post(q, symbol), max_nodes);
+                        (alphabet.make_expression(symbol), priority, successor)
+                    })
+                    .collect()
+            },
+        )
+        .ok_or(DeterminizationError::StateLimitExceeded(limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeterminizationError;
+    use crate::automaton::NBA;
+    use crate::core::{alphabet::CharAlphabet, upw};
+    use crate::hoa::input::hoa_to_ts;
+    use crate::random::{generate_random_nba, generate_random_omega_words};
+    use crate::{NTS, TransitionSystem};
+    use automata_core::alphabet::Alphabet;
+    use automata_core::word::ReducedOmegaWord;
+
+    #[test]
+    fn determinize_eventually_always_a() {
+        let nba: NBA = NBA::from_parts(
+            NTS::builder()
+                .with_state_colors([false, true])
+                .with_transitions([
+                    (0, 'a', false, 0),
+                    (0, 'b', false, 0),
+                    (0, 'a', false, 1),
+                    (1, 'a', false, 1),
+                ])
+                .into_nts(),
+            0,
+        );
+        let dpa = nba.determinize();
+        assert!(dpa.accepts(upw!("a")));
+        assert!(dpa.accepts(upw!("babb", "a")));
+        assert!(!dpa.accepts(upw!("ab")));
+        assert!(!dpa.accepts(upw!("a", "aab")));
+
+        assert_eq!(
+            nba.determinize_with_limit(1),
+            Err(DeterminizationError::StateLimitExceeded(1))
+        );
+        assert!(nba.determinize_with_limit(dpa.size()).is_ok());
+    }
+
+    #[test]
+    fn determinize_random_nbas() {
+        let alphabet = CharAlphabet::of_size(2);
+        for _ in 0..40 {
+            let nba = generate_random_nba(2, 4, 0.3, 0.3);
+            let dpa = nba.determinize();
+            for word in generate_random_omega_words(&alphabet, 0, 4, 1, 4, 30) {
+                assert_eq!(
+                    nba.accepts(&word),
+                    dpa.accepts(&word),
+                    "mismatch on {word:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn determinize_propositional() {
+        // accepts all words in which `a` holds infinitely often and `b` eventually always holds
+        let hoa = r#"HOA: v1
+States: 2
+Start: 0
+AP: 2 "a" "b"
+acc-name: Buchi
+Acceptance: 1 Inf(0)
+--BODY--
+State: 0
+[t] 0
+[1] 1
+State: 1
+[0&1] 1 {0}
+[!0&1] 1
+--END--
+"#;
+        let automaton = hoa_to_ts::<false>(hoa)
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        let nba = NBA::try_from(automaton).expect("has Büchi acceptance");
+        let dpa = nba.determinize();
+
+        let symbols: Vec<_> = nba.ts().alphabet().universe().collect();
+        assert_eq!(symbols.len(), 4);
+        for &x in &symbols {
+            for &y in &symbols {
+                for &z in &symbols {
+                    let word = ReducedOmegaWord::ultimately_periodic(vec![x], vec![y, z]);
+                    assert_eq!(nba.accepts(&word), dpa.accepts(&word));
+                }
+            }
+        }
+        let witness = nba.give_word().expect("language is not empty");
+        assert!(dpa.accepts(&witness));
+    }
+}
@@ -0,0 +1,449 @@
+use std::fmt::Display;
+
+use crate::automaton::{AcceptanceMask, InfiniteWordAutomaton, Semantics};
+use crate::core::{Int, Void, alphabet::CharAlphabet, math::OrderedSet};
+use crate::ts::{Deterministic, StateColor, run};
+use crate::{DTS, TransitionSystem};
+use itertools::Itertools;
+
+/// A deterministic Emerson-Lei automaton (DELA) uses an [`EmersonLeiCondition`] to determine
+/// acceptance. Edges are labeled with an [`AcceptanceMask`], i.e. a set of acceptance sets
+/// that the edge belongs to, and the condition is a positive boolean formula over atoms of
+/// the form `Fin(i)` and `Inf(i)`. This is the most general acceptance condition that can be
+/// expressed in the HOA format.
+pub type DELA<A = CharAlphabet, Q = Void, D = DTS<A, Q, AcceptanceMask>> =
+    InfiniteWordAutomaton<A, EmersonLeiCondition, Q, AcceptanceMask, true, D>;
+/// Helper type alias for casting a given transition system `T` into a [`DELA`].
+pub type IntoDELA<T> = DELA<<T as TransitionSystem>::Alphabet, StateColor<T>, T>;
+
+/// An atom of an [`EmersonLeiCondition`] refers to an acceptance set or its complement.
+/// An edge visits a positive atom if it belongs to the acceptance set, and it visits a
+/// negative atom if it does not belong to the acceptance set.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmersonLeiAtom {
+    /// Refers to the acceptance set with the given index.
+    Positive(Int),
+    /// Refers to the complement of the acceptance set with the given index.
+    Negative(Int),
+}
+
+impl EmersonLeiAtom {
+    /// Returns the index of the acceptance set that this atom refers to.
+    pub fn set(&self) -> Int {
+        match self {
+            EmersonLeiAtom::Positive(i) | EmersonLeiAtom::Negative(i) => *i,
+        }
+    }
+
+    /// Returns true if and only if an edge that is labeled with `mask` visits this atom.
+    pub fn visited_by(&self, mask: &AcceptanceMask) -> bool {
+        match self {
+            EmersonLeiAtom::Positive(i) => mask.contains(*i),
+            EmersonLeiAtom::Negative(i) => !mask.contains(*i),
+        }
+    }
+}
+
+/// An Emerson-Lei condition is a positive boolean formula over atoms `Fin(i)` and `Inf(i)`,
+/// where `i` is an [`EmersonLeiAtom`]. It is evaluated on the set of [`AcceptanceMask`]s that
+/// appear infinitely often in a run, where `Inf(i)` holds if some mask in this set visits `i`
+/// and `Fin(i)` holds if no mask in the set visits `i`.
+///
+/// Such a condition can be brought into a normal form with [`Self::simplified`] and
+/// [`Self::classify`] recognizes formulas that are of a well-known shape, see
+/// [`EmersonLeiClass`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmersonLeiCondition {
+    /// A constant boolean value.
+    Boolean(bool),
+    /// Satisfied if the atom is visited only finitely often.
+    Fin(EmersonLeiAtom),
+    /// Satisfied if the atom is visited infinitely often.
+    Inf(EmersonLeiAtom),
+    /// Satisfied if all subformulas are satisfied.
+    And(Vec<EmersonLeiCondition>),
+    /// Satisfied if at least one subformula is satisfied.
+    Or(Vec<EmersonLeiCondition>),
+}
+
+/// Classifies an [`EmersonLeiCondition`] into one of the well-known types of acceptance
+/// conditions. This is produced by [`EmersonLeiCondition::classify`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EmersonLeiClass {
+    /// The condition is constant, i.e. accepts everything or nothing.
+    Boolean(bool),
+    /// A Büchi condition `Inf(i)`.
+    Buchi(Int),
+    /// A co-Büchi condition `Fin(i)`.
+    CoBuchi(Int),
+    /// A generalized Büchi condition `Inf(i_1) & .. & Inf(i_n)`.
+    GeneralizedBuchi(Vec<Int>),
+    /// A generalized co-Büchi condition `Fin(i_1) | .. | Fin(i_n)`.
+    GeneralizedCoBuchi(Vec<Int>),
+    /// A min even parity condition with the given number of priorities, where priority `i`
+    /// corresponds to the acceptance set `i`.
+    Parity(Int),
+    /// A Rabin condition, which is a disjunction of pairs `Fin(fin) & Inf(inf)` that are
+    /// given as `(fin, inf)`.
+    Rabin(Vec<(Int, Int)>),
+    /// A Streett condition, which is a conjunction of pairs `Fin(fin) | Inf(inf)` that are
+    /// given as `(fin, inf)`.
+    Streett(Vec<(Int, Int)>),
+}
+
+impl EmersonLeiCondition {
+    /// Creates the condition `Fin(set)`.
+    pub fn fin(set: Int) -> Self {
+        Self::Fin(EmersonLeiAtom::Positive(set))
+    }
+
+    /// Creates the condition `Inf(set)`.
+    pub fn inf(set: Int) -> Self {
+        Self::Inf(EmersonLeiAtom::Positive(set))
+    }
+
+    /// Creates a Büchi condition, which is satisfied if set `0` is visited infinitely often.
+    pub fn buchi() -> Self {
+        Self::inf(0)
+    }
+
+    /// Creates a co-Büchi condition, which is satisfied if set `0` is visited finitely often.
+    pub fn co_buchi() -> Self {
+        Self::fin(0)
+    }
+
+    /// Creates a generalized Büchi condition over the sets `0..sets`.
+    pub fn generalized_buchi(sets: Int) -> Self {
+        Self::And((0..sets).map(Self::inf).collect())
+    }
+
+    /// Creates a min even parity condition with the given number of priorities, where
+    /// priority `i` is encoded by set `i`. This is the formula
+    /// `Inf(0) | (Fin(1) & (Inf(2) | ..))` that is used in the HOA format.
+    pub fn parity(priorities: Int) -> Self {
+        let Some(last) = priorities.checked_sub(1) else {
+            return Self::Boolean(false);
+        };
+        let innermost = if last % 2 == 0 {
+            Self::inf(last)
+        } else {
+            Self::fin(last)
+        };
+        (0..last).rev().fold(innermost, |acc, i| {
+            if i % 2 == 0 {
+                Self::Or(vec![Self::inf(i), acc])
+            } else {
+                Self::And(vec![Self::fin(i), acc])
+            }
+        })
+    }
+
+    /// Creates a Rabin condition with the given number of pairs, where pair `i` is
+    /// `Fin(2i) & Inf(2i + 1)`.
+    pub fn rabin(pairs: Int) -> Self {
+        Self::Or(
+            (0..pairs)
+                .map(|i| Self::And(vec![Self::fin(2 * i), Self::inf(2 * i + 1)]))
+                .collect(),
+        )
+    }
+
+    /// Creates a Streett condition with the given number of pairs, where pair `i` is
+    /// `Fin(2i) | Inf(2i + 1)`.
+    pub fn streett(pairs: Int) -> Self {
+        Self::And(
+            (0..pairs)
+                .map(|i| Self::Or(vec![Self::fin(2 * i), Self::inf(2 * i + 1)]))
+                .collect(),
+        )
+    }
+
+    /// Evaluates the condition, where `inf` decides whether an atom is visited infinitely often.
+    pub fn evaluate_with<F: Fn(&EmersonLeiAtom) -> bool>(&self, inf: &F) -> bool {
+        match self {
+            EmersonLeiCondition::Boolean(b) => *b,
+            EmersonLeiCondition::Fin(atom) => !inf(atom),
+            EmersonLeiCondition::Inf(atom) => inf(atom),
+            EmersonLeiCondition::And(conjuncts) => conjuncts.iter().all(|c| c.evaluate_with(inf)),
+            EmersonLeiCondition::Or(disjuncts) => disjuncts.iter().any(|d| d.evaluate_with(inf)),
+        }
+    }
+
+    /// Returns true if and only if the condition is satisfied by the given set of
+    /// [`AcceptanceMask`]s, which should be the masks that are seen infinitely often.
+    pub fn satisfied(&self, infset: &OrderedSet<AcceptanceMask>) -> bool {
+        self.evaluate_with(&|atom| infset.iter().any(|mask| atom.visited_by(mask)))
+    }
+
+    /// Returns the set of all atoms that occur in the condition.
+    pub fn atoms(&self) -> OrderedSet<EmersonLeiAtom> {
+        match self {
+            EmersonLeiCondition::Boolean(_) => OrderedSet::default(),
+            EmersonLeiCondition::Fin(atom) | EmersonLeiCondition::Inf(atom) => [*atom].into(),
+            EmersonLeiCondition::And(subs) | EmersonLeiCondition::Or(subs) => {
+                subs.iter().flat_map(|sub| sub.atoms()).collect()
+            }
+        }
+    }
+
+    /// Returns the number of acceptance sets that the condition refers to, which is one more
+    /// than the largest index of a set that occurs in it.
+    pub fn set_count(&self) -> usize {
+        self.atoms()
+            .iter()
+            .map(|atom| atom.set() as usize + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Brings the condition into a normal form. Nested conjunctions and disjunctions are
+    /// flattened, constants are propagated and the subformulas of each conjunction or
+    /// disjunction are sorted and deduplicated. Conjunctions and disjunctions with a single
+    /// subformula are replaced by that subformula.
+    pub fn simplified(self) -> Self {
+        match self {
+            EmersonLeiCondition::And(conjuncts) => Self::simplify_junction(conjuncts, true),
+            EmersonLeiCondition::Or(disjuncts) => Self::simplify_junction(disjuncts, false),
+            atomic => atomic,
+        }
+    }
+
+    fn simplify_junction(subs: Vec<Self>, conjunction: bool) -> Self {
+        let mut out = vec![];
+        for sub in subs.into_iter().map(Self::simplified) {
+            match sub {
+                // the neutral element can be dropped
+                Self::Boolean(b) if b == conjunction => {}
+                // the absorbing element decides the whole junction
+                Self::Boolean(b) => return Self::Boolean(b),
+                Self::And(inner) if conjunction => out.extend(inner),
+                Self::Or(inner) if !conjunction => out.extend(inner),
+                other => out.push(other),
+            }
+        }
+        out.sort();
+        out.dedup();
+
+        match out.len() {
+            0 => Self::Boolean(conjunction),
+            1 => out.pop().unwrap(),
+            _ if conjunction => Self::And(out),
+            _ => Self::Or(out),
+        }
+    }
+
+    /// Attempts to classify the condition as one of the well-known types of acceptance
+    /// conditions that are listed in [`EmersonLeiClass`]. Returns `None` if the (simplified)
+    /// formula does not have the shape of any of them. Note that this is a purely syntactic
+    /// check, so a condition may be equivalent to one of the types without being recognized.
+    pub fn classify(&self) -> Option<EmersonLeiClass> {
+        use EmersonLeiAtom::Positive;
+        let simplified = self.clone().simplified();
+
+        let sets = simplified.set_count() as Int;
+        if sets >= 2 && simplified == Self::parity(sets).simplified() {
+            return Some(EmersonLeiClass::Parity(sets));
+        }
+
+        let pair = |sub: &Self| match sub {
+            Self::And(pair) | Self::Or(pair) => match pair.as_slice() {
+                [Self::Fin(Positive(fin)), Self::Inf(Positive(inf))] => Some((*fin, *inf)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match &simplified {
+            Self::Boolean(b) => Some(EmersonLeiClass::Boolean(*b)),
+            Self::Inf(Positive(i)) => Some(EmersonLeiClass::Buchi(*i)),
+            Self::Fin(Positive(i)) => Some(EmersonLeiClass::CoBuchi(*i)),
+            Self::And(subs) => {
+                if let Some(sets) = subs
+                    .iter()
+                    .map(|sub| match sub {
+                        Self::Inf(Positive(i)) => Some(*i),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                {
+                    Some(EmersonLeiClass::GeneralizedBuchi(sets))
+                } else if subs.iter().all(|sub| matches!(sub, Self::Or(_))) {
+                    subs.iter()
+                        .map(pair)
+                        .collect::<Option<Vec<_>>>()
+                        .map(EmersonLeiClass::Streett)
+                } else {
+                    pair(&simplified).map(|p| EmersonLeiClass::Rabin(vec![p]))
+                }
+            }
+            Self::Or(subs) => {
+                if let Some(sets) = subs
+                    .iter()
+                    .map(|sub| match sub {
+                        Self::Fin(Positive(i)) => Some(*i),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                {
+                    Some(EmersonLeiClass::GeneralizedCoBuchi(sets))
+                } else if subs.iter().all(|sub| matches!(sub, Self::And(_))) {
+                    subs.iter()
+                        .map(pair)
+                        .collect::<Option<Vec<_>>>()
+                        .map(EmersonLeiClass::Rabin)
+                } else {
+                    pair(&simplified).map(|p| EmersonLeiClass::Streett(vec![p]))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for EmersonLeiAtom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmersonLeiAtom::Positive(i) => write!(f, "{i}"),
+            EmersonLeiAtom::Negative(i) => write!(f, "!{i}"),
+        }
+    }
+}
+
+/// Displays the condition in the syntax that is used by the HOA format.
+impl Display for EmersonLeiCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmersonLeiCondition::Boolean(true) => write!(f, "t"),
+            EmersonLeiCondition::Boolean(false) => write!(f, "f"),
+            EmersonLeiCondition::Fin(atom) => write!(f, "Fin({atom})"),
+            EmersonLeiCondition::Inf(atom) => write!(f, "Inf({atom})"),
+            EmersonLeiCondition::And(subs) if subs.is_empty() => write!(f, "t"),
+            EmersonLeiCondition::Or(subs) if subs.is_empty() => write!(f, "f"),
+            EmersonLeiCondition::And(subs) => {
+                write!(f, "({})", subs.iter().map(|s| s.to_string()).join(" & "))
+            }
+            EmersonLeiCondition::Or(subs) => {
+                write!(f, "({})", subs.iter().map(|s| s.to_string()).join(" | "))
+            }
+        }
+    }
+}
+
+impl<T: Deterministic<EdgeColor = AcceptanceMask>> Semantics<T, true> for EmersonLeiCondition {
+    type Output = bool;
+    type Observer = run::EdgeColorSet<T>;
+    fn evaluate(&self, observed: <Self::Observer as run::Observer<T>>::Current) -> Self::Output {
+        self.evaluate_with(&|atom| observed.0.iter().any(|mask| atom.visited_by(mask)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ts::TSBuilder;
+    use automata_core::upw;
+
+    fn mask<const N: usize>(sets: [Int; N]) -> AcceptanceMask {
+        sets.into_iter().collect()
+    }
+
+    #[test]
+    fn emerson_lei_automaton() {
+        // accepts if `a` and `b` appear infinitely often, or if `c` appears finitely often
+        let condition = EmersonLeiCondition::Or(vec![
+            EmersonLeiCondition::And(vec![
+                EmersonLeiCondition::inf(0),
+                EmersonLeiCondition::inf(1),
+            ]),
+            EmersonLeiCondition::Fin(EmersonLeiAtom::Negative(2)),
+        ]);
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', mask([0, 2]), 0),
+                (0, 'b', mask([1, 2]), 0),
+                (0, 'c', mask([]), 0),
+            ])
+            .into_dts();
+        let dela = DELA::from_parts_with_acceptance(ts, 0, condition);
+
+        assert!(dela.accepts(upw!("ab")));
+        assert!(dela.accepts(upw!("abc")));
+        assert!(dela.accepts(upw!("c", "ba")));
+        assert!(dela.accepts(upw!("a")));
+        assert!(!dela.accepts(upw!("ac")));
+        assert!(!dela.accepts(upw!("c")));
+    }
+
+    #[test]
+    fn simplification() {
+        use EmersonLeiCondition::*;
+        let condition = And(vec![
+            And(vec![EmersonLeiCondition::inf(1), Boolean(true)]),
+            Or(vec![Boolean(false), EmersonLeiCondition::fin(0)]),
+            EmersonLeiCondition::inf(1),
+        ]);
+        assert_eq!(
+            condition.simplified(),
+            And(vec![
+                EmersonLeiCondition::fin(0),
+                EmersonLeiCondition::inf(1)
+            ])
+        );
+        assert_eq!(
+            Or(vec![EmersonLeiCondition::inf(1), Boolean(true)]).simplified(),
+            Boolean(true)
+        );
+        assert_eq!(And(vec![]).simplified(), Boolean(true));
+        assert_eq!(
+            EmersonLeiCondition::parity(1).simplified(),
+            EmersonLeiCondition::buchi()
+        );
+    }
+
+    #[test]
+    fn classification() {
+        use EmersonLeiClass::*;
+        for (condition, class) in [
+            (EmersonLeiCondition::Boolean(true), Some(Boolean(true))),
+            (EmersonLeiCondition::buchi(), Some(Buchi(0))),
+            (EmersonLeiCondition::co_buchi(), Some(CoBuchi(0))),
+            (
+                EmersonLeiCondition::generalized_buchi(3),
+                Some(GeneralizedBuchi(vec![0, 1, 2])),
+            ),
+            (EmersonLeiCondition::parity(4), Some(Parity(4))),
+            (EmersonLeiCondition::rabin(1), Some(Rabin(vec![(0, 1)]))),
+            (
+                EmersonLeiCondition::rabin(2),
+                Some(Rabin(vec![(0, 1), (2, 3)])),
+            ),
+            (
+                EmersonLeiCondition::streett(2),
+                Some(Streett(vec![(0, 1), (2, 3)])),
+            ),
+            (
+                EmersonLeiCondition::Or(vec![
+                    EmersonLeiCondition::inf(0),
+                    EmersonLeiCondition::Inf(EmersonLeiAtom::Negative(1)),
+                ]),
+                None,
+            ),
+        ] {
+            assert_eq!(condition.classify(), class, "wrong class for {condition}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            EmersonLeiCondition::parity(3).simplified().to_string(),
+            "(Inf(0) | (Fin(1) & Inf(2)))"
+        );
+        assert_eq!(
+            EmersonLeiCondition::Fin(EmersonLeiAtom::Negative(2)).to_string(),
+            "Fin(!2)"
+        );
+    }
+}
@@ -21,6 +21,7 @@ pub type IntoDMA<T> = DMA<<T as TransitionSystem>::Alphabet, StateColor<T>, Edge
 /// is satisfied by a set (usually the set of colors that appear infinitely often in a run),
 /// if it contains the set.
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MullerCondition<C: Color>(Vec<math::Set<C>>);
 
 impl<C: Color + Ord> MullerCondition<C> {
@@ -60,6 +61,11 @@ impl<C: Color + Ord> MullerCondition<C> {
     pub fn new(sets: Vec<math::Set<C>>) -> Self {
         Self(sets)
     }
+
+    /// Returns the sets of colors that are allowed to appear infinitely often.
+    pub fn sets(&self) -> &[math::Set<C>] {
+        &self.0
+    }
 }
 
 impl<T: Deterministic> Semantics<T, true> for MullerCondition<EdgeColor<T>> {
@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+
+use crate::automaton::{
+    BuchiCondition, InfiniteWordAutomaton, NondeterministicOmegaAutomaton, OmegaAcceptanceCondition,
+};
+use crate::core::{
+    alphabet::{Alphabet, CharAlphabet, Expression},
+    math,
+    word::{OmegaWord, ReducedOmegaWord},
+};
+use crate::ts::{ForAlphabet, IsEdge, Sproutable, StateIndex, SymbolOf};
+use crate::{NTS, TransitionSystem};
+
+/// A nondeterministic Büchi automaton (NBA) accepts an infinite word if it has a run on it
+/// which infinitely often visits an accepting state or infinitely often takes an accepting
+/// transition. States as well as edges are colored with a `bool` that indicates whether they
+/// are accepting. This allows both state-based acceptance (where all edges are colored with
+/// `false`) and transition-based acceptance (where all states are colored with `false`).
+///
+/// As the automaton is not deterministic, membership of a word is decided by
+/// [`NBA::accepts`] through an explicit product with the word, and the language can be
+/// tested for emptiness with [`NBA::is_empty`].
+pub type NBA<A = CharAlphabet, D = NTS<A, bool, bool>> =
+    InfiniteWordAutomaton<A, BuchiCondition, bool, bool, false, D>;
+/// Helper type for creating an [`NBA`] from a given transition system.
+pub type IntoNBA<T> = NBA<<T as TransitionSystem>::Alphabet, T>;
+
+impl<D> IntoNBA<D>
+where
+    D: TransitionSystem<StateColor = bool, EdgeColor = bool>,
+{
+    /// Returns `true` if the given `word` is accepted, i.e. if there exists a run of `self`
+    /// on `word` that infinitely often visits an accepting state or takes an accepting edge.
+    ///
+    /// This is decided by building the product of `self` with the lasso that represents
+    /// `word` and checking the product for emptiness.
+    pub fn accepts<W: OmegaWord<Symbol = SymbolOf<D>>>(&self, word: W) -> bool {
+        let len = word.combined_len();
+        assert!(len > 0, "an omega word must have a non-empty cycle");
+
+        let alphabet = self.ts.alphabet().clone();
+        let mut product: NTS<D::Alphabet, bool, bool> = NTS::for_alphabet(alphabet.clone());
+        let mut indices: math::Map<(StateIndex<D>, usize), _> = math::Map::default();
+        let mut queue = VecDeque::new();
+
+        let origin = (self.initial, 0);
+        let initial = product.add_state(self.state_color(self.initial));
+        indices.insert(origin, initial);
+        queue.push_back(origin);
+
+        while let Some((q, i)) = queue.pop_front() {
+            let source = indices[&(q, i)];
+            let symbol = word.nth(i).expect("position lies within the lasso");
+            let next = if i + 1 == len {
+                word.loop_index()
+            } else {
+                i + 1
+            };
+
+            for edge in self.ts.edges_from(q).expect("state must exist") {
+                if !edge.expression().matched_by(symbol) {
+                    continue;
+                }
+                let target = (edge.target(), next);
+                let target_index = match indices.get(&target) {
+                    Some(idx) => *idx,
+                    None => {
+                        let idx = product.add_state(self.state_color(edge.target()));
+                        indices.insert(target, idx);
+                        queue.push_back(target);
+                        idx
+                    }
+                };
+                product.add_edge((
+                    source,
+                    alphabet.make_expression(symbol),
+                    edge.color(),
+                    target_index,
+                ));
+            }
+        }
+
+        !NBA::from_parts(product, initial).is_empty()
+    }
+
+    /// Attempts to find an ultimately periodic word that is accepted by `self`. If the
+    /// language of `self` is empty, `None` is returned.
+    ///
+    /// The check is SCC-based: the language is non-empty if and only if some SCC that is
+    /// reachable from the initial state contains an interior edge that is accepting or
+    /// leaves an accepting state. The witness is then the lasso which leads to the source
+    /// of such an edge and loops through it.
+    pub fn give_word(&self) -> Option<ReducedOmegaWord<SymbolOf<D>>> {
+        let ts = &self.ts;
+        let reachable: math::Set<_> = ts.reachable_state_indices_from(self.initial).collect();
+
+        for scc in ts.sccs().sccs_iter() {
+            for (p, expression, color, q) in scc.interior_edges() {
+                if !reachable.contains(p) || !(*color || self.state_color(*p)) {
+                    continue;
+                }
+                let Some(symbol) = expression.symbols().next() else {
+                    continue;
+                };
+                let spoke = ts
+                    .word_from_to(self.initial, *p)
+                    .expect("we know this is reachable");
+                let mut cycle = vec![symbol];
+                cycle.extend(
+                    ts.word_from_to(*q, *p)
+                        .expect("source and target lie in the same SCC"),
+                );
+                return Some(ReducedOmegaWord::ultimately_periodic(spoke, cycle));
+            }
+        }
+        None
+    }
+
+    /// Returns the initial state of `self`.
+    pub fn initial(&self) -> StateIndex<D> {
+        self.initial
+    }
+
+    /// Returns `true` if and only if `self` accepts the empty language.
+    pub fn is_empty(&self) -> bool {
+        self.give_word().is_none()
+    }
+
+    fn state_color(&self, state: StateIndex<D>) -> bool {
+        self.ts.state_color(state).expect("state must exist")
+    }
+}
+
+impl<A: Alphabet> TryFrom<NondeterministicOmegaAutomaton<A>> for NBA<A> {
+    type Error = String;
+
+    /// Converts a [`NondeterministicOmegaAutomaton`] with a Büchi acceptance condition into
+    /// an [`NBA`] with transition-based acceptance. An edge is accepting if its
+    /// [`crate::automaton::AcceptanceMask`] contains color `0`.
+    fn try_from(value: NondeterministicOmegaAutomaton<A>) -> Result<Self, Self::Error> {
+        let (ts, initial, acceptance) = value.into_parts();
+        if !matches!(acceptance, OmegaAcceptanceCondition::Buchi) {
+            return Err(format!(
+                "cannot convert automaton with acceptance {acceptance:?} into an NBA"
+            ));
+        }
+        let ts = ts.linked_map(|_, _| false, |_, e, c, _| (e, c.as_bool()));
+        Ok(NBA::from_parts(ts, initial))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NBA;
+    use crate::core::upw;
+    use crate::hoa::input::hoa_to_ts;
+    use crate::{NTS, TransitionSystem};
+
+    /// Accepts all words over `a` and `b` that eventually only contain `a`, using
+    /// state-based acceptance.
+    fn eventually_always_a() -> NBA {
+        let ts = NTS::builder()
+            .with_state_colors([false, true])
+            .with_transitions([
+                (0, 'a', false, 0),
+                (0, 'b', false, 0),
+                (0, 'a', false, 1),
+                (1, 'a', false, 1),
+            ])
+            .into_nts();
+        NBA::from_parts(ts, 0)
+    }
+
+    #[test]
+    fn nba_membership() {
+        let nba = eventually_always_a();
+        assert!(nba.accepts(upw!("a")));
+        assert!(nba.accepts(upw!("bba", "a")));
+        assert!(!nba.accepts(upw!("ab")));
+        assert!(!nba.accepts(upw!("a", "b")));
+
+        let transition_based: NBA = NBA::from_parts(
+            NTS::builder()
+                .with_state_colors([false, false])
+                .with_transitions([
+                    (0, 'a', false, 0),
+                    (0, 'b', false, 0),
+                    (0, 'a', false, 1),
+                    (1, 'a', true, 1),
+                ])
+                .into_nts(),
+            0,
+        );
+        assert!(transition_based.accepts(upw!("b", "a")));
+        assert!(transition_based.accepts(upw!("aaa")));
+        assert!(!transition_based.accepts(upw!("ba")));
+        assert!(!transition_based.accepts(upw!("a", "bab")));
+    }
+
+    #[test]
+    fn nba_emptiness() {
+        let nba = eventually_always_a();
+        let witness = nba.give_word().expect("language is not empty");
+        assert!(nba.accepts(&witness));
+
+        let unreachable: NBA = NBA::from_parts(
+            NTS::builder()
+                .with_state_colors([false, false, true])
+                .with_transitions([(0, 'a', false, 1), (1, 'b', false, 0), (2, 'a', true, 2)])
+                .into_nts(),
+            0,
+        );
+        assert!(unreachable.is_empty());
+
+        let transient: NBA = NBA::from_parts(
+            NTS::builder()
+                .with_state_colors([true, false])
+                .with_transitions([(0, 'a', true, 1), (1, 'a', false, 1)])
+                .into_nts(),
+            0,
+        );
+        assert!(transient.is_empty());
+        assert!(!transient.accepts(upw!("a")));
+    }
+
+    #[test]
+    fn nba_from_hoa() {
+        let hoa = r#"HOA: v1
+States: 2
+Start: 0
+AP: 1 "a"
+acc-name: Buchi
+Acceptance: 1 Inf(0)
+--BODY--
+State: 0
+[t] 0
+[0] 1
+State: 1
+[0] 1 {0}
+--END--
+"#;
+        let automaton = hoa_to_ts::<false>(hoa)
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        let nba = NBA::try_from(automaton).expect("has Büchi acceptance");
+        assert_eq!(nba.ts().size(), 2);
+
+        let witness = nba.give_word().expect("language is not empty");
+        assert!(nba.accepts(&witness));
+    }
+}
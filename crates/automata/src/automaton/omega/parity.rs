@@ -42,6 +42,7 @@ pub type IntoDPA<T, Sem = MinEvenParityCondition> =
 /// [`MaxEvenParityCondition`], [`MinOddParityCondition`] and [`MaxOddParityCondition`],
 /// respectively.
 #[derive(Clone, Debug, Default, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MinEvenParityCondition;
 
 impl<T> Semantics<T, true> for MinEvenParityCondition
@@ -58,14 +59,17 @@ where
 /// Defines a [`Semantics`] that outputs `true` if the *maximum* color/priority that
 /// appears infinitely often is *even*. See also [`MinEvenParityCondition`].
 #[derive(Clone, Debug, Default, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaxEvenParityCondition;
 /// Defines a [`Semantics`] that outputs `true` if the *minimum* color/priority that
 /// appears infinitely often is *odd*. See also [`MinEvenParityCondition`].
 #[derive(Clone, Debug, Default, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MinOddParityCondition;
 /// Defines a [`Semantics`] that outputs `true` if the *maximum* color/priority that
 /// appears infinitely often is *odd*. See also [`MinEvenParityCondition`].
 #[derive(Clone, Debug, Default, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaxOddParityCondition;
 
 impl<D> IntoDPA<D>
@@ -20,12 +20,18 @@ pub type IntoDRA<T> = DRA<<T as TransitionSystem>::Alphabet, StateColor<T>, Edge
 /// Represents a Rabin condition, which is a set of [`RabinPair`]s. Such a condition is satisfied
 /// if at least one of its pairs is satisfied.
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RabinCondition<C: Color + Ord>(OrderedSet<RabinPair<C>>);
 
 /// A Rabin pair over some [`Color`] `C` consists of a set `fin` and a set `inf` of elements of type `C`.
 /// A pair is satisfied by a set (usually the set of colors that appear infinitely often in a run),
 /// if the set contains no elements of `fin` and at least one element of `inf`.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "C: serde::Deserialize<'de> + Ord"))
+)]
 pub struct RabinPair<C> {
     pub(crate) fin: BTreeSet<C>,
     pub(crate) inf: BTreeSet<C>,
@@ -41,6 +47,13 @@ where
     }
 }
 
+impl<C: Color + Ord> RabinCondition<C> {
+    /// Returns an iterator over the [`RabinPair`]s that make up the condition.
+    pub fn pairs(&self) -> impl Iterator<Item = &RabinPair<C>> {
+        self.0.iter()
+    }
+}
+
 impl<C: Color + Ord> RabinPair<C> {
     /// Creates a new pair from the given set of finite and infinite colors.
     pub fn new(fin: BTreeSet<C>, inf: BTreeSet<C>) -> Self {
@@ -59,6 +72,16 @@ impl<C: Color + Ord> RabinPair<C> {
         }
     }
 
+    /// Returns the set of colors that may only be visited finitely often.
+    pub fn fin(&self) -> &BTreeSet<C> {
+        &self.fin
+    }
+
+    /// Returns the set of colors of which at least one has to be visited infinitely often.
+    pub fn inf(&self) -> &BTreeSet<C> {
+        &self.inf
+    }
+
     /// Returns true if and only if the pair is satisfied by the given set of colors, i.e.
     /// if the set contains no color from `fin` and at least one color from `inf`.
     pub fn satisfied_by_set(&self, colors: &BTreeSet<C>) -> bool {
@@ -0,0 +1,81 @@
+use crate::automaton::{InfiniteWordAutomaton, RabinPair, Semantics};
+use crate::core::{Color, Void, alphabet::CharAlphabet, math::OrderedSet};
+use crate::ts::run::Observer;
+use crate::ts::{Deterministic, EdgeColor, StateColor, run};
+use crate::{DTS, TransitionSystem};
+
+/// A deterministic Streett automaton (DSA) uses a [`StreettCondition`] to determine
+/// acceptance. Such a condition is the dual of a [`super::RabinCondition`]: it consists of
+/// a set of [`RabinPair`]s and is satisfied by an infinite run if none of its pairs is
+/// satisfied. So for every pair, the run has to visit a color from `fin` infinitely often
+/// or all colors from `inf` only finitely often.
+pub type DSA<A = CharAlphabet, Q = Void, C = usize, D = DTS<A, Q, C>> =
+    InfiniteWordAutomaton<A, StreettCondition<C>, Q, C, true, D>;
+/// Helper type alias for casting a given transition system `T` into a [`DSA`].
+pub type IntoDSA<T> = DSA<<T as TransitionSystem>::Alphabet, StateColor<T>, EdgeColor<T>, T>;
+
+/// Represents a Streett condition, which is a set of [`RabinPair`]s. Such a condition is
+/// satisfied if none of its pairs is satisfied.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreettCondition<C: Color + Ord>(OrderedSet<RabinPair<C>>);
+
+impl<C, I> From<I> for StreettCondition<C>
+where
+    C: Color + Ord,
+    I: IntoIterator<Item = RabinPair<C>>,
+{
+    fn from(value: I) -> Self {
+        Self(value.into_iter().collect())
+    }
+}
+
+impl<C: Color + Ord> StreettCondition<C> {
+    /// Returns an iterator over the [`RabinPair`]s whose satisfaction leads to rejection.
+    pub fn pairs(&self) -> impl Iterator<Item = &RabinPair<C>> {
+        self.0.iter()
+    }
+}
+
+impl<T: Deterministic> Semantics<T, true> for StreettCondition<EdgeColor<T>>
+where
+    EdgeColor<T>: Color + Ord,
+{
+    type Output = bool;
+    type Observer = run::EdgeColorSet<T>;
+    fn evaluate(
+        &self,
+        observed: <Self::Observer as crate::ts::run::Observer<T>>::Current,
+    ) -> Self::Output {
+        let cur = observed.into_current().0;
+        !self
+            .0
+            .iter()
+            .any(|set| set.satisfied_by_iter(cur.iter().cloned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ts::TSBuilder;
+    use automata_core::upw;
+
+    #[test]
+    fn streett_automaton() {
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 0),
+                (0, 'b', 1, 1),
+                (1, 'a', 0, 0),
+                (1, 'b', 1, 1),
+            ])
+            .into_dts();
+        // infinitely many `b` imply infinitely many `a`
+        let dsa = DSA::from_parts_with_acceptance(ts, 0, [RabinPair::from_iters([0], [1])].into());
+        assert!(dsa.accepts(upw!("ba")));
+        assert!(dsa.accepts(upw!("a")));
+        assert!(!dsa.accepts(upw!("b")));
+        assert!(!dsa.accepts(upw!("a", "b")));
+    }
+}
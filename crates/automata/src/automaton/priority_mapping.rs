@@ -6,6 +6,7 @@ use automata_core::alphabet::CharAlphabet;
 use automata_core::{Int, Void};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WeakPriorityMappingSemantics<const ON_EDGES: bool>;
 
 impl<T: MooreLike> Semantics<T, false> for WeakPriorityMappingSemantics<false> {
@@ -1,16 +1,21 @@
 use super::{FiniteWordAutomaton, Semantics, StatesWithColor};
+use crate::minimization::partition_refinement::moore_partition_refinement;
 use crate::representation::CollectTs;
 use crate::ts::operations::{DefaultIfMissing, Product, ProductIndex};
 use crate::ts::run::ReachedStateColor;
-use crate::ts::{Deterministic, EdgeColor, StateIndex, SymbolOf, operations};
-use crate::{Congruence, DTS, Pointed, TransitionSystem};
-use automata_core::alphabet::CharAlphabet;
+use crate::ts::{
+    Deterministic, EdgeColor, ForAlphabet, IsEdge, Sproutable, StateIndex, SymbolOf, operations,
+};
+use crate::{Congruence, DTS, NTS, Pointed, TransitionSystem};
+use automata_core::alphabet::{CharAlphabet, Expression};
 use automata_core::{Void, math};
+use itertools::Itertools;
 
 /// Defines the [`Semantics`] that are used by a deterministic finite automaton
 /// [`DFA`]. This leads to a [`crate::core::word::FiniteWord`] being accepted if the state that it reaches
 /// is colored with `true`, and the word being rejected otherwise.
 #[derive(Clone, Copy, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReachabilityCondition;
 
 impl std::fmt::Debug for ReachabilityCondition {
@@ -159,6 +164,96 @@ where
             .collect_dfa()
     }
 
+    /// Builds a [`DFA`] accepting the reverse of the language accepted by `self`, i.e. the set of
+    /// all words whose reversal is accepted by `self`. This is done by reversing all transitions,
+    /// which yields a nondeterministic transition system whose initial states are the accepting
+    /// states of `self`, and then applying the subset construction.
+    ///
+    /// The resulting automaton only contains reachable states. It is complete, which means it may
+    /// contain a rejecting sink state corresponding to the empty set of states.
+    pub fn reversed(&self) -> DFA<D::Alphabet> {
+        let mut reversed = NTS::for_alphabet(self.alphabet().clone());
+        let mut indices = math::Map::default();
+        for q in self.state_indices() {
+            indices.insert(q, reversed.add_state(q == self.initial));
+        }
+        for q in self.state_indices() {
+            for edge in self.edges_from(q).expect("state must exist") {
+                reversed.add_edge((
+                    indices[&edge.target()],
+                    edge.expression().clone(),
+                    Void,
+                    indices[&q],
+                ));
+            }
+        }
+
+        let initial = self.accepting_states().map(|q| indices[&q]).collect_vec();
+        reversed
+            .subset_construction_from(initial)
+            .map_state_colors(|colors| colors.contains(&true))
+            .collect_dfa()
+    }
+
+    /// Minimizes `self` with Brzozowski's algorithm, which reverses and determinizes the automaton
+    /// twice (see [`Self::reversed`]). The result is the unique minimal complete [`DFA`] for the
+    /// language of `self`. In general, this is slower than the partition refinement used by
+    /// [`Self::minimize`], but it can be used to cross-check the latter.
+    pub fn minimize_brzozowski(&self) -> DFA<D::Alphabet> {
+        self.reversed().reversed()
+    }
+
+    /// Checks whether the language accepted by `self` is reverse-deterministic, meaning that the
+    /// reversal of its trim minimal automaton is again deterministic. This is the case if and
+    /// only if the minimal automaton restricted to the states that are reachable and from which
+    /// an accepting state is reachable has at most one accepting state, and no state in it has
+    /// two incoming transitions on the same symbol.
+    pub fn is_reverse_deterministic(&self) -> bool {
+        let minimal = moore_partition_refinement(self);
+
+        let reachable: math::Set<_> = minimal.reachable_state_indices().collect();
+        let accepting = reachable
+            .iter()
+            .filter(|q| minimal.state_color(**q) == Some(true))
+            .copied()
+            .collect_vec();
+        if accepting.len() > 1 {
+            return false;
+        }
+
+        let mut predecessors: math::Map<_, Vec<_>> = math::Map::default();
+        for &q in &reachable {
+            for edge in minimal.edges_from(q).expect("state must exist") {
+                for sym in edge.expression().symbols() {
+                    predecessors
+                        .entry(edge.target())
+                        .or_default()
+                        .push((sym, q));
+                }
+            }
+        }
+
+        let mut productive: math::Set<_> = accepting.iter().copied().collect();
+        let mut queue = accepting;
+        while let Some(q) = queue.pop() {
+            for (_, p) in predecessors.get(&q).into_iter().flatten() {
+                if productive.insert(*p) {
+                    queue.push(*p);
+                }
+            }
+        }
+
+        productive.iter().all(|q| {
+            predecessors
+                .get(q)
+                .into_iter()
+                .flatten()
+                .filter(|(_, p)| productive.contains(p))
+                .map(|(sym, _)| sym)
+                .all_unique()
+        })
+    }
+
     /// Attempts to separate the state `left` from the state `right` by finding a word that leads to different colors.
     /// For a [`DFA`], this means that the returned word is in the symmetric difference of
     /// the languages accepted by the two states.
@@ -187,8 +282,12 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::TransitionSystem;
     use crate::automaton::DFA;
-    use crate::ts::TSBuilder;
+    use crate::random::generate_random_ts_sized;
+    use crate::representation::IntoTs;
+    use crate::ts::{Shrinkable, TSBuilder};
+    use rand::{Rng, thread_rng};
 
     #[test]
     fn dfa_from_ts() {
@@ -200,4 +299,74 @@ mod tests {
         assert!(!DFA::from_ts(&ts, [1]).accepts("a"));
         assert!(!DFA::from_ts(ts, []).accepts("a"));
     }
+
+    #[test]
+    fn dfa_reversal() {
+        // accepts all words that start with `a`
+        let dfa = TSBuilder::default()
+            .with_state_colors([false, true, false])
+            .with_edges([
+                (0, 'a', 1),
+                (0, 'b', 2),
+                (1, 'a', 1),
+                (1, 'b', 1),
+                (2, 'a', 2),
+                (2, 'b', 2),
+            ])
+            .into_dfa(0);
+        let reversed = dfa.reversed();
+        assert!(reversed.accepts("a"));
+        assert!(reversed.accepts("bba"));
+        assert!(!reversed.accepts("ab"));
+        assert!(!reversed.accepts(""));
+        assert!(reversed.reversed().equivalent(&dfa));
+        assert_eq!(reversed.minimize().size(), 2);
+    }
+
+    #[test]
+    fn brzozowski_agrees_with_hopcroft() {
+        for _ in 0..30 {
+            let (mut ts, initial) = generate_random_ts_sized(2, 12);
+            ts.trim_from(initial);
+            let dfa = ts
+                .map_state_colors(|_| thread_rng().gen_bool(0.3))
+                .with_initial(initial)
+                .into_dfa();
+            let brzozowski = dfa.minimize_brzozowski();
+            assert!(brzozowski.equivalent(&dfa));
+            assert_eq!(brzozowski.size(), dfa.minimize().size());
+        }
+    }
+
+    #[test]
+    fn reverse_determinism() {
+        let alphabet_a = TSBuilder::default()
+            .with_state_colors([true])
+            .with_edges([(0, 'a', 0)])
+            .into_dfa(0);
+        assert!(alphabet_a.is_reverse_deterministic());
+
+        // a*b, which has a rejecting sink
+        let a_star_b = TSBuilder::default()
+            .with_state_colors([false, true, false])
+            .with_edges([
+                (0, 'a', 0),
+                (0, 'b', 1),
+                (1, 'a', 2),
+                (1, 'b', 2),
+                (2, 'a', 2),
+                (2, 'b', 2),
+            ])
+            .into_dfa(0);
+        assert!(a_star_b.is_reverse_deterministic());
+
+        // all words ending with `a`
+        let ends_with_a = TSBuilder::default()
+            .with_state_colors([false, true])
+            .with_edges([(0, 'a', 1), (0, 'b', 0), (1, 'a', 1), (1, 'b', 0)])
+            .into_dfa(0);
+        assert!(!ends_with_a.is_reverse_deterministic());
+        // all words starting with `a`, the accepting state has two `a`-predecessors
+        assert!(!ends_with_a.reversed().is_reverse_deterministic());
+    }
 }
@@ -6,6 +6,7 @@ use std::fmt::Debug;
 /// Auxiliary type that is used as marker for an [`Automaton`] where we are not
 /// interested in the semantics.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WithoutCondition;
 
 /// An [`Automaton`] which has no semantics. Essentially, this just fixes one
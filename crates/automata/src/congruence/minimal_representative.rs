@@ -205,6 +205,23 @@ impl<T: TransitionSystem> Default for LazyMinimalRepresentatives<T> {
     }
 }
 
+/// The minimal representatives are a cache that can be recomputed from the congruence,
+/// so they are not serialized and deserialization yields an empty cache.
+#[cfg(feature = "serde")]
+impl<T: TransitionSystem> serde::Serialize for LazyMinimalRepresentatives<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: TransitionSystem> serde::Deserialize<'de> for LazyMinimalRepresentatives<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <()>::deserialize(deserializer)?;
+        Ok(Self::default())
+    }
+}
+
 impl<T: TransitionSystem> PartialEq for LazyMinimalRepresentatives<T> {
     fn eq(&self, _other: &Self) -> bool {
         true
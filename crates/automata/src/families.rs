@@ -29,6 +29,14 @@ pub use convert::*;
 /// Represents a family indexed by a transition system. For every
 /// state/class of the leading ts, there exists an element of type `X`.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize, StateIndex<T>: serde::Serialize, X: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>, StateIndex<T>: serde::Deserialize<'de>, X: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Family<T: Congruence, X> {
     leading: T,
     progress: Map<StateIndex<T>, X>,
@@ -95,6 +103,41 @@ impl<A: Alphabet, X> Family<RightCongruence<A>, X> {
 mod tests {
     use super::*;
     use automata_core::alphabet::CharAlphabet;
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        let dfa = DFA::builder()
+            .with_state_colors([false, true])
+            .with_edges([(0, 'a', 1), (0, 'b', 0), (1, 'a', 1), (1, 'b', 0)])
+            .into_dfa(0);
+        let mut fdfa: FDFA = Family::trivial(CharAlphabet::of_size(2), dfa);
+
+        let json = serde_json::to_string(&fdfa).unwrap();
+        let back: FDFA = serde_json::from_str(&json).unwrap();
+        assert!(back.get("ba").unwrap().accepts("ba"));
+        assert!(!back.get("").unwrap().accepts("ab"));
+
+        fdfa.set(
+            "b",
+            DFA::builder()
+                .with_state_colors([true])
+                .with_edges([(0, 'a', 0), (0, 'b', 0)])
+                .into_dfa(0),
+        );
+        let bytes = bincode::serialize(&fdfa).unwrap();
+        let back: FDFA = bincode::deserialize(&bytes).unwrap();
+        assert!(back[0].accepts("ab"));
+
+        let fwpm: FWPM = Family::trivial(
+            CharAlphabet::of_size(2),
+            MealyMachine::builder()
+                .with_transitions([(0, 'a', 1, 0), (0, 'b', 0, 0)])
+                .into_mealy(0),
+        );
+        let back: FWPM = bincode::deserialize(&bincode::serialize(&fwpm).unwrap()).unwrap();
+        assert!(back[0].bisimilar(&fwpm[0]));
+    }
+
     #[test]
     fn family_interaction() {
         let mut fam = Family::trivial(CharAlphabet::of_size(2), 1337);
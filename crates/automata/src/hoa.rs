@@ -4,7 +4,8 @@ use tracing::{trace, warn};
 
 pub mod input;
 pub use input::{
-    HoaAutomatonStream, IntoDeterministicHoaAutomatonStream, NoneterministicHoaAutomatonStream,
+    HoaAutomatonStream, HoaConversionError, HoaError, IntoDeterministicHoaAutomatonStream,
+    NoneterministicHoaAutomatonStream,
 };
 
 pub mod output;
@@ -16,104 +17,81 @@ use crate::core::alphabet::PropAlphabet;
 
 pub type HoaAutomaton<const DET: bool> = crate::automaton::OmegaAutomaton<PropAlphabet, DET>;
 
+/// A string containing automata in the HOA format, from which automata can be [`HoaString::pop`]ped
+/// one after another. Popping only advances a position in the string, so the remainder is
+/// never copied.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct HoaString(pub(crate) String);
+pub struct HoaString {
+    buf: String,
+    pos: usize,
+}
 
 impl HoaString {
-    pub fn into_inner(self) -> String {
-        self.0
+    /// Returns the part of the string that has not been popped yet.
+    pub fn into_inner(mut self) -> String {
+        self.buf.drain(..self.pos);
+        self.buf
     }
 
+    /// Pops the foremost automaton that can be parsed, automata that are aborted or can not
+    /// be parsed are skipped. Returns `None` if no automaton is left.
     pub fn pop<const DET: bool>(&mut self) -> Option<HoaAutomaton<DET>> {
-        const END_LEN: usize = "--END--".len();
-        const ABORT_LEN: usize = "--ABORT--".len();
-
         loop {
-            match (self.0.find("--ABORT--"), self.0.find("--END--")) {
-                (None, Some(pos)) => {
-                    trace!("Returnting first automaton, going up to position {pos}");
-                    match input::parse_omega_automaton_range(&self.0, 0, pos + END_LEN) {
-                        Ok(aut) => return Some(aut),
-                        Err(e) => {
-                            warn!("Could not parse automaton, skipping... {:?}", e);
-                            self.0 = self.0[pos + END_LEN..].trim_start().to_string();
-                        }
-                    }
-                }
-                (Some(abort), None) => {
-                    trace!(
-                        "Found only one automaton --ABORT--ed at {abort}, but no subsequent --END-- of automaton to parse."
-                    );
-                    return None;
-                }
-                (Some(abort), Some(end)) => {
-                    if abort < end {
-                        trace!(
-                            "Found --ABORT-- before --END--, returning first automaton from {abort} to {end}"
-                        );
-                        match input::parse_omega_automaton_range(
-                            &self.0,
-                            abort + ABORT_LEN,
-                            end + END_LEN,
-                        ) {
-                            Ok(aut) => return Some(aut),
-                            Err(e) => {
-                                warn!("Could not parse automaton, skipping... {:?}", e);
-                                self.0 = self.0[end + END_LEN..].trim_start().to_string();
-                            }
-                        }
-                    } else {
-                        trace!("Found --END--, returning first automaton up to {end}");
-                        match input::parse_omega_automaton_range(&self.0, 0, end + END_LEN) {
-                            Ok(aut) => return Some(aut),
-                            Err(e) => {
-                                warn!("Could not parse automaton, skipping... {:?}", e);
-                                self.0 = self.0[end + END_LEN..].trim_start().to_string();
-                            }
-                        }
-                    }
-                }
-                (None, None) => {
-                    trace!("No end of automaton found, there is no automaton to parse.");
-                    return None;
-                }
+            let range = hoars::split_hoa_automata(&self.buf[self.pos..]).next()?;
+            let (start, end) = (self.pos + range.start, self.pos + range.end);
+            trace!("popping automaton from {start} to {end}");
+            let aut = input::parse_omega_automaton_range(&self.buf, start, end);
+            self.pos = end;
+            self.pos += self.buf[end..].len() - self.buf[end..].trim_start().len();
+            match aut {
+                Ok(aut) => return Some(aut),
+                Err(e) => warn!("Could not parse automaton, skipping... {e}"),
             }
         }
     }
 }
 
 impl std::ops::Deref for HoaString {
-    type Target = String;
+    type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-impl std::ops::DerefMut for HoaString {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &self.buf[self.pos..]
     }
 }
 impl std::borrow::Borrow<str> for HoaString {
     fn borrow(&self) -> &str {
-        self.0.as_str()
+        self
     }
 }
 impl From<String> for HoaString {
-    fn from(value: String) -> Self {
-        Self(value)
+    fn from(buf: String) -> Self {
+        Self { buf, pos: 0 }
     }
 }
 impl From<HoaString> for String {
     fn from(value: HoaString) -> Self {
-        value.0
+        value.into_inner()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::HoaString;
     use crate::{TransitionSystem, hoa::input::hoa_to_ts};
 
+    #[test]
+    fn pop_from_hoa_string() {
+        let aut = "HOA: v1\nStates: 1\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n--BODY--\n\
+            State: 0\n[0] 0 {0}\n[!0] 0\n--END--\n";
+        let broken = aut.replace("States: 1", "States: 0");
+        let mut hoa = HoaString::from(format!("{aut}{broken}{aut}  HOA: v1"));
+        assert_eq!(hoa.pop::<true>().map(|aut| aut.size()), Some(1));
+        assert_eq!(hoa.pop::<true>().map(|aut| aut.size()), Some(1));
+        assert_eq!(&*hoa, "HOA: v1");
+        assert!(hoa.pop::<true>().is_none());
+        assert_eq!(hoa.into_inner(), "HOA: v1");
+    }
+
     #[test]
     fn parse_generated_hoa() {
         let hoa = r#"HOA: v1
@@ -174,7 +152,7 @@ mod tests {
         [!0] 3
         --END--
         "#;
-        let auts = hoa_to_ts(hoa);
+        let auts = hoa_to_ts(hoa).unwrap();
         assert_eq!(auts.len(), 1);
         let aut = &auts[0];
         assert_eq!(aut.size(), 10);
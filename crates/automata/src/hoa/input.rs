@@ -1,18 +1,61 @@
-use std::{io::BufRead, ops::Deref};
+use std::{collections::BTreeSet, io::BufRead, ops::Deref, ops::Range};
 
 use crate::automaton::{
-    AcceptanceMask, DeterministicOmegaAutomaton, OmegaAcceptanceCondition, OmegaAutomaton,
+    AcceptanceMask, AlternatingOmegaAutomaton, AutomatonMetadata, DeterministicOmegaAutomaton,
+    EmersonLeiAtom, EmersonLeiClass, EmersonLeiCondition, OmegaAcceptanceCondition, OmegaAutomaton,
 };
 use crate::core::{
     Int,
     alphabet::{PropAlphabet, PropExpression},
 };
-use crate::ts::{ForAlphabet, Sproutable, TransitionSystem};
+use crate::ts::{DefaultIdType, ForAlphabet, Sproutable, TransitionSystem};
 use hoars::HoaRepresentation;
-use tracing::{trace, warn};
+use thiserror::Error;
+use tracing::warn;
 
 use super::HoaString;
 
+/// Errors that can occur when reading automata in the HOA format.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum HoaError {
+    /// The automaton occupying the given byte range of the input could not be parsed. The
+    /// spans of syntax errors in `source` are relative to the start of that range.
+    #[error("could not parse automaton at {}..{}: {source}", range.start, range.end)]
+    Parse {
+        /// The byte range of the input that contains the automaton.
+        range: Range<usize>,
+        /// The error that the parser encountered.
+        #[source]
+        source: hoars::FromHoaError,
+    },
+    /// The automaton occupying the given byte range of the input was parsed, but could not
+    /// be converted into an automaton.
+    #[error("could not convert automaton at {}..{}: {source}", range.start, range.end)]
+    Conversion {
+        /// The byte range of the input that contains the automaton.
+        range: Range<usize>,
+        /// The reason why the conversion failed.
+        #[source]
+        source: HoaConversionError,
+    },
+    /// Reading from the underlying reader failed.
+    #[error("could not read input: {0}")]
+    Io(String),
+}
+
+impl HoaError {
+    /// Returns the byte range of the input that contains the offending automaton, if
+    /// the error refers to one.
+    pub fn range(&self) -> Option<&Range<usize>> {
+        match self {
+            HoaError::Parse { range, .. } | HoaError::Conversion { range, .. } => Some(range),
+            HoaError::Io(_) => None,
+        }
+    }
+}
+
+/// Reads automata from a [`BufRead`] and yields them as [`DeterministicOmegaAutomaton`]s.
+/// Automata that are not deterministic result in a [`HoaConversionError::NotDeterministic`].
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct IntoDeterministicHoaAutomatonStream<R> {
     base: HoaAutomatonStream<R, false>,
@@ -27,109 +70,71 @@ impl<R> IntoDeterministicHoaAutomatonStream<R> {
 }
 
 impl<R: BufRead> Iterator for IntoDeterministicHoaAutomatonStream<R> {
-    type Item = DeterministicOmegaAutomaton<PropAlphabet>;
+    type Item = Result<DeterministicOmegaAutomaton<PropAlphabet>, HoaError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.base.next() {
-                None => return None,
-                Some(aut) => {
-                    if let Ok(det) = aut.try_into_deterministic() {
-                        return Some(det);
-                    } else {
-                        warn!("Encountered automaton that is not deterministic, skipping...")
-                    }
-                }
-            }
-        }
+        let aut = match self.base.next()? {
+            Ok(aut) => aut,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(
+            aut.try_into_deterministic()
+                .map_err(|_| HoaError::Conversion {
+                    range: self.base.reader.last_range(),
+                    source: HoaConversionError::NotDeterministic,
+                }),
+        )
     }
 }
 
 pub type NoneterministicHoaAutomatonStream<R> = HoaAutomatonStream<R, false>;
 
+/// Reads automata from a [`BufRead`] and yields each of them as soon as its `--END--` is
+/// encountered, see [`hoars::HoaReader`]. Automata that are terminated by `--ABORT--` are
+/// skipped, an automaton that can not be parsed results in an error, after which the
+/// stream continues with the next automaton. Once reading fails, the stream yields
+/// [`HoaError::Io`] and ends.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct HoaAutomatonStream<R, const DET: bool = true> {
-    read: R,
-    buf: String,
-    pos: usize,
+    reader: hoars::HoaReader<R>,
 }
 
 impl<R: BufRead, const DET: bool> Iterator for HoaAutomatonStream<R, DET> {
-    type Item = OmegaAutomaton<PropAlphabet, DET>;
+    type Item = Result<OmegaAutomaton<PropAlphabet, DET>, HoaError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.read.read_line(&mut self.buf) {
-                Ok(0) => return None,
-                Ok(read_bytes) => {
-                    if self.buf[self.pos..].contains("--ABORT--") {
-                        trace!("encountered --ABORT-- in stream, resetting");
-                        self.buf.clear();
-                        self.pos = 0;
-                        continue;
-                    }
-
-                    if self.buf[self.pos..].contains("--END--") {
-                        let end = self.pos + "--END--".len();
-                        trace!(
-                            "encountered --END-- in stream, attempting to parse automaton \n{}",
-                            &self.buf[..end]
-                        );
-                        let aut: Result<OmegaAutomaton<PropAlphabet, DET>, _> =
-                            parse_omega_automaton_range(&self.buf, 0, end);
-                        self.buf.clear();
-                        self.pos = 0;
-
-                        match aut {
-                            Err(e) => {
-                                warn!(
-                                    "Encountered automaton that could not be parsed, skipping... {:?}",
-                                    e
-                                );
-                            }
-                            Ok(aut) => {
-                                assert!(!DET || aut.ts().is_deterministic(), "type error");
-                                return Some(aut);
-                            }
-                        }
-                    }
-
-                    self.pos += read_bytes;
-                }
-                Err(_e) => return None,
-            }
-        }
+        let aut = self.reader.next()?;
+        let range = self.reader.last_range();
+        Some(match aut {
+            Ok(aut) => OmegaAutomaton::try_from(aut)
+                .map_err(|source| HoaError::Conversion { range, source }),
+            Err(hoars::FromHoaError::Io(e)) => Err(HoaError::Io(e)),
+            Err(source) => Err(HoaError::Parse { range, source }),
+        })
     }
 }
 
 impl<R, const DET: bool> HoaAutomatonStream<R, DET> {
     pub fn new(read: R) -> Self {
         Self {
-            read,
-            pos: 0,
-            buf: String::new(),
+            reader: hoars::HoaReader::new(read),
         }
     }
 }
 
+/// Parses the automaton in the byte range `start..end` of `hoa`.
 pub fn parse_omega_automaton_range<const DET: bool>(
     hoa: &str,
     start: usize,
     end: usize,
-) -> Result<OmegaAutomaton<PropAlphabet, DET>, String> {
-    match HoaRepresentation::try_from(&hoa[start..end]) {
-        Ok(aut) => match OmegaAutomaton::try_from(aut) {
-            Ok(aut) => Ok(aut),
-            Err(e) => Err(format!(
-                "In range {}..{}, could not convert automaton into omega automaton: {}",
-                start, end, e
-            )),
-        },
-        Err(e) => Err(format!(
-            "Could not parse automaton from range {}..{}: {}",
-            start, end, e
-        )),
-    }
+) -> Result<OmegaAutomaton<PropAlphabet, DET>, HoaError> {
+    let range = start..end;
+    let aut =
+        HoaRepresentation::try_from(&hoa[range.clone()]).map_err(|source| HoaError::Parse {
+            range: range.clone(),
+            source,
+        })?;
+    OmegaAutomaton::try_from(aut).map_err(|source| HoaError::Conversion { range, source })
 }
 
 pub fn pop_deterministic_omega_automaton(
@@ -142,176 +147,458 @@ pub fn pop_deterministic_omega_automaton(
 /// If no valid automaton is found before the end of the stream is reached, the
 /// function returns `None`.
 pub fn pop_omega_automaton<const DET: bool>(
-    hoa: HoaString,
+    mut hoa: HoaString,
 ) -> Option<(OmegaAutomaton<PropAlphabet, DET>, HoaString)> {
-    let mut hoa = hoa;
-    const END_LEN: usize = "--END--".len();
-    const ABORT_LEN: usize = "--ABORT--".len();
-
-    loop {
-        match (hoa.0.find("--ABORT--"), hoa.0.find("--END--")) {
-            (None, Some(pos)) => {
-                trace!("Returnting first automaton, going up to position {pos}");
-                match parse_omega_automaton_range(&hoa.0, 0, pos + END_LEN) {
-                    Ok(aut) => {
-                        return Some((
-                            aut,
-                            HoaString(hoa.0[pos + END_LEN..].trim_start().to_string()),
-                        ));
-                    }
-                    Err(e) => {
-                        warn!("Could not parse automaton, skipping... {:?}", e);
-                        hoa = HoaString(hoa.0[pos + END_LEN..].trim_start().to_string());
-                    }
-                }
-            }
-            (Some(abort), None) => {
-                trace!(
-                    "Found only one automaton --ABORT--ed at {abort}, but no subsequent --END-- of automaton to parse."
-                );
-                return None;
-            }
-            (Some(abort), Some(end)) => {
-                if abort < end {
-                    trace!(
-                        "Found --ABORT-- before --END--, returning first automaton from {abort} to {end}"
-                    );
-                    match parse_omega_automaton_range(&hoa.0, abort + ABORT_LEN, end + END_LEN) {
-                        Ok(aut) => {
-                            return Some((
-                                aut,
-                                HoaString(hoa.0[end + END_LEN..].trim_start().to_string()),
-                            ));
-                        }
-                        Err(e) => {
-                            warn!("Could not parse automaton, skipping... {:?}", e);
-                            hoa = HoaString(hoa.0[end + END_LEN..].trim_start().to_string());
-                        }
-                    }
-                } else {
-                    trace!("Found --END--, returning first automaton up to {end}");
-                    match parse_omega_automaton_range(&hoa.0, 0, end + END_LEN) {
-                        Ok(aut) => {
-                            return Some((
-                                aut,
-                                HoaString(hoa.0[end + END_LEN..].trim_start().to_string()),
-                            ));
-                        }
-                        Err(e) => {
-                            warn!("Could not parse automaton, skipping... {:?}", e);
-                            hoa = HoaString(hoa.0[end + END_LEN..].trim_start().to_string());
-                        }
-                    }
-                }
-            }
-            (None, None) => {
-                trace!("No end of automaton found, there is no automaton to parse.");
-                return None;
-            }
-        }
-    }
+    let aut = hoa.pop()?;
+    Some((aut, hoa))
 }
 
-/// Considers the given HOA string as a single automaton and tries to parse it into an
-/// [`OmegaAutomaton`].
-pub fn hoa_to_ts<const DET: bool>(hoa: &str) -> Vec<OmegaAutomaton<PropAlphabet, DET>> {
-    let mut out = vec![];
-    for hoa_aut in hoars::parse_hoa_automata(hoa) {
-        match hoa_aut.try_into() {
-            Ok(aut) => out.push(aut),
-            Err(e) => tracing::warn!("Encountered parsing error {}", e),
-        }
-    }
-    out
+/// Parses all automata in the given HOA string into [`OmegaAutomaton`]s. Automata that are
+/// terminated by `--ABORT--` are skipped, any other automaton that can not be parsed or
+/// converted results in an error.
+pub fn hoa_to_ts<const DET: bool>(
+    hoa: &str,
+) -> Result<Vec<OmegaAutomaton<PropAlphabet, DET>>, HoaError> {
+    hoars::split_hoa_automata(hoa)
+        .map(|range| parse_omega_automaton_range(hoa, range.start, range.end))
+        .collect()
 }
 
 impl TryFrom<&hoars::Header> for OmegaAcceptanceCondition {
     type Error = String;
 
+    /// Determines the acceptance condition from the `Acceptance` header. If the formula is
+    /// of a shape that one of the dedicated acceptance conditions can represent, that is
+    /// used. Otherwise, the full formula is kept as an [`EmersonLeiCondition`].
     fn try_from(value: &hoars::Header) -> Result<Self, Self::Error> {
-        let acceptance_sets = value.iter().find_map(|it| match it {
-            hoars::HeaderItem::Acceptance(acceptance, _cond) => Some(*acceptance),
-            _ => None,
+        let (acceptance_sets, condition) = value
+            .iter()
+            .find_map(|it| match it {
+                hoars::HeaderItem::Acceptance(acceptance, cond) => Some((*acceptance, cond)),
+                _ => None,
+            })
+            .ok_or("Acceptance header is missing".to_string())?;
+        let condition = EmersonLeiCondition::try_from(condition)?.simplified();
+
+        let min_even_parity = value.iter().any(|it| {
+            let Some((hoars::AcceptanceName::Parity, info)) = it.try_acceptance_name() else {
+                return false;
+            };
+            !info.iter().any(|i| {
+                matches!(i, hoars::AcceptanceInfo::Identifier(id) if id == "max" || id == "odd")
+            })
         });
-
-        match value.acceptance_name() {
-            hoars::AcceptanceName::Buchi => Ok(OmegaAcceptanceCondition::Buchi),
-            hoars::AcceptanceName::Parity => Ok(OmegaAcceptanceCondition::Parity(
+        if min_even_parity {
+            return Ok(OmegaAcceptanceCondition::Parity(
                 0,
-                acceptance_sets.unwrap() as Int,
-            )),
-            _ => Err("Unsupported acceptance condition".to_string()),
+                (acceptance_sets as Int).saturating_sub(1),
+            ));
         }
+
+        let canonical_pairs = |pairs: &[(Int, Int)]| {
+            pairs
+                .iter()
+                .enumerate()
+                .all(|(i, &(fin, inf))| fin as usize == 2 * i && inf as usize == 2 * i + 1)
+        };
+        Ok(match condition.classify() {
+            Some(EmersonLeiClass::Buchi(0)) => OmegaAcceptanceCondition::Buchi,
+            Some(EmersonLeiClass::CoBuchi(0)) => OmegaAcceptanceCondition::CoBuchi,
+            Some(EmersonLeiClass::Parity(priorities)) => {
+                OmegaAcceptanceCondition::Parity(0, priorities - 1)
+            }
+            Some(EmersonLeiClass::Rabin(pairs)) if canonical_pairs(&pairs) => {
+                OmegaAcceptanceCondition::Rabin
+            }
+            Some(EmersonLeiClass::Streett(pairs)) if canonical_pairs(&pairs) => {
+                OmegaAcceptanceCondition::Streett
+            }
+            _ => OmegaAcceptanceCondition::EmersonLei(condition),
+        })
     }
 }
 
-impl<const DET: bool> TryFrom<HoaRepresentation> for OmegaAutomaton<PropAlphabet, DET> {
+impl TryFrom<&hoars::AcceptanceCondition> for EmersonLeiCondition {
     type Error = String;
+
+    fn try_from(value: &hoars::AcceptanceCondition) -> Result<Self, Self::Error> {
+        let atom = |atom: &hoars::AcceptanceAtom| -> Result<EmersonLeiAtom, String> {
+            let (hoars::AcceptanceAtom::Positive(id) | hoars::AcceptanceAtom::Negative(id)) = atom;
+            let set =
+                Int::try_from(*id).map_err(|_| format!("Acceptance set {id} is too large"))?;
+            Ok(match atom {
+                hoars::AcceptanceAtom::Positive(_) => EmersonLeiAtom::Positive(set),
+                hoars::AcceptanceAtom::Negative(_) => EmersonLeiAtom::Negative(set),
+            })
+        };
+        Ok(match value {
+            hoars::AcceptanceCondition::Fin(a) => EmersonLeiCondition::Fin(atom(a)?),
+            hoars::AcceptanceCondition::Inf(a) => EmersonLeiCondition::Inf(atom(a)?),
+            hoars::AcceptanceCondition::And(left, right) => EmersonLeiCondition::And(vec![
+                left.as_ref().try_into()?,
+                right.as_ref().try_into()?,
+            ]),
+            hoars::AcceptanceCondition::Or(left, right) => {
+                EmersonLeiCondition::Or(vec![left.as_ref().try_into()?, right.as_ref().try_into()?])
+            }
+            hoars::AcceptanceCondition::Boolean(b) => EmersonLeiCondition::Boolean(b.0),
+        })
+    }
+}
+
+/// Errors that can occur when converting a [`HoaRepresentation`] into an automaton.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum HoaConversionError {
+    /// The automaton uses more atomic propositions than are supported.
+    #[error("automaton has {count} atomic propositions, but at most {max} are supported")]
+    TooManyAps {
+        /// The number of atomic propositions of the automaton.
+        count: u8,
+        /// The maximal number of supported atomic propositions.
+        max: u8,
+    },
+    /// A state is referred to that lies outside of the declared range of states.
+    #[error("state {0} does not exist")]
+    UnknownState(hoars::Id),
+    /// The given state is defined more than once in the body.
+    #[error("state {0} is defined more than once")]
+    DuplicateState(hoars::Id),
+    /// An edge leaving the given state leads to a conjunction of states, which is only
+    /// supported by [`AlternatingOmegaAutomaton`].
+    #[error("edge from state {0} is alternating, use AlternatingOmegaAutomaton instead")]
+    AlternatingEdge(hoars::Id),
+    /// An initial state is a conjunction of states, which is only supported by
+    /// [`AlternatingOmegaAutomaton`].
+    #[error("initial state is a conjunction, use AlternatingOmegaAutomaton instead")]
+    ConjunctiveInitialState,
+    /// A deterministic automaton was requested, but there is more than one initial state.
+    #[error("deterministic automaton must have a single initial state")]
+    MultipleInitialStates,
+    /// A deterministic automaton was requested, but some state has overlapping edges.
+    #[error("automaton is not deterministic")]
+    NotDeterministic,
+    /// The automaton does not have an initial state.
+    #[error("automaton has no initial state")]
+    NoInitialState,
+    /// A label could not be interpreted.
+    #[error("invalid label: {0}")]
+    Label(String),
+    /// The acceptance condition could not be interpreted.
+    #[error("invalid acceptance: {0}")]
+    Acceptance(String),
+}
+
+impl<const DET: bool> TryFrom<HoaRepresentation> for OmegaAutomaton<PropAlphabet, DET> {
+    type Error = HoaConversionError;
     fn try_from(value: HoaRepresentation) -> Result<Self, Self::Error> {
         hoa_automaton_to_ts(value)
     }
 }
 
+/// Determines the number of states of `aut`, which is either declared in the header or
+/// given by the largest state in the body. Returns an error if some state or edge
+/// refers to a state beyond that.
+fn hoa_num_states(aut: &HoaRepresentation) -> Result<usize, HoaConversionError> {
+    let size = aut.num_states().unwrap_or_else(|| {
+        aut.body()
+            .iter()
+            .map(|state| state.id() as usize + 1)
+            .max()
+            .unwrap_or(0)
+    });
+    let referenced = aut
+        .body()
+        .iter()
+        .flat_map(|state| {
+            std::iter::once(state.id()).chain(
+                state
+                    .edges()
+                    .iter()
+                    .flat_map(|edge| edge.state_conjunction().iter().copied()),
+            )
+        })
+        .chain(aut.start().into_iter().flat_map(|c| c.iter().copied()));
+    for id in referenced {
+        if id as usize >= size {
+            return Err(HoaConversionError::UnknownState(id));
+        }
+    }
+    Ok(size)
+}
+
+/// Checks that every state is defined at most once and that edges are only marked with
+/// acceptance sets that are declared in the `Acceptance` header.
+fn hoa_check_body(aut: &HoaRepresentation) -> Result<(), HoaConversionError> {
+    let mut defined = BTreeSet::new();
+    for state in aut.body().iter() {
+        if !defined.insert(state.id()) {
+            return Err(HoaConversionError::DuplicateState(state.id()));
+        }
+    }
+
+    let Some((sets, _)) = aut.acceptance() else {
+        // the missing condition is reported when it is converted
+        return Ok(());
+    };
+    for state in aut.body().iter() {
+        for edge in state.edges() {
+            if let Some(&mark) = edge
+                .acceptance_signature()
+                .iter()
+                .find(|&&mark| mark as usize >= sets)
+            {
+                let location = edge
+                    .span()
+                    .map(|span| format!(" at {}..{}", span.start, span.end))
+                    .unwrap_or_default();
+                return Err(HoaConversionError::Acceptance(format!(
+                    "edge from state {}{location} is marked with set {mark}, but only {sets} sets are declared",
+                    state.id()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn hoa_aps(aut: &HoaRepresentation) -> Result<u8, HoaConversionError> {
+    let aps = aut.num_aps();
+    if aps > crate::hoa::MAX_APS {
+        return Err(HoaConversionError::TooManyAps {
+            count: aps,
+            max: crate::hoa::MAX_APS,
+        });
+    }
+    Ok(aps)
+}
+
 /// Converts a [`HoaRepresentation`] into a [`crate::NTS`] with the same semantics. This creates the appropriate
 /// number of states and inserts transitions with the appropriate labels and colors.
+///
+/// If a nondeterministic automaton has multiple initial states, a fresh initial state is
+/// added, which has a copy of every edge leaving one of the original initial states.
 pub fn hoa_automaton_to_ts<const DET: bool>(
     aut: HoaRepresentation,
-) -> Result<OmegaAutomaton<PropAlphabet, DET>, String> {
-    let aps = aut.num_aps();
-    assert!(aps <= crate::hoa::MAX_APS);
+) -> Result<OmegaAutomaton<PropAlphabet, DET>, HoaConversionError> {
+    let aps = hoa_aps(&aut)?;
+    let size = hoa_num_states(&aut)?;
+    hoa_check_body(&aut)?;
+
+    let mut initial = Vec::new();
+    for conjunction in aut.start() {
+        let Some(state) = conjunction.get_singleton() else {
+            return Err(HoaConversionError::ConjunctiveInitialState);
+        };
+        initial.push(state);
+    }
+    let fresh_initial = match initial[..] {
+        [] => return Err(HoaConversionError::NoInitialState),
+        [_] => false,
+        _ if DET => return Err(HoaConversionError::MultipleInitialStates),
+        _ => true,
+    };
 
     let alphabet = PropAlphabet::from_apnames(aut.aps().iter());
     let mut ts: crate::TS<PropAlphabet, Int, AcceptanceMask, DET> =
         crate::TS::for_alphabet(alphabet);
 
-    for (id, state) in aut.body().iter().enumerate() {
-        assert_eq!(id, state.id() as usize);
-        assert_eq!(id, ts.add_state(state.id() as Int) as usize);
+    for id in 0..size {
+        ts.add_state(id as Int);
     }
+    let start = if fresh_initial {
+        ts.add_state(size as Int)
+    } else {
+        initial[0]
+    };
 
     for state in aut.body().iter() {
         for edge in state.edges() {
-            let target = edge
-                .state_conjunction()
-                .get_singleton()
-                .expect("Cannot yet deal with conjunctions of target states");
+            let Some(target) = edge.state_conjunction().get_singleton() else {
+                return Err(HoaConversionError::AlternatingEdge(state.id()));
+            };
             let label = edge.label().deref().clone();
 
-            let expr = label.try_into_hoa_expression(aps)?;
+            let expr = PropExpression::from_bdd(
+                label
+                    .try_into_hoa_expression(aps)
+                    .map_err(HoaConversionError::Label)?,
+            );
             let color: AcceptanceMask = edge.acceptance_signature().into();
 
-            if ts
-                .add_edge((state.id(), PropExpression::from_bdd(expr), color, target))
-                .is_some()
-            {
-                // this thing is not deterministic, so we return
-                if DET {
+            let mut sources = vec![state.id()];
+            if fresh_initial && initial.contains(&state.id()) {
+                sources.push(start);
+            }
+            for source in sources {
+                if ts
+                    .add_edge((source, expr.clone(), color.clone(), target))
+                    .is_some()
+                    && DET
+                {
                     warn!("rejecting nondeterministic automaton");
-                    return Err("Automaton is not deterministic".to_string());
+                    return Err(HoaConversionError::NotDeterministic);
                 }
             }
         }
     }
 
-    debug_assert!(!DET || ts.is_deterministic());
+    // edges with distinct but overlapping labels are not caught when inserting them
+    if DET && !ts.is_deterministic() {
+        warn!("rejecting nondeterministic automaton");
+        return Err(HoaConversionError::NotDeterministic);
+    }
+
+    let acceptance: OmegaAcceptanceCondition = aut
+        .header()
+        .try_into()
+        .map_err(HoaConversionError::Acceptance)?;
+
+    Ok(
+        OmegaAutomaton::from_parts_with_acceptance(ts, start, acceptance)
+            .with_metadata(hoa_metadata(&aut)),
+    )
+}
+
+/// Collects the name of the automaton, the tool that produced it and the comments of the
+/// states, which are used as their names.
+fn hoa_metadata(aut: &HoaRepresentation) -> AutomatonMetadata<DefaultIdType> {
+    let mut metadata = AutomatonMetadata::default();
+    for item in aut.header().iter() {
+        match item {
+            hoars::HeaderItem::Name(name) => metadata.set_name(name.clone()),
+            hoars::HeaderItem::Tool(tool, version) => {
+                metadata.set_tool(tool.clone(), version.clone())
+            }
+            _ => {}
+        }
+    }
+    for state in aut.body().iter() {
+        if let Some(name) = state.comment() {
+            metadata.set_state_name(state.id() as DefaultIdType, name);
+        }
+    }
+    metadata
+}
+
+/// Parses all automata in the given HOA string into [`AlternatingOmegaAutomaton`]s. In
+/// contrast to [`hoa_to_ts`], edges may lead to a conjunction of states.
+pub fn hoa_to_alternating(
+    hoa: &str,
+) -> Result<Vec<AlternatingOmegaAutomaton<PropAlphabet>>, HoaError> {
+    hoars::split_hoa_automata(hoa)
+        .map(|range| {
+            let aut = HoaRepresentation::try_from(&hoa[range.clone()]).map_err(|source| {
+                HoaError::Parse {
+                    range: range.clone(),
+                    source,
+                }
+            })?;
+            AlternatingOmegaAutomaton::try_from(aut)
+                .map_err(|source| HoaError::Conversion { range, source })
+        })
+        .collect()
+}
 
-    let start = aut.start();
-    assert_eq!(start.len(), 1);
-    let initial = start[0]
-        .get_singleton()
-        .expect("Initial state must be a singleton");
+impl TryFrom<HoaRepresentation> for AlternatingOmegaAutomaton<PropAlphabet> {
+    type Error = HoaConversionError;
+
+    /// Converts a [`HoaRepresentation`] into an [`AlternatingOmegaAutomaton`], where each
+    /// edge leads to the conjunction of states given in the HOA body.
+    ///
+    /// If there are multiple initial conjunctions, a fresh initial state is added. For each
+    /// of the conjunctions and each choice of one edge per state in it, the fresh state has
+    /// an edge labeled with the conjunction of the chosen labels, which leads to the union
+    /// of their targets.
+    fn try_from(aut: HoaRepresentation) -> Result<Self, Self::Error> {
+        let aps = hoa_aps(&aut)?;
+        let size = hoa_num_states(&aut)?;
+        hoa_check_body(&aut)?;
+
+        let acceptance: OmegaAcceptanceCondition = aut
+            .header()
+            .try_into()
+            .map_err(HoaConversionError::Acceptance)?;
+        let alphabet = PropAlphabet::from_apnames(aut.aps().iter());
+        let mut out = AlternatingOmegaAutomaton::new(alphabet, acceptance);
+
+        for _ in 0..size {
+            out.add_state();
+        }
 
-    let acceptance: OmegaAcceptanceCondition = aut.header().try_into()?;
+        for state in aut.body().iter() {
+            for edge in state.edges() {
+                let expr = edge
+                    .label()
+                    .deref()
+                    .clone()
+                    .try_into_hoa_expression(aps)
+                    .map_err(HoaConversionError::Label)?;
+                out.add_edge(
+                    state.id(),
+                    PropExpression::from_bdd(expr),
+                    edge.state_conjunction().iter().copied(),
+                    edge.acceptance_signature().into(),
+                );
+            }
+        }
 
-    Ok(OmegaAutomaton::from_parts_with_acceptance(
-        ts, initial, acceptance,
-    ))
+        match aut.start()[..] {
+            [] => return Err(HoaConversionError::NoInitialState),
+            [start] => out.set_initial(start.iter().copied()),
+            ref starts => {
+                let mut combined = vec![];
+                for start in starts {
+                    let mut choices = vec![(
+                        PropExpression::universal(aps),
+                        BTreeSet::new(),
+                        AcceptanceMask::default(),
+                    )];
+                    for &state in start.iter() {
+                        let edges = out.edges_from(state).expect("state exists");
+                        choices = choices
+                            .into_iter()
+                            .flat_map(|(expr, targets, color)| {
+                                edges.iter().map(move |edge| {
+                                    (
+                                        expr.clone() & edge.expression().clone(),
+                                        targets.union(edge.targets()).copied().collect(),
+                                        color.iter().chain(edge.color().iter()).collect(),
+                                    )
+                                })
+                            })
+                            .collect();
+                    }
+                    combined.extend(choices);
+                }
+                let fresh = out.add_state();
+                for (expr, targets, color) in combined {
+                    out.add_edge(fresh, expr, targets, color);
+                }
+                out.set_initial([fresh]);
+            }
+        }
+        Ok(out)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use tracing::debug;
 
+    use hoars::HoaRepresentation;
+
+    use super::{
+        HoaAutomatonStream, HoaConversionError, HoaError, IntoDeterministicHoaAutomatonStream,
+    };
+    use crate::automaton::{
+        DeterministicOmegaAutomaton, EmersonLeiAtom, EmersonLeiCondition, NBA,
+        OmegaAcceptanceCondition, OmegaAutomaton,
+    };
+    use crate::core::{
+        alphabet::{Alphabet, CharAlphabet},
+        upw,
+        word::ReducedOmegaWord,
+    };
     use crate::{TransitionSystem, hoa::HoaString};
 
     #[test]
@@ -354,13 +641,285 @@ mod tests {
         [!0] 0
         --END--
         "#;
-        let hoa = HoaString(raw_hoa.to_string());
+        let hoa = HoaString::from(raw_hoa.to_string());
         debug!("SADF");
 
         let first = super::pop_deterministic_omega_automaton(hoa);
         assert!(first.is_some());
         let (first, hoa) = first.unwrap();
         assert_eq!(first.size(), 1);
-        assert!(hoa.0.is_empty());
+        assert!(hoa.is_empty());
+    }
+
+    #[test]
+    fn hoa_emerson_lei_acceptance() {
+        let raw_hoa = r#"
+        HOA: v1
+        States: 1
+        Start: 0
+        acc-name: generalized-Buchi 2
+        Acceptance: 2 Inf(0) & Inf(1)
+        AP: 1 "a"
+        --BODY--
+        State: 0
+        [0] 0 {0}
+        [!0] 0 {1}
+        --END--
+        "#;
+        let auts = super::hoa_to_ts::<true>(raw_hoa).unwrap();
+        assert_eq!(auts.len(), 1);
+        assert_eq!(
+            auts[0].acceptance(),
+            &OmegaAcceptanceCondition::EmersonLei(EmersonLeiCondition::generalized_buchi(2))
+        );
+
+        let dpa = DeterministicOmegaAutomaton::<CharAlphabet>::from(auts[0].clone()).into_dpa();
+        assert!(dpa.accepts(upw!("ab")));
+        assert!(!dpa.accepts(upw!("a")));
+        assert!(!dpa.accepts(upw!("b")));
+    }
+
+    #[test]
+    fn hoa_classified_acceptance() {
+        for (acceptance, expected) in [
+            ("1 Inf(0)", OmegaAcceptanceCondition::Buchi),
+            ("1 Fin(0)", OmegaAcceptanceCondition::CoBuchi),
+            (
+                "3 Inf(0) | (Fin(1) & Inf(2))",
+                OmegaAcceptanceCondition::Parity(0, 2),
+            ),
+            (
+                "4 (Fin(0) & Inf(1)) | (Fin(2) & Inf(3))",
+                OmegaAcceptanceCondition::Rabin,
+            ),
+            (
+                "4 (Fin(0) | Inf(1)) & (Fin(2) | Inf(3))",
+                OmegaAcceptanceCondition::Streett,
+            ),
+            (
+                "2 Fin(!0) | Inf(1)",
+                OmegaAcceptanceCondition::EmersonLei(EmersonLeiCondition::Or(vec![
+                    EmersonLeiCondition::Fin(EmersonLeiAtom::Negative(0)),
+                    EmersonLeiCondition::inf(1),
+                ])),
+            ),
+        ] {
+            let raw_hoa = format!(
+                "HOA: v1\nStates: 1\nStart: 0\nAcceptance: {acceptance}\nAP: 1 \"a\"\n\
+                 --BODY--\nState: 0\n[t] 0\n--END--\n"
+            );
+            let auts = super::hoa_to_ts::<true>(&raw_hoa).unwrap();
+            assert_eq!(auts.len(), 1, "could not parse {acceptance}");
+            assert_eq!(auts[0].acceptance(), &expected);
+        }
+    }
+
+    #[test]
+    fn hoa_multiple_initial_states_aliases_and_implicit_labels() {
+        // accepts a^ω and all words with infinitely many occurrences of !a
+        let raw_hoa = r#"HOA: v1
+        States: 2
+        Start: 0
+        Start: 1
+        AP: 1 "a"
+        Alias: @a 0
+        acc-name: Buchi
+        Acceptance: 1 Inf(0)
+        --BODY--
+        State: 0 {0}
+        1
+        0
+        State: 1
+        [!@a] 1 {0}
+        [@a] 1
+        --END--
+        "#;
+        let auts = super::hoa_to_ts::<false>(raw_hoa).unwrap();
+        assert_eq!(auts.len(), 1);
+        let nba = NBA::try_from(auts[0].clone()).expect("has Büchi acceptance");
+        let alternating = super::hoa_to_alternating(raw_hoa)
+            .unwrap()
+            .pop()
+            .expect("is alternating automaton");
+
+        let symbols: Vec<_> = nba.ts().alphabet().universe().collect();
+        let a = *symbols.iter().find(|s| s.as_bools() == [true]).unwrap();
+        let b = *symbols.iter().find(|s| s.as_bools() == [false]).unwrap();
+        for (prefix, period, expected) in [
+            (vec![], vec![a], true),
+            (vec![b], vec![a], false),
+            (vec![], vec![b], true),
+            (vec![a, a], vec![a, b], true),
+        ] {
+            let word = ReducedOmegaWord::ultimately_periodic(prefix, period);
+            assert_eq!(nba.accepts(&word), expected);
+            assert_eq!(alternating.accepts(&word), Ok(expected));
+        }
+
+        let hoa = HoaRepresentation::try_from(raw_hoa).unwrap();
+        assert_eq!(
+            DeterministicOmegaAutomaton::try_from(hoa).err(),
+            Some(HoaConversionError::MultipleInitialStates)
+        );
+        let conjunctive = raw_hoa.replace("Start: 0\n        Start: 1", "Start: 0 & 1");
+        let hoa = HoaRepresentation::try_from(conjunctive.as_str()).unwrap();
+        assert_eq!(
+            OmegaAutomaton::<_, false>::try_from(hoa).err(),
+            Some(HoaConversionError::ConjunctiveInitialState)
+        );
+    }
+
+    const SINGLE_STATE: &str = "HOA: v1\nStates: 1\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n\
+        --BODY--\nState: 0\n[0] 0 {0}\n[!0] 0\n--END--\n";
+
+    #[test]
+    fn hoa_malformed_input_is_rejected() {
+        let err = |hoa: &str| super::hoa_to_ts::<false>(hoa).err();
+
+        let overflow = SINGLE_STATE.replace("States: 1", "States: 99999999999999999999");
+        let digits = overflow.find('9').unwrap();
+        assert!(matches!(
+            err(&overflow),
+            Some(HoaError::Parse { source: hoars::FromHoaError::ParserError(errors), .. })
+                if errors.iter().any(|e| e.span.start == digits)
+        ));
+
+        let lexer = SINGLE_STATE.replace("[!0] 0", "[!0] 0 $");
+        let Some(HoaError::Parse {
+            source: hoars::FromHoaError::LexerError(errors),
+            ..
+        }) = err(&lexer)
+        else {
+            panic!("expected a lexer error");
+        };
+        let position = lexer.chars().position(|c| c == '$').unwrap();
+        assert!(errors.iter().any(|e| e.span.contains(&position)));
+
+        let conversion = |hoa: &str| match err(hoa) {
+            Some(HoaError::Conversion { source, .. }) => Some(source),
+            _ => None,
+        };
+        assert_eq!(
+            conversion(&SINGLE_STATE.replace("Start: 0\n", "")),
+            Some(HoaConversionError::NoInitialState)
+        );
+        assert_eq!(
+            conversion(&SINGLE_STATE.replace("Start: 0", "Start: 3")),
+            Some(HoaConversionError::UnknownState(3))
+        );
+        assert!(matches!(
+            conversion(&SINGLE_STATE.replace("[0] 0 {0}", "[0 & 4] 0 {0}")),
+            Some(HoaConversionError::Label(_))
+        ));
+        assert!(matches!(
+            conversion(&SINGLE_STATE.replace("Acceptance: 1 Inf(0)\n", "")),
+            Some(HoaConversionError::Acceptance(_))
+        ));
+
+        for mark in ["70", "200"] {
+            let marked = SINGLE_STATE.replace("[0] 0 {0}", &format!("[0] 0 {{{mark}}}"));
+            let Some(HoaConversionError::Acceptance(message)) = conversion(&marked) else {
+                panic!("expected undeclared set {mark} to be rejected");
+            };
+            let edge = marked.find("[0] 0").unwrap();
+            let span = format!("{}..{}", edge, edge + "[0] 0 {}".len() + mark.len());
+            assert!(message.contains(&span), "{message} does not contain {span}");
+        }
+        assert!(matches!(
+            err(&SINGLE_STATE.replace("HOA: v1", "HOA: v2")),
+            Some(HoaError::Parse {
+                source: hoars::FromHoaError::UnsupportedVersion(version),
+                ..
+            }) if version == "v2"
+        ));
+        assert_eq!(
+            conversion(&SINGLE_STATE.replace("--END--", "State: 0\n[t] 0\n--END--")),
+            Some(HoaConversionError::DuplicateState(0))
+        );
+
+        // without an AP header, only labels that do not refer to propositions are valid
+        let no_aps = SINGLE_STATE
+            .replace("AP: 1 \"a\"\n", "")
+            .replace("[0] 0 {0}\n[!0] 0", "[t] 0 {0}");
+        let aut = super::hoa_to_ts::<true>(&no_aps).unwrap().pop().unwrap();
+        assert_eq!(aut.alphabet().universe().count(), 1);
+        assert!(
+            DeterministicOmegaAutomaton::<CharAlphabet>::from(aut)
+                .into_dpa()
+                .accepts(upw!("a"))
+        );
+
+        let second = format!(
+            "{SINGLE_STATE}{}",
+            SINGLE_STATE.replace("Start: 0", "Start: 3")
+        );
+        assert_eq!(
+            err(&second).as_ref().and_then(HoaError::range),
+            Some(&(SINGLE_STATE.len()..second.len() - 1))
+        );
+    }
+
+    #[test]
+    fn hoa_deterministic_rejects_overlapping_labels() {
+        let overlapping = SINGLE_STATE
+            .replace("States: 1", "States: 2")
+            .replace("[!0] 0", "[t] 1\nState: 1\n[t] 1");
+        assert!(super::hoa_to_ts::<false>(&overlapping).is_ok());
+        assert!(matches!(
+            super::hoa_to_ts::<true>(&overlapping),
+            Err(HoaError::Conversion {
+                source: HoaConversionError::NotDeterministic,
+                ..
+            })
+        ));
+
+        // the overlapping edges are not adjacent to each other
+        let separated = overlapping
+            .replace("AP: 1 \"a\"", "AP: 2 \"a\" \"b\"")
+            .replace("[0] 0 {0}\n[t] 1", "[0 & 1] 0 {0}\n[!0] 1\n[1] 1");
+        assert!(matches!(
+            super::hoa_to_ts::<true>(&separated),
+            Err(HoaError::Conversion {
+                source: HoaConversionError::NotDeterministic,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn hoa_stream_reports_broken_automata() {
+        let input = format!(
+            "{SINGLE_STATE}{}{}{SINGLE_STATE}",
+            SINGLE_STATE.replace("Start: 0", "Start: 3"),
+            SINGLE_STATE.replace("[!0] 0", "[0] 0")
+        );
+        let results: Vec<_> = HoaAutomatonStream::<_, false>::new(input.as_bytes())
+            .map(|aut| aut.map(|aut| aut.ts().size()))
+            .collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Ok(1));
+        assert_eq!(
+            results[1],
+            Err(HoaError::Conversion {
+                range: SINGLE_STATE.len()..2 * SINGLE_STATE.len() - 1,
+                source: HoaConversionError::UnknownState(3)
+            })
+        );
+        assert_eq!(results[2], Ok(1));
+        assert_eq!(results[3], Ok(1));
+
+        let deterministic: Vec<_> = IntoDeterministicHoaAutomatonStream::new(input.as_bytes())
+            .map(|aut| aut.map(|aut| aut.size()).map_err(|e| e.range().cloned()))
+            .collect();
+        let third = 2 * SINGLE_STATE.len()..input.len() - SINGLE_STATE.len() - 1;
+        assert_eq!(
+            deterministic,
+            vec![
+                Ok(1),
+                Err(Some(SINGLE_STATE.len()..2 * SINGLE_STATE.len() - 1)),
+                Err(Some(third)),
+                Ok(1)
+            ]
+        );
     }
 }
@@ -1,3 +1,5 @@
+use hoars::HoaRepresentation;
+use hoars::output::quoted;
 use itertools::Itertools;
 use tracing::trace;
 
@@ -5,17 +7,29 @@ use std::fmt::{Result, Write};
 
 use crate::{
     Pointed, TransitionSystem,
-    automaton::{DBA, DPA, OmegaAcceptanceCondition},
+    automaton::{
+        AcceptanceMask, AlternatingOmegaAutomaton, AutomatonMetadata, DBA, DCBA, DMA, DPA, DRA,
+        DSA, EmersonLeiClass, EmersonLeiCondition, IntoNBA, OmegaAcceptanceCondition,
+        OmegaAutomaton, RabinPair,
+    },
     core::{
-        Int, Show,
-        alphabet::{Alphabet, CharAlphabet, PropAlphabet},
+        Color, Int, Void,
+        alphabet::{Alphabet, CharAlphabet, PropAlphabet, SimpleAlphabet},
+        math::Set,
     },
-    ts::{EdgeColor, EdgeExpression, IsEdge},
+    ts::{EdgeColor, EdgeExpression, IsEdge, StateIndex},
 };
 
+/// The `properties` that hold for every automaton we write, as labels and acceptance
+/// marks are always placed explicitly on the edges.
+const HOA_PROPERTIES: &str = "trans-labels explicit-labels trans-acc";
+
 pub trait WriteHoa: TransitionSystem + Pointed {
     fn write_hoa<W: Write>(&self, w: &mut W) -> Result {
         w.write_str("HOA: v1\n")?;
+        if let Some(metadata) = self.metadata() {
+            write_name_and_tool(w, metadata)?;
+        }
 
         self.write_alphabet_description(w)?;
 
@@ -25,13 +39,21 @@ pub trait WriteHoa: TransitionSystem + Pointed {
         self.write_state_id(w, self.initial())?;
         w.write_char('\n')?;
 
-        self.write_acceptance(w)?;
+        let sets = self.acceptance_sets();
+        self.write_acceptance(w, &sets)?;
+        self.write_properties(w)?;
+        if let Some(metadata) = self.metadata() {
+            write_header_items(w, metadata)?;
+        }
 
         w.write_str("--BODY--")?;
 
         for state in self.state_indices() {
             w.write_str("\nState: ")?;
             self.write_state_id(w, state)?;
+            if let Some(name) = self.metadata().and_then(|m| m.state_name(state)) {
+                write_state_name(w, name)?;
+            }
             for edge in self.edges_from(state).expect("We know this state exists") {
                 w.write_char('\n')?;
                 w.write_char('[')?;
@@ -41,8 +63,7 @@ pub trait WriteHoa: TransitionSystem + Pointed {
 
                 self.write_state_id(w, edge.target())?;
 
-                w.write_char(' ')?;
-                self.write_edge_color(w, edge.color())?;
+                self.write_edge_color(w, &sets, edge.color())?;
             }
         }
 
@@ -51,7 +72,22 @@ pub trait WriteHoa: TransitionSystem + Pointed {
         Ok(())
     }
 
-    fn write_edge_color<W: std::fmt::Write>(&self, w: &mut W, label: EdgeColor<Self>) -> Result;
+    /// The assignment of edge colors to acceptance sets, which is computed once per
+    /// automaton by [`Self::acceptance_sets`] and passed to the methods writing the
+    /// acceptance condition and the marks of each edge.
+    type AcceptanceSets;
+
+    /// Computes the [`Self::AcceptanceSets`] of `self`.
+    fn acceptance_sets(&self) -> Self::AcceptanceSets;
+
+    /// Writes the acceptance marks of an edge with the given color, including a leading
+    /// space. Nothing is written if the edge does not belong to any acceptance set.
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result;
 
     fn write_expression<W: std::fmt::Write>(
         &self,
@@ -67,7 +103,22 @@ pub trait WriteHoa: TransitionSystem + Pointed {
 
     fn write_alphabet_description<W: std::fmt::Write>(&self, w: &mut W) -> Result;
 
-    fn write_acceptance<W: std::fmt::Write>(&self, w: &mut W) -> Result;
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+    ) -> Result;
+
+    /// Returns the [`AutomatonMetadata`] that is written along with the automaton, by default
+    /// there is none.
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        None
+    }
+
+    /// Writes the `properties` line, by default the automaton is assumed to be deterministic.
+    fn write_properties<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+        writeln!(w, "properties: {HOA_PROPERTIES} deterministic")
+    }
 
     fn to_hoa(&self) -> String {
         let mut w = String::new();
@@ -78,23 +129,393 @@ pub trait WriteHoa: TransitionSystem + Pointed {
 }
 
 impl OmegaAcceptanceCondition {
-    pub fn write_hoa<W: Write>(&self, w: &mut W) -> Result {
+    /// Writes the `acc-name` and `Acceptance` lines of a HOA header for `self`. As the
+    /// number of pairs of a Rabin or Streett condition is not stored in the condition, it is
+    /// derived from `sets`, which should be the number of acceptance sets used by the edges.
+    ///
+    /// [`Self::Reachability`] and [`Self::Safety`] can not be expressed over the sets that
+    /// are visited infinitely often. For them, a Büchi condition and the trivial condition `t`
+    /// are written respectively, which is correct for the automaton that is obtained by
+    /// redirecting all marked edges into an accepting sink or removing them altogether. This
+    /// is what [`OmegaAutomaton::write_hoa`] does.
+    pub fn write_hoa<W: Write>(&self, w: &mut W, sets: usize) -> Result {
         match self {
-            OmegaAcceptanceCondition::Parity(low, high) => {
-                let zero_based_range = high - low + (low % 2);
-                write!(
-                    w,
-                    "acc-name: parity min even {}\nAcceptance: {} {}\n",
-                    zero_based_range + 1,
-                    zero_based_range + 1,
-                    build_parity_condition_hoa(*low, *high)
-                )
-            }
-            OmegaAcceptanceCondition::Buchi => {
+            OmegaAcceptanceCondition::Parity(_, high) => write!(
+                w,
+                "acc-name: parity min even {}\nAcceptance: {} {}\n",
+                high + 1,
+                high + 1,
+                build_parity_condition_hoa(0, *high)
+            ),
+            OmegaAcceptanceCondition::MaxParity(_, high) => write!(
+                w,
+                "acc-name: parity max even {}\nAcceptance: {} {}\n",
+                high + 1,
+                high + 1,
+                build_max_parity_condition_hoa(*high)
+            ),
+            OmegaAcceptanceCondition::Buchi | OmegaAcceptanceCondition::Reachability => {
                 write!(w, "acc-name: Buchi\nAcceptance: 1 Inf(0)\n")
             }
-            _ => todo!("Can not yet deal with other acceptance types"),
+            OmegaAcceptanceCondition::CoBuchi => {
+                write!(w, "acc-name: co-Buchi\nAcceptance: 1 Fin(0)\n")
+            }
+            OmegaAcceptanceCondition::Safety => {
+                write!(w, "acc-name: all\nAcceptance: 0 t\n")
+            }
+            OmegaAcceptanceCondition::Rabin => {
+                write_emerson_lei_hoa(w, &EmersonLeiCondition::rabin(sets.div_ceil(2) as Int))
+            }
+            OmegaAcceptanceCondition::Streett => {
+                write_emerson_lei_hoa(w, &EmersonLeiCondition::streett(sets.div_ceil(2) as Int))
+            }
+            OmegaAcceptanceCondition::EmersonLei(condition) => write_emerson_lei_hoa(w, condition),
+        }
+    }
+
+    /// Returns the number of acceptance sets that are declared when writing `self` for an
+    /// automaton whose edges use `sets` acceptance sets. Marks beyond that do not influence
+    /// acceptance and are therefore omitted.
+    fn hoa_set_count(&self, sets: usize) -> usize {
+        match self {
+            OmegaAcceptanceCondition::Parity(_, high)
+            | OmegaAcceptanceCondition::MaxParity(_, high) => *high as usize + 1,
+            OmegaAcceptanceCondition::Buchi
+            | OmegaAcceptanceCondition::CoBuchi
+            | OmegaAcceptanceCondition::Reachability => 1,
+            OmegaAcceptanceCondition::Safety => 0,
+            OmegaAcceptanceCondition::Rabin | OmegaAcceptanceCondition::Streett => {
+                2 * sets.div_ceil(2)
+            }
+            OmegaAcceptanceCondition::EmersonLei(condition) => condition.set_count(),
+        }
+    }
+}
+
+/// Determines the `acc-name` of the given condition, if it has one of the canonical forms
+/// that are named in the HOA format.
+fn emerson_lei_acc_name(condition: &EmersonLeiCondition) -> Option<String> {
+    let sets = condition.set_count() as Int;
+    let canonical_sets = |found: &[Int]| found.iter().copied().eq(0..sets);
+    let canonical_pairs = |pairs: &[(Int, Int)]| {
+        pairs
+            .iter()
+            .enumerate()
+            .all(|(i, &(fin, inf))| fin as usize == 2 * i && inf as usize == 2 * i + 1)
+            && 2 * pairs.len() == sets as usize
+    };
+    Some(match condition.classify()? {
+        EmersonLeiClass::Boolean(true) => "all".to_string(),
+        EmersonLeiClass::Boolean(false) => "none".to_string(),
+        EmersonLeiClass::Buchi(0) => "Buchi".to_string(),
+        EmersonLeiClass::CoBuchi(0) => "co-Buchi".to_string(),
+        EmersonLeiClass::GeneralizedBuchi(found) if canonical_sets(&found) => {
+            format!("generalized-Buchi {sets}")
+        }
+        EmersonLeiClass::GeneralizedCoBuchi(found) if canonical_sets(&found) => {
+            format!("generalized-co-Buchi {sets}")
         }
+        EmersonLeiClass::Parity(priorities) => format!("parity min even {priorities}"),
+        EmersonLeiClass::Rabin(pairs) if canonical_pairs(&pairs) => {
+            format!("Rabin {}", pairs.len())
+        }
+        EmersonLeiClass::Streett(pairs) if canonical_pairs(&pairs) => {
+            format!("Streett {}", pairs.len())
+        }
+        _ => return None,
+    })
+}
+
+/// Writes the `acc-name` (if the condition has a canonical form) and `Acceptance` lines.
+fn write_emerson_lei_hoa<W: Write>(w: &mut W, condition: &EmersonLeiCondition) -> Result {
+    write_emerson_lei_hoa_over(w, condition, 0)
+}
+
+/// Works like [`write_emerson_lei_hoa`], but declares at least `sets` acceptance sets, even
+/// if the condition does not refer to all of them. This is needed when edges may be marked
+/// with sets that vanished from the condition when simplifying it.
+fn write_emerson_lei_hoa_over<W: Write>(
+    w: &mut W,
+    condition: &EmersonLeiCondition,
+    sets: usize,
+) -> Result {
+    let declared = sets.max(condition.set_count());
+    if declared == condition.set_count()
+        && let Some(name) = emerson_lei_acc_name(condition)
+    {
+        writeln!(w, "acc-name: {name}")?;
+    }
+    writeln!(w, "Acceptance: {declared} {condition}")
+}
+
+/// Writes the acceptance marks of an edge with a leading space, nothing is written if
+/// `sets` is empty.
+fn write_marks<W: Write, I: IntoIterator<Item = usize>>(w: &mut W, sets: I) -> Result {
+    let mut sets = sets.into_iter().peekable();
+    if sets.peek().is_some() {
+        write!(w, " {{{}}}", sets.join(" "))?;
+    }
+    Ok(())
+}
+
+/// Writes the `name:` and `tool:` lines for the given metadata, if it has a name or tool.
+fn write_name_and_tool<W: Write, Idx: Ord>(w: &mut W, metadata: &AutomatonMetadata<Idx>) -> Result {
+    if let Some(name) = metadata.name() {
+        writeln!(w, "name: {}", quoted(name))?;
+    }
+    if let Some((tool, version)) = metadata.tool() {
+        write!(w, "tool: {}", quoted(tool))?;
+        if let Some(version) = version {
+            write!(w, " {}", quoted(version))?;
+        }
+        w.write_char('\n')?;
+    }
+    Ok(())
+}
+
+/// Writes the additional header items of the given metadata, one per line.
+fn write_header_items<W: Write, Idx: Ord>(w: &mut W, metadata: &AutomatonMetadata<Idx>) -> Result {
+    for item in metadata.header_items() {
+        writeln!(w, "{item}")?;
+    }
+    Ok(())
+}
+
+/// Writes the name of a state as it follows the id in a `State:` line, including a leading
+/// space.
+fn write_state_name<W: Write>(w: &mut W, name: &str) -> Result {
+    write!(w, " {}", quoted(name))
+}
+
+impl<A: HoaSuitableAlphabet, const DET: bool> OmegaAutomaton<A, DET> {
+    /// Writes `self` in the HOA format, which preserves the [`OmegaAcceptanceCondition`]
+    /// and the [`AcceptanceMask`] of each edge. Only the marks that are relevant for the
+    /// acceptance condition are written. States are numbered consecutively in the order
+    /// in which the transition system lists them.
+    ///
+    /// As the HOA format only supports conditions on the sets that are visited infinitely
+    /// often, an automaton with [`OmegaAcceptanceCondition::Reachability`] acceptance is
+    /// written with an additional accepting sink, into which all marked edges lead. For
+    /// [`OmegaAcceptanceCondition::Safety`], marked edges are omitted.
+    pub fn write_hoa<W: Write>(&self, w: &mut W) -> Result {
+        let ts = self.ts();
+        let ids: crate::core::math::Map<_, usize> = ts
+            .state_indices()
+            .enumerate()
+            .map(|(i, q)| (q, i))
+            .collect();
+        let used = ts
+            .state_indices()
+            .flat_map(|q| ts.edges_from(q).expect("state must exist"))
+            .filter_map(|edge| AcceptanceMask::max(&edge.color()))
+            .max()
+            .map_or(0, |c| c as usize + 1);
+        let acceptance = self.acceptance();
+        let sets = acceptance.hoa_set_count(used);
+        let sink = (*acceptance == OmegaAcceptanceCondition::Reachability).then_some(ids.len());
+
+        w.write_str("HOA: v1\n")?;
+        write_name_and_tool(w, self.metadata())?;
+        ts.alphabet().write_alphabet_description(w)?;
+        writeln!(w, "States: {}", ids.len() + usize::from(sink.is_some()))?;
+        writeln!(w, "Start: {}", ids[&self.initial()])?;
+        acceptance.write_hoa(w, used)?;
+        w.write_str("properties: ")?;
+        w.write_str(HOA_PROPERTIES)?;
+        if DET {
+            w.write_str(" deterministic")?;
+        }
+        w.write_str("\n")?;
+        write_header_items(w, self.metadata())?;
+        w.write_str("--BODY--")?;
+
+        for (&state, id) in &ids {
+            write!(w, "\nState: {id}")?;
+            if let Some(name) = self.metadata().state_name(state) {
+                write_state_name(w, name)?;
+            }
+            for edge in ts.edges_from(state).expect("state must exist") {
+                let color = edge.color();
+                let target = match (acceptance, sink) {
+                    (OmegaAcceptanceCondition::Safety, _) if color.contains(0) => continue,
+                    (_, Some(sink)) if color.contains(0) => sink,
+                    _ => ids[&edge.target()],
+                };
+                w.write_str("\n[")?;
+                ts.alphabet().write_expression(w, edge.expression())?;
+                write!(w, "] {target}")?;
+                if sink.is_none() {
+                    write_marks(w, color.iter().map(|c| c as usize).filter(|c| *c < sets))?;
+                }
+            }
+        }
+        if let Some(sink) = sink {
+            write!(w, "\nState: {sink}")?;
+            let alphabet = ts.alphabet();
+            for symbol in alphabet.universe() {
+                w.write_str("\n[")?;
+                alphabet.write_expression(w, &alphabet.make_expression(symbol))?;
+                write!(w, "] {sink} {{0}}")?;
+            }
+        }
+
+        w.write_str("\n--END--\n")
+    }
+
+    /// Works like [`Self::write_hoa`], but additionally lists every property that
+    /// [`HoaRepresentation::infer_properties`] finds to hold for the written automaton,
+    /// such as `deterministic`, `complete`, `state-acc`, `colored`, `weak` and `very-weak`.
+    /// As the properties are inferred from the output itself, they can not disagree with
+    /// what [`HoaRepresentation::validate_properties`] checks.
+    pub fn write_hoa_inferring_properties<W: Write>(&self, w: &mut W) -> Result {
+        let mut hoa = HoaRepresentation::try_from(self.to_hoa().as_str())
+            .expect("the HOA output of an automaton must be parseable");
+        hoa.add_inferred_properties();
+        w.write_str(&hoars::output::to_hoa(&hoa))?;
+        w.write_str("\n")
+    }
+
+    /// Produces a HOA string for `self`, see [`Self::write_hoa`].
+    pub fn to_hoa(&self) -> String {
+        let mut w = String::new();
+        self.write_hoa(&mut w).unwrap();
+        trace!("produced HOA string from automaton\n{}", w);
+        w
+    }
+
+    /// Produces a HOA string for `self`, see [`Self::write_hoa_inferring_properties`].
+    pub fn to_hoa_inferring_properties(&self) -> String {
+        let mut w = String::new();
+        self.write_hoa_inferring_properties(&mut w).unwrap();
+        w
+    }
+}
+
+impl<A, D> IntoNBA<D>
+where
+    A: HoaSuitableAlphabet,
+    D: TransitionSystem<Alphabet = A, StateColor = bool, EdgeColor = bool>,
+{
+    /// Writes `self` in the HOA format. The acceptance is transition-based, an edge belongs
+    /// to the acceptance set `0` if it is accepting or leaves an accepting state. States are
+    /// numbered consecutively in the order in which the transition system lists them.
+    pub fn write_hoa<W: Write>(&self, w: &mut W) -> Result {
+        let ts = self.ts();
+        let ids: crate::core::math::Map<StateIndex<D>, usize> = ts
+            .state_indices()
+            .enumerate()
+            .map(|(i, q)| (q, i))
+            .collect();
+
+        w.write_str("HOA: v1\n")?;
+        write_name_and_tool(w, self.metadata())?;
+        ts.alphabet().write_alphabet_description(w)?;
+        writeln!(w, "States: {}", ids.len())?;
+        writeln!(w, "Start: {}", ids[&self.initial()])?;
+        OmegaAcceptanceCondition::Buchi.write_hoa(w, 1)?;
+        writeln!(w, "properties: {HOA_PROPERTIES}")?;
+        write_header_items(w, self.metadata())?;
+        w.write_str("--BODY--")?;
+
+        for (&state, id) in &ids {
+            write!(w, "\nState: {id}")?;
+            if let Some(name) = self.metadata().state_name(state) {
+                write_state_name(w, name)?;
+            }
+            let accepting = ts.state_color(state).expect("state must exist");
+            for edge in ts.edges_from(state).expect("state must exist") {
+                w.write_str("\n[")?;
+                ts.alphabet().write_expression(w, edge.expression())?;
+                write!(w, "] {}", ids[&edge.target()])?;
+                if accepting || edge.color() {
+                    w.write_str(" {0}")?;
+                }
+            }
+        }
+
+        w.write_str("\n--END--\n")
+    }
+
+    /// Works like [`Self::write_hoa`], but additionally lists every property that
+    /// [`HoaRepresentation::infer_properties`] finds to hold for the written automaton,
+    /// such as `deterministic`, `complete`, `state-acc`, `colored`, `weak` and `very-weak`.
+    /// As the properties are inferred from the output itself, they can not disagree with
+    /// what [`HoaRepresentation::validate_properties`] checks.
+    pub fn write_hoa_inferring_properties<W: Write>(&self, w: &mut W) -> Result {
+        let mut hoa = HoaRepresentation::try_from(self.to_hoa().as_str())
+            .expect("the HOA output of an automaton must be parseable");
+        hoa.add_inferred_properties();
+        w.write_str(&hoars::output::to_hoa(&hoa))?;
+        w.write_str("\n")
+    }
+
+    /// Produces a HOA string for `self`, see [`Self::write_hoa`].
+    pub fn to_hoa(&self) -> String {
+        let mut w = String::new();
+        self.write_hoa(&mut w).unwrap();
+        trace!("produced HOA string from automaton\n{}", w);
+        w
+    }
+}
+
+impl<A: HoaSuitableAlphabet> AlternatingOmegaAutomaton<A> {
+    /// Writes `self` in the HOA format, where each edge leads to the conjunction of its
+    /// targets.
+    pub fn write_hoa<W: Write>(&self, w: &mut W) -> Result {
+        let edges = || {
+            self.state_indices()
+                .flat_map(|q| self.edges_from(q).expect("state must exist"))
+        };
+        let used = edges()
+            .filter_map(|edge| edge.color().max())
+            .max()
+            .map_or(0, |c| c as usize + 1);
+        let universal = self.initial().len() > 1 || edges().any(|edge| edge.targets().len() > 1);
+
+        w.write_str("HOA: v1\n")?;
+        self.alphabet().write_alphabet_description(w)?;
+        writeln!(w, "States: {}", self.size())?;
+        writeln!(w, "Start: {}", self.initial().iter().join("&"))?;
+        self.acceptance().write_hoa(w, used)?;
+        if universal {
+            writeln!(w, "properties: {HOA_PROPERTIES} univ-branch")?;
+        } else {
+            writeln!(w, "properties: {HOA_PROPERTIES}")?;
+        }
+        w.write_str("--BODY--")?;
+
+        for state in self.state_indices() {
+            write!(w, "\nState: {state}")?;
+            for edge in self.edges_from(state).expect("state must exist") {
+                w.write_str("\n[")?;
+                self.alphabet().write_expression(w, edge.expression())?;
+                write!(w, "] {}", edge.targets().iter().join("&"))?;
+                write_marks(w, edge.color().iter().map(|c| c as usize))?;
+            }
+        }
+
+        w.write_str("\n--END--\n")
+    }
+
+    /// Works like [`Self::write_hoa`], but additionally lists every property that
+    /// [`HoaRepresentation::infer_properties`] finds to hold for the written automaton,
+    /// such as `deterministic`, `complete`, `state-acc`, `colored`, `weak` and `very-weak`.
+    /// As the properties are inferred from the output itself, they can not disagree with
+    /// what [`HoaRepresentation::validate_properties`] checks.
+    pub fn write_hoa_inferring_properties<W: Write>(&self, w: &mut W) -> Result {
+        let mut hoa = HoaRepresentation::try_from(self.to_hoa().as_str())
+            .expect("the HOA output of an automaton must be parseable");
+        hoa.add_inferred_properties();
+        w.write_str(&hoars::output::to_hoa(&hoa))?;
+        w.write_str("\n")
+    }
+
+    /// Produces a HOA string for `self`, see [`Self::write_hoa`].
+    pub fn to_hoa(&self) -> String {
+        let mut w = String::new();
+        self.write_hoa(&mut w).unwrap();
+        trace!("produced HOA string from automaton\n{}", w);
+        w
     }
 }
 
@@ -104,12 +525,74 @@ pub trait HoaSuitableAlphabet: Alphabet {
 }
 
 impl<A: HoaSuitableAlphabet> WriteHoa for DBA<A> {
-    fn write_edge_color<W: std::fmt::Write>(&self, w: &mut W, label: EdgeColor<Self>) -> Result {
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = ();
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {}
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
         if label {
-            write!(w, "{{{}}}", 0)
-        } else {
-            write!(w, "{{{}}}", 1)
+            w.write_str(" {0}")?;
+        }
+        Ok(())
+    }
+
+    fn write_expression<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        expr: &EdgeExpression<Self>,
+    ) -> Result {
+        self.alphabet().write_expression(w, expr)
+    }
+
+    fn write_state_id<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        id: Self::StateIndex,
+    ) -> std::fmt::Result {
+        write!(w, "{}", id)
+    }
+
+    fn write_alphabet_description<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+        self.alphabet().write_alphabet_description(w)
+    }
+
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+    ) -> Result {
+        OmegaAcceptanceCondition::Buchi.write_hoa(w, 1)
+    }
+}
+
+impl<A: HoaSuitableAlphabet> WriteHoa for DCBA<A> {
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = ();
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {}
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
+        if label {
+            w.write_str(" {0}")?;
         }
+        Ok(())
     }
 
     fn write_expression<W: std::fmt::Write>(
@@ -132,14 +615,31 @@ impl<A: HoaSuitableAlphabet> WriteHoa for DBA<A> {
         self.alphabet().write_alphabet_description(w)
     }
 
-    fn write_acceptance<W: std::fmt::Write>(&self, w: &mut W) -> Result {
-        OmegaAcceptanceCondition::Buchi.write_hoa(w)
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+    ) -> Result {
+        OmegaAcceptanceCondition::CoBuchi.write_hoa(w, 1)
     }
 }
 
 impl<A: HoaSuitableAlphabet> WriteHoa for DPA<A> {
-    fn write_edge_color<W: std::fmt::Write>(&self, w: &mut W, label: EdgeColor<Self>) -> Result {
-        write!(w, "{{{}}}", label)
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = ();
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {}
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
+        write!(w, " {{{}}}", label)
     }
 
     fn write_expression<W: std::fmt::Write>(
@@ -162,10 +662,249 @@ impl<A: HoaSuitableAlphabet> WriteHoa for DPA<A> {
         self.alphabet().write_alphabet_description(w)
     }
 
-    fn write_acceptance<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        _sets: &Self::AcceptanceSets,
+    ) -> Result {
         let (low, high) = self.low_and_high_priority();
 
-        OmegaAcceptanceCondition::Parity(low, high).write_hoa(w)
+        OmegaAcceptanceCondition::Parity(low, high).write_hoa(w, high as usize + 1)
+    }
+}
+
+/// Assigns to each color that occurs in one of the given pairs of a Rabin or Streett
+/// condition an acceptance set, which is its position among all such colors.
+fn pair_colors<'a, C: Color + Ord + 'a>(pairs: impl Iterator<Item = &'a RabinPair<C>>) -> Set<C> {
+    pairs
+        .flat_map(|pair| pair.fin().iter().chain(pair.inf()))
+        .sorted()
+        .cloned()
+        .collect()
+}
+
+impl<A: HoaSuitableAlphabet, C: Color + Ord> WriteHoa for DRA<A, Void, C> {
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = Set<C>;
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {
+        pair_colors(self.acceptance().pairs())
+    }
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
+        write_marks(w, sets.get_index_of(&label))
+    }
+
+    fn write_expression<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        expr: &EdgeExpression<Self>,
+    ) -> Result {
+        self.alphabet().write_expression(w, expr)
+    }
+
+    fn write_state_id<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        id: Self::StateIndex,
+    ) -> std::fmt::Result {
+        write!(w, "{}", id)
+    }
+
+    fn write_alphabet_description<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+        self.alphabet().write_alphabet_description(w)
+    }
+
+    /// Each pair becomes a conjunction of `Fin` for each of its `fin` colors and a
+    /// disjunction of `Inf` over its `inf` colors.
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+    ) -> Result {
+        let set = |c: &C| sets.get_index_of(c).expect("color occurs") as Int;
+        let condition = EmersonLeiCondition::Or(
+            self.acceptance()
+                .pairs()
+                .map(|pair| {
+                    let mut conjuncts: Vec<_> = pair
+                        .fin()
+                        .iter()
+                        .map(|c| EmersonLeiCondition::fin(set(c)))
+                        .collect();
+                    conjuncts.push(EmersonLeiCondition::Or(
+                        pair.inf()
+                            .iter()
+                            .map(|c| EmersonLeiCondition::inf(set(c)))
+                            .collect(),
+                    ));
+                    EmersonLeiCondition::And(conjuncts)
+                })
+                .collect(),
+        )
+        .simplified();
+        write_emerson_lei_hoa_over(w, &condition, sets.len())
+    }
+}
+
+impl<A: HoaSuitableAlphabet, C: Color + Ord> WriteHoa for DSA<A, Void, C> {
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = Set<C>;
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {
+        pair_colors(self.acceptance().pairs())
+    }
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
+        write_marks(w, sets.get_index_of(&label))
+    }
+
+    fn write_expression<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        expr: &EdgeExpression<Self>,
+    ) -> Result {
+        self.alphabet().write_expression(w, expr)
+    }
+
+    fn write_state_id<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        id: Self::StateIndex,
+    ) -> std::fmt::Result {
+        write!(w, "{}", id)
+    }
+
+    fn write_alphabet_description<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+        self.alphabet().write_alphabet_description(w)
+    }
+
+    /// As a Streett condition is violated by every run that satisfies one of its pairs,
+    /// each pair becomes a disjunction of `Inf` for each of its `fin` colors and a
+    /// conjunction of `Fin` over its `inf` colors.
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+    ) -> Result {
+        let set = |c: &C| sets.get_index_of(c).expect("color occurs") as Int;
+        let condition = EmersonLeiCondition::And(
+            self.acceptance()
+                .pairs()
+                .map(|pair| {
+                    let mut disjuncts: Vec<_> = pair
+                        .fin()
+                        .iter()
+                        .map(|c| EmersonLeiCondition::inf(set(c)))
+                        .collect();
+                    disjuncts.push(EmersonLeiCondition::And(
+                        pair.inf()
+                            .iter()
+                            .map(|c| EmersonLeiCondition::fin(set(c)))
+                            .collect(),
+                    ));
+                    EmersonLeiCondition::Or(disjuncts)
+                })
+                .collect(),
+        )
+        .simplified();
+        write_emerson_lei_hoa_over(w, &condition, sets.len())
+    }
+}
+
+/// Assigns to each color that occurs in one of the sets of a Muller condition an
+/// acceptance set, which is its position among all such colors. All other colors share
+/// the acceptance set that comes after these.
+fn muller_colors<C: Color + Ord>(dma: &DMA<impl Alphabet, Void, C>) -> Set<C> {
+    dma.acceptance()
+        .sets()
+        .iter()
+        .flatten()
+        .sorted()
+        .cloned()
+        .collect()
+}
+
+impl<A: HoaSuitableAlphabet, C: Color + Ord> WriteHoa for DMA<A, Void, C> {
+    fn metadata(&self) -> Option<&AutomatonMetadata<Self::StateIndex>> {
+        Some(crate::Automaton::metadata(self))
+    }
+
+    type AcceptanceSets = Set<C>;
+
+    fn acceptance_sets(&self) -> Self::AcceptanceSets {
+        muller_colors(self)
+    }
+
+    fn write_edge_color<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+        label: EdgeColor<Self>,
+    ) -> Result {
+        write_marks(w, [sets.get_index_of(&label).unwrap_or(sets.len())])
+    }
+
+    fn write_expression<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        expr: &EdgeExpression<Self>,
+    ) -> Result {
+        self.alphabet().write_expression(w, expr)
+    }
+
+    fn write_state_id<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        id: Self::StateIndex,
+    ) -> std::fmt::Result {
+        write!(w, "{}", id)
+    }
+
+    fn write_alphabet_description<W: std::fmt::Write>(&self, w: &mut W) -> Result {
+        self.alphabet().write_alphabet_description(w)
+    }
+
+    /// Each set `M` of the condition becomes a conjunction of `Inf` for each color in `M`
+    /// and of `Fin` for every other acceptance set.
+    fn write_acceptance<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        sets: &Self::AcceptanceSets,
+    ) -> Result {
+        let condition = EmersonLeiCondition::Or(
+            self.acceptance()
+                .sets()
+                .iter()
+                .map(|set| {
+                    EmersonLeiCondition::And(
+                        (0..=sets.len())
+                            .map(|i| match sets.get_index(i) {
+                                Some(c) if set.contains(c) => EmersonLeiCondition::inf(i as Int),
+                                _ => EmersonLeiCondition::fin(i as Int),
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+        write_emerson_lei_hoa(w, &condition)
     }
 }
 
@@ -175,19 +914,17 @@ impl HoaSuitableAlphabet for CharAlphabet {
             w,
             "AP: {} {}",
             self.size(),
-            (0..self.size())
-                .map(|i| ((b'a') + (i as u8)) as char)
-                .map(|c| format!("\"{}\"", c))
-                .join(" ")
+            self.universe().map(|c| quoted(&c.to_string())).join(" ")
         )
     }
 
     fn write_expression<W: std::fmt::Write>(&self, w: &mut W, expr: &Self::Expression) -> Result {
+        let position = self.try_position(*expr);
         write!(
             w,
             "{}",
             (0..self.size())
-                .map(|i| if i as u8 == (*expr as u8) - (b'a') {
+                .map(|i| if Some(i) == position {
                     format!("{i}")
                 } else {
                     format!("!{i}")
@@ -203,15 +940,12 @@ impl HoaSuitableAlphabet for PropAlphabet {
             w,
             "AP: {} {}",
             self.aps(),
-            self.apnames()
-                .iter()
-                .map(|name| format!("\"{}\"", name))
-                .join(" ")
+            self.apnames().iter().map(|name| quoted(name)).join(" ")
         )
     }
 
     fn write_expression<W: std::fmt::Write>(&self, w: &mut W, expr: &Self::Expression) -> Result {
-        write!(w, "{}", expr.show())
+        write!(w, "{}", expr.to_hoa_label())
     }
 }
 
@@ -220,7 +954,7 @@ impl HoaSuitableAlphabet for PropAlphabet {
 fn build_parity_condition_hoa(low: Int, high: Int) -> String {
     let parity = low % 2;
 
-    if high <= low {
+    if high < low {
         return match parity {
             0 => "t".to_string(),
             1 => "f".to_string(),
@@ -253,10 +987,33 @@ fn build_parity_condition_hoa(low: Int, high: Int) -> String {
     }
 }
 
+/// Builds the canonical max even parity condition over the priorities `0..=high`, which
+/// is `Inf(high) | (Fin(high - 1) & (..))` if `high` is even.
+fn build_max_parity_condition_hoa(high: Int) -> String {
+    (1..=high).fold("Inf(0)".to_string(), |acc, i| {
+        let acc = if i == 1 { acc } else { format!("({acc})") };
+        if i.is_multiple_of(2) {
+            format!("Inf({i}) | {acc}")
+        } else {
+            format!("Fin({i}) & {acc}")
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use super::WriteHoa;
+    use crate::TransitionSystem;
+    use crate::automaton::{
+        DCBA, DMA, DRA, DSA, DeterministicOmegaAutomaton, EmersonLeiCondition, MullerCondition,
+        OmegaAcceptanceCondition, OmegaAutomaton, RabinPair,
+    };
+    use crate::core::{alphabet::CharAlphabet, upw};
+    use crate::hoa::input::hoa_to_ts;
     use crate::ts::TSBuilder;
+    use hoars::{HoaRepresentation, Property};
 
     #[test]
     fn build_parity_hoa_string() {
@@ -268,6 +1025,10 @@ mod tests {
             crate::hoa::output::build_parity_condition_hoa(0, 2),
             "Inf(0) | (Fin(1) & Inf(2))"
         );
+        assert_eq!(
+            crate::hoa::output::build_max_parity_condition_hoa(3),
+            "Fin(3) & (Inf(2) | (Fin(1) & Inf(0)))"
+        );
     }
 
     #[test]
@@ -278,7 +1039,7 @@ mod tests {
         let hoa = dpa.to_hoa();
         assert_eq!(
             hoa,
-            "HOA: v1\nAP: 3 \"a\" \"b\" \"c\"\nStates: 1\nStart: 0\nacc-name: parity min even 3\nAcceptance: 3 Inf(0) | (Fin(1) & Inf(2))\n--BODY--\nState: 0\n[0 & !1 & !2] 0 {0}\n[!0 & 1 & !2] 0 {1}\n[!0 & !1 & 2] 0 {2}\n--END--\n"
+            "HOA: v1\nAP: 3 \"a\" \"b\" \"c\"\nStates: 1\nStart: 0\nacc-name: parity min even 3\nAcceptance: 3 Inf(0) | (Fin(1) & Inf(2))\nproperties: trans-labels explicit-labels trans-acc deterministic\n--BODY--\nState: 0\n[0 & !1 & !2] 0 {0}\n[!0 & 1 & !2] 0 {1}\n[!0 & !1 & 2] 0 {2}\n--END--\n"
         );
     }
 
@@ -290,7 +1051,374 @@ mod tests {
         let hoa = dba.to_hoa();
         assert_eq!(
             hoa,
-            "HOA: v1\nAP: 3 \"a\" \"b\" \"c\"\nStates: 1\nStart: 0\nacc-name: Buchi\nAcceptance: 1 Inf(0)\n--BODY--\nState: 0\n[0 & !1 & !2] 0 {0}\n[!0 & 1 & !2] 0 {1}\n[!0 & !1 & 2] 0 {0}\n--END--\n"
+            "HOA: v1\nAP: 3 \"a\" \"b\" \"c\"\nStates: 1\nStart: 0\nacc-name: Buchi\nAcceptance: 1 Inf(0)\nproperties: trans-labels explicit-labels trans-acc deterministic\n--BODY--\nState: 0\n[0 & !1 & !2] 0 {0}\n[!0 & 1 & !2] 0\n[!0 & !1 & 2] 0 {0}\n--END--\n"
+        );
+    }
+
+    #[test]
+    fn write_hoa_omega_automaton_roundtrip() {
+        for acceptance in [
+            "acc-name: Buchi\nAcceptance: 1 Inf(0)",
+            "acc-name: co-Buchi\nAcceptance: 1 Fin(0)",
+            "acc-name: parity min even 3\nAcceptance: 3 Inf(0) | (Fin(1) & Inf(2))",
+            "acc-name: Rabin 1\nAcceptance: 2 Fin(0) & Inf(1)",
+            "acc-name: Streett 1\nAcceptance: 2 Fin(0) | Inf(1)",
+            "acc-name: generalized-Buchi 2\nAcceptance: 2 Inf(0) & Inf(1)",
+            "Acceptance: 2 Fin(!0) | Inf(1)",
+        ] {
+            // marks have to refer to declared sets
+            let sets: usize = acceptance
+                .split_once("Acceptance: ")
+                .and_then(|(_, rest)| rest.split(' ').next()?.parse().ok())
+                .unwrap();
+            let last = sets - 1;
+            let raw_hoa = format!(
+                "HOA: v1\nStates: 2\nStart: 0\n{acceptance}\nAP: 1 \"a\"\n--BODY--\n\
+                 State: 0\n[0] 0 {{0}}\n[0] 1\n[!0] 1 {{{last}}}\nState: 1\n[t] 0 {{0 {last}}}\n--END--\n"
+            );
+            let aut = hoa_to_ts::<false>(&raw_hoa)
+                .unwrap()
+                .pop()
+                .expect("contains an automaton");
+            let written = aut.to_hoa();
+            let name = acceptance.lines().find(|line| line.starts_with("acc-name"));
+            assert_eq!(
+                written.lines().find(|line| line.starts_with("acc-name")),
+                name,
+                "lost acc-name in\n{written}"
+            );
+            let reparsed = hoa_to_ts::<false>(&written)
+                .unwrap()
+                .pop()
+                .expect("written automaton can be parsed");
+            assert_eq!(reparsed.acceptance(), aut.acceptance());
+            // edges may be listed in a different order after parsing
+            let rewritten = reparsed.to_hoa();
+            assert_eq!(
+                rewritten.lines().sorted().collect_vec(),
+                written.lines().sorted().collect_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn write_hoa_keeps_metadata() {
+        let raw_hoa = "HOA: v1\nname: \"GFa\"\ntool: \"ltl2tgba\" \"2.11\"\nStates: 2\nStart: 0\n\
+            Acceptance: 1 Inf(0)\nAP: 1 \"a\"\n--BODY--\nState: 0 \"waiting\"\n[0] 1\n[!0] 0\n\
+            State: 1\n[t] 0 {0}\n--END--\n";
+        let mut aut = hoa_to_ts::<true>(raw_hoa).unwrap().pop().unwrap();
+        assert_eq!(aut.metadata().name(), Some("GFa"));
+        assert_eq!(aut.metadata().tool(), Some(("ltl2tgba", Some("2.11"))));
+        assert_eq!(
+            aut.metadata().state_names().collect_vec(),
+            vec![(&0, "waiting")]
+        );
+
+        aut.metadata_mut().set_state_name(1, "seen a");
+        aut.metadata_mut()
+            .push_header_item(hoars::HeaderItem::Properties(vec![Property::Complete]));
+        let written = aut.to_hoa();
+        for line in [
+            "name: \"GFa\"",
+            "tool: \"ltl2tgba\" \"2.11\"",
+            "properties: complete",
+            "State: 0 \"waiting\"",
+            "State: 1 \"seen a\"",
+        ] {
+            assert!(
+                written.lines().contains(&line),
+                "missing {line} in\n{written}"
+            );
+        }
+        let reparsed = hoa_to_ts::<true>(&written).unwrap().pop().unwrap();
+        assert_eq!(reparsed.metadata().name(), Some("GFa"));
+        assert_eq!(reparsed.metadata().state_name(1), Some("seen a"));
+        assert_eq!(
+            aut.to_hoa_inferring_properties().matches("tool:").count(),
+            1
+        );
+        let inferred = hoa_to_ts::<true>(&aut.to_hoa_inferring_properties())
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(inferred.metadata(), reparsed.metadata());
+
+        // the conversion builds a new automaton, but its states are numbered alike
+        let mut dpa = aut.into_dpa().with_metadata(reparsed.metadata().clone());
+        assert!(dpa.to_hoa().contains("State: 0 \"waiting\""));
+
+        dpa.metadata_mut().set_name("complemented");
+        let mapped = dpa.map_ts(|ts| ts.map_edge_colors(|c| c + 1));
+        assert_eq!(mapped.metadata().name(), Some("complemented"));
+        let restricted = mapped.restrict_states(|q| q == 0).unwrap();
+        assert_eq!(restricted.metadata().state_name(0), Some("waiting"));
+        assert_eq!(restricted.metadata().state_name(1), None);
+        assert!(restricted.restrict_states(|q| q != 0).is_none());
+    }
+
+    #[test]
+    fn write_hoa_escapes_strings() {
+        let raw_hoa = "HOA: v1\nStates: 1\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n\
+            --BODY--\nState: 0\n[t] 0 {0}\n--END--\n";
+        let mut aut = hoa_to_ts::<true>(raw_hoa).unwrap().pop().unwrap();
+        let name = r#"say "hi" \ bye"#;
+        aut.metadata_mut().set_name(name);
+        aut.metadata_mut()
+            .set_tool(r#"C:\tool"#, Some(r#"1.0 "beta""#.to_string()));
+        aut.metadata_mut().set_state_name(0, r#"\"#);
+
+        let written = aut.to_hoa();
+        for line in [
+            r#"name: "say \"hi\" \\ bye""#,
+            r#"tool: "C:\\tool" "1.0 \"beta\"""#,
+            r#"State: 0 "\\""#,
+        ] {
+            assert!(
+                written.lines().contains(&line),
+                "missing {line} in\n{written}"
+            );
+        }
+        let reparsed = hoa_to_ts::<true>(&written).unwrap().pop().unwrap();
+        assert_eq!(reparsed.metadata().name(), Some(name));
+        assert_eq!(
+            reparsed.metadata().tool(),
+            Some((r#"C:\tool"#, Some(r#"1.0 "beta""#)))
+        );
+        assert_eq!(reparsed.metadata().state_name(0), Some(r#"\"#));
+    }
+
+    #[test]
+    fn write_hoa_inferring_properties() {
+        let properties = |hoa: &str| {
+            let written = hoa_to_ts::<false>(hoa)
+                .unwrap()
+                .pop()
+                .unwrap()
+                .to_hoa_inferring_properties();
+            let parsed = HoaRepresentation::try_from(written.as_str()).unwrap();
+            assert_eq!(parsed.validate_properties(), vec![]);
+            parsed.properties()
+        };
+        let nondeterministic = "HOA: v1\nStates: 2\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n\
+            --BODY--\nState: 0\n[0] 0 {0}\n[0] 1\n[!0] 1\nState: 1\n[t] 0 {0}\n--END--\n";
+        let found = properties(nondeterministic);
+        assert!(found.contains(&Property::Complete));
+        assert!(!found.contains(&Property::Deterministic));
+
+        let found = properties(&nondeterministic.replace("[0] 1\n", ""));
+        assert!(found.contains(&Property::Complete));
+        assert!(found.contains(&Property::Deterministic));
+
+        let found = properties(&nondeterministic.replace("[!0] 1\n", ""));
+        assert!(!found.contains(&Property::Complete));
+        assert!(!found.contains(&Property::StateAcceptance));
+        assert!(!found.contains(&Property::Weak));
+
+        // every edge carries exactly one mark and the marks only depend on the source
+        let colored = "HOA: v1\nStates: 2\nStart: 0\nAcceptance: 2 Inf(0) | Inf(1)\n\
+            AP: 1 \"a\"\n--BODY--\nState: 0\n[0] 0 {0}\n[!0] 1 {0}\n\
+            State: 1\n[t] 0 {1}\n--END--\n";
+        let found = properties(colored);
+        assert!(found.contains(&Property::StateAcceptance));
+        assert!(found.contains(&Property::Colored));
+        assert!(!found.contains(&Property::Weak));
+
+        // the only cycles are self-loops, so each SCC consists of a single state
+        let very_weak = "HOA: v1\nStates: 2\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n\
+            --BODY--\nState: 0\n[0] 0\n[!0] 1\nState: 1\n[t] 1 {0}\n--END--\n";
+        let found = properties(very_weak);
+        assert!(found.contains(&Property::Weak));
+        assert!(found.contains(&Property::VeryWeak));
+        assert!(!found.contains(&Property::Colored));
+
+        let found = properties(
+            &very_weak
+                .replace("[!0] 1\n", "[!0] 1\n[0] 0\n")
+                .replace("[t] 1 {0}", "[t] 0"),
+        );
+        assert!(found.contains(&Property::Weak));
+        assert!(!found.contains(&Property::VeryWeak));
+    }
+
+    #[test]
+    fn write_hoa_reachability_and_safety() {
+        let aut = hoa_to_ts::<true>(
+            "HOA: v1\nStates: 1\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n--BODY--\n\
+             State: 0\n[0] 0 {0}\n[!0] 0\n--END--\n",
+        )
+        .unwrap()
+        .pop()
+        .expect("contains an automaton");
+        let (ts, initial, _) = aut.into_parts();
+
+        let aut = OmegaAutomaton::new(ts.clone(), initial, OmegaAcceptanceCondition::Reachability);
+        let reachability = hoa_to_ts::<true>(&aut.to_hoa())
+            .unwrap()
+            .pop()
+            .expect("written automaton can be parsed");
+        assert_eq!(reachability.size(), 2);
+        let dpa = DeterministicOmegaAutomaton::<CharAlphabet>::from(reachability).into_dpa();
+        // the symbol `b` corresponds to the valuation in which `a` holds
+        assert!(dpa.accepts(upw!("a", "b")));
+        assert!(dpa.accepts(upw!("ab", "a")));
+        assert!(!dpa.accepts(upw!("a")));
+
+        let aut = OmegaAutomaton::new(ts, initial, OmegaAcceptanceCondition::Safety);
+        let safety = hoa_to_ts::<true>(&aut.to_hoa())
+            .unwrap()
+            .pop()
+            .expect("written automaton can be parsed");
+        assert_eq!(
+            safety.acceptance(),
+            &OmegaAcceptanceCondition::EmersonLei(EmersonLeiCondition::Boolean(true))
         );
+        assert_eq!(safety.ts().edges_from(0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn write_hoa_dra_and_dma() {
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 0),
+                (0, 'b', 1, 1),
+                (1, 'a', 0, 0),
+                (1, 'b', 2, 1),
+            ])
+            .into_dts();
+        // after parsing, the symbol `a` corresponds to `b` (only the first proposition
+        // holds) and `b` corresponds to `c` (only the second proposition holds)
+        let words = [
+            (upw!("a"), upw!("b")),
+            (upw!("b"), upw!("c")),
+            (upw!("ab"), upw!("bc")),
+            (upw!("abb"), upw!("bcc")),
+            (upw!("a", "b"), upw!("b", "c")),
+        ];
+
+        let dra = DRA::from_parts_with_acceptance(
+            ts.clone(),
+            0,
+            [
+                RabinPair::from_iters([0], [1]),
+                RabinPair::from_iters([1], [2, 0]),
+            ]
+            .into(),
+        );
+        let hoa = dra.to_hoa();
+        assert!(hoa.contains("Acceptance: 3 "), "{hoa}");
+        let parsed = hoa_to_ts::<true>(&hoa)
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        let parsed = DeterministicOmegaAutomaton::<CharAlphabet>::from(parsed).into_dpa();
+        for (word, parsed_word) in &words {
+            assert_eq!(
+                dra.accepts(word),
+                parsed.accepts(parsed_word),
+                "mismatch on {word:?}"
+            );
+        }
+
+        let dma = DMA::from_parts_with_acceptance(
+            ts,
+            0,
+            MullerCondition::from_iter_iter([vec![0], vec![0, 1]]),
+        );
+        let parsed = hoa_to_ts::<true>(&dma.to_hoa())
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        let parsed = DeterministicOmegaAutomaton::<CharAlphabet>::from(parsed).into_dpa();
+        for (word, parsed_word) in &words {
+            assert_eq!(
+                dma.accepts(word),
+                parsed.accepts(parsed_word),
+                "mismatch on {word:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn write_hoa_dcba_and_dsa() {
+        // see `write_hoa_dra_and_dma` for how the symbols are renamed by parsing
+        let words = [
+            (upw!("a"), upw!("b")),
+            (upw!("b"), upw!("c")),
+            (upw!("ab"), upw!("bc")),
+            (upw!("abb"), upw!("bcc")),
+            (upw!("a", "b"), upw!("b", "c")),
+        ];
+        let roundtrip = |hoa: &str| {
+            let parsed = hoa_to_ts::<true>(hoa)
+                .unwrap()
+                .pop()
+                .expect("contains an automaton");
+            DeterministicOmegaAutomaton::<CharAlphabet>::from(parsed).into_dpa()
+        };
+
+        // finitely many `b`
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([(0, 'a', false, 0), (0, 'b', true, 0)])
+            .into_dts();
+        let dcba = DCBA::from_parts(ts, 0);
+        let hoa = dcba.to_hoa();
+        assert!(
+            hoa.contains("acc-name: co-Buchi\nAcceptance: 1 Fin(0)\n"),
+            "{hoa}"
+        );
+        let parsed = roundtrip(&hoa);
+        for (word, parsed_word) in &words {
+            assert_eq!(
+                dcba.accepts(word),
+                parsed.accepts(parsed_word),
+                "mismatch on {word:?}"
+            );
+        }
+
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 0),
+                (0, 'b', 1, 1),
+                (1, 'a', 0, 0),
+                (1, 'b', 2, 1),
+            ])
+            .into_dts();
+        let dsa = DSA::from_parts_with_acceptance(
+            ts,
+            0,
+            [
+                RabinPair::from_iters([0], [1]),
+                RabinPair::from_iters([], [2]),
+            ]
+            .into(),
+        );
+        let hoa = dsa.to_hoa();
+        assert!(hoa.contains("Acceptance: 3 "), "{hoa}");
+        let parsed = roundtrip(&hoa);
+        for (word, parsed_word) in &words {
+            assert_eq!(
+                dsa.accepts(word),
+                parsed.accepts(parsed_word),
+                "mismatch on {word:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn write_hoa_declares_sets_of_unsatisfiable_pairs() {
+        let ts = TSBuilder::without_state_colors()
+            .with_transitions([(0, 'a', 0, 0), (0, 'b', 2, 0)])
+            .into_dts();
+        // the second pair can not be satisfied, so its color does not occur in the condition
+        let pairs = [
+            RabinPair::from_iters([0], [1]),
+            RabinPair::from_iters([2], []),
+        ];
+        let dra = DRA::from_parts_with_acceptance(ts.clone(), 0, pairs.clone().into());
+        let dsa = DSA::from_parts_with_acceptance(ts, 0, pairs.into());
+        for hoa in [dra.to_hoa(), dsa.to_hoa()] {
+            assert!(hoa.contains("Acceptance: 3 "), "{hoa}");
+            assert!(hoa_to_ts::<true>(&hoa).is_ok(), "{hoa}");
+        }
     }
 }
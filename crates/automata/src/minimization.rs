@@ -1,4 +1,4 @@
-pub(crate) mod partition_refinement;
+pub mod partition_refinement;
 
 use crate::automaton::{
     DFA, IntoDFA, IntoMealyMachine, IntoMooreMachine, MealyMachine, MooreMachine,
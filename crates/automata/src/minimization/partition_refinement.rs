@@ -5,10 +5,15 @@
 //! the output of the state, whereas for Mealy machines, we consider the output of the transition.
 //! It is necessary to have two distinct algorithms (so with different names) as there might be
 //! transition systems which have outputs on both the states and the transitions.
+//!
+//! The functions [`moore_greatest_bisimulation`] and [`mealy_greatest_bisimulation`] implement a
+//! naive iterative refinement, whereas [`moore_hopcroft_bisimulation`] and
+//! [`mealy_hopcroft_bisimulation`] use Hopcroft's algorithm, which runs in O(k n log n) time for
+//! an automaton with n states over an alphabet with k symbols. The latter are used for minimization.
 use std::{collections::BTreeSet, time::Instant};
 
 use crate::automaton::{MealyMachine, MooreMachine};
-use crate::core::{Color, math, math::Partition};
+use crate::core::{Color, alphabet::Expression, math, math::Partition};
 use crate::representation::{CollectTs, IntoTs};
 use crate::ts::{Deterministic, EdgeColor, IsEdge, StateColor};
 use crate::{Congruence, TransitionSystem};
@@ -87,7 +92,7 @@ where
     D: Congruence,
     EdgeColor<D>: Color,
 {
-    let partition = mealy_greatest_bisimulation(&mm);
+    let partition = mealy_hopcroft_bisimulation(&mm);
     trace!(
         "Building quotient with partition {{{}}}",
         partition
@@ -179,14 +184,14 @@ where
 /// Partition refinement algorithm for deterministic finite automata that have outputs on the states.
 /// Runs in O(n log n) time, where n is the number of states of the automaton and returns the unique
 /// minimal automaton that is bisimilar to the input. This method computes the maximal bisimulation
-/// by using [`moore_greatest_bisimulation`] and then uses the partition to compute the quotient
+/// by using [`moore_hopcroft_bisimulation`] and then uses the partition to compute the quotient
 /// automaton.
 pub fn moore_partition_refinement<D>(mm: D) -> MooreMachine<D::Alphabet, D::StateColor>
 where
     D: Congruence,
     StateColor<D>: Color,
 {
-    let partition = moore_greatest_bisimulation(&mm);
+    let partition = moore_hopcroft_bisimulation(&mm);
     trace!(
         "Building quotient with partition {{{}}}",
         partition
@@ -213,11 +218,271 @@ where
     ts.into_moore_with_initial(initial)
 }
 
+/// Computes the maximal bisimulation of a Moore machine, just like [`moore_greatest_bisimulation`],
+/// but uses Hopcroft's algorithm. The initial partition groups states by their color, which is
+/// then refined until it is stable.
+pub fn moore_hopcroft_bisimulation<D>(mm: D) -> Partition<D::StateIndex>
+where
+    D: Deterministic,
+    StateColor<D>: Color,
+{
+    let start = Instant::now();
+    let states: Vec<_> = mm.state_indices().collect();
+
+    let mut classes = math::Map::default();
+    let initial = states
+        .iter()
+        .map(|q| {
+            let color = mm.state_color(*q).expect("state must exist");
+            let next = classes.len();
+            *classes.entry(color).or_insert(next)
+        })
+        .collect();
+    let partition = hopcroft_refinement(&mm, &states, initial);
+
+    debug!(
+        "computed greatest bisimulation for Moore machine with Hopcroft's algorithm in {} microseconds",
+        start.elapsed().as_micros()
+    );
+    partition
+}
+
+/// Computes the maximal bisimulation of a Mealy machine, just like [`mealy_greatest_bisimulation`],
+/// but uses Hopcroft's algorithm. The initial partition groups states which produce the same
+/// output on every symbol, which is then refined until it is stable.
+pub fn mealy_hopcroft_bisimulation<D>(mm: D) -> Partition<D::StateIndex>
+where
+    D: Deterministic,
+    EdgeColor<D>: Color,
+{
+    let start = Instant::now();
+    let states: Vec<_> = mm.state_indices().collect();
+    let symbols: Vec<_> = mm.symbols().collect();
+
+    let mut classes = math::Map::default();
+    let initial = states
+        .iter()
+        .map(|q| {
+            let outputs = symbols
+                .iter()
+                .map(|sym| {
+                    mm.edges_from(*q)
+                        .expect("state must exist")
+                        .find(|t| t.expression().matched_by(*sym))
+                        .map(|t| t.color())
+                })
+                .collect::<Vec<_>>();
+            let next = classes.len();
+            *classes.entry(outputs).or_insert(next)
+        })
+        .collect();
+    let partition = hopcroft_refinement(&mm, &states, initial);
+
+    debug!(
+        "computed greatest bisimulation for Mealy machine with Hopcroft's algorithm in {} microseconds",
+        start.elapsed().as_micros()
+    );
+    partition
+}
+
+/// A partition of the dense indices `0..n` which can be refined efficiently. The elements
+/// of each block are stored contiguously in `elements`, and the first `marked[b]` elements
+/// of block `b` are the ones that have been marked in the current round.
+struct RefinablePartition {
+    elements: Vec<usize>,
+    position: Vec<usize>,
+    block: Vec<usize>,
+    start: Vec<usize>,
+    end: Vec<usize>,
+    marked: Vec<usize>,
+}
+
+impl RefinablePartition {
+    fn new(classes: &[usize]) -> Self {
+        let count = classes.iter().max().map_or(0, |max| max + 1);
+        let mut sizes = vec![0; count];
+        for class in classes {
+            sizes[*class] += 1;
+        }
+        let mut start = Vec::with_capacity(count);
+        let mut offset = 0;
+        for size in &sizes {
+            start.push(offset);
+            offset += size;
+        }
+        let end: Vec<_> = start.iter().zip(&sizes).map(|(s, l)| s + l).collect();
+
+        let mut fill = start.clone();
+        let mut elements = vec![0; classes.len()];
+        let mut position = vec![0; classes.len()];
+        for (q, class) in classes.iter().enumerate() {
+            elements[fill[*class]] = q;
+            position[q] = fill[*class];
+            fill[*class] += 1;
+        }
+
+        Self {
+            elements,
+            position,
+            block: classes.to_vec(),
+            start,
+            end,
+            marked: vec![0; count],
+        }
+    }
+
+    fn size(&self, block: usize) -> usize {
+        self.end[block] - self.start[block]
+    }
+
+    /// Marks `q` and returns `true` if it is the first marked element of its block.
+    fn mark(&mut self, q: usize) -> bool {
+        let b = self.block[q];
+        let first_unmarked = self.start[b] + self.marked[b];
+        let pos = self.position[q];
+        if pos < first_unmarked {
+            return false;
+        }
+        let other = self.elements[first_unmarked];
+        self.elements.swap(pos, first_unmarked);
+        self.position[other] = pos;
+        self.position[q] = first_unmarked;
+        self.marked[b] += 1;
+        self.marked[b] == 1
+    }
+
+    /// Splits the marked elements of `block` off into a new block, whose index is returned.
+    /// If either all or none of the elements are marked, no split happens and `None` is returned.
+    fn split(&mut self, block: usize) -> Option<usize> {
+        let marked = std::mem::take(&mut self.marked[block]);
+        if marked == 0 || marked == self.size(block) {
+            return None;
+        }
+        let new = self.start.len();
+        self.start.push(self.start[block]);
+        self.end.push(self.start[block] + marked);
+        self.marked.push(0);
+        self.start[block] += marked;
+        for pos in self.start[new]..self.end[new] {
+            self.block[self.elements[pos]] = new;
+        }
+        Some(new)
+    }
+}
+
+/// Refines the given partition of the states of `ts` into the coarsest partition that is stable,
+/// i.e. where any two states in the same class reach the same class on each symbol. The `i`-th
+/// entry of `initial` gives the class of `states[i]`. Missing transitions are treated as going to
+/// an additional sink state that lies in its own class.
+fn hopcroft_refinement<D: Deterministic>(
+    ts: &D,
+    states: &[D::StateIndex],
+    mut initial: Vec<usize>,
+) -> Partition<D::StateIndex> {
+    let n = states.len();
+    let sink = n;
+    let index: math::Map<_, _> = states.iter().enumerate().map(|(i, q)| (*q, i)).collect();
+    let symbols: Vec<_> = ts.symbols().collect();
+
+    // the predecessors of state `p` on the `a`-th symbol are stored at
+    // `sources[offsets[a * (n + 1) + p]..offsets[a * (n + 1) + p + 1]]`
+    let mut targets = Vec::with_capacity(symbols.len() * (n + 1));
+    for sym in &symbols {
+        for q in states {
+            targets.push(
+                ts.edges_from(*q)
+                    .expect("state must exist")
+                    .find(|t| t.expression().matched_by(*sym))
+                    .map(|t| index[&t.target()])
+                    .unwrap_or(sink),
+            );
+        }
+        targets.push(sink);
+    }
+    let mut offsets = vec![0; targets.len() + 1];
+    for (i, p) in targets.iter().enumerate() {
+        offsets[(i / (n + 1)) * (n + 1) + p + 1] += 1;
+    }
+    for i in 1..offsets.len() {
+        offsets[i] += offsets[i - 1];
+    }
+    let mut fill = offsets.clone();
+    let mut sources = vec![0; targets.len()];
+    for (i, p) in targets.iter().enumerate() {
+        let slot = (i / (n + 1)) * (n + 1) + p;
+        sources[fill[slot]] = i % (n + 1);
+        fill[slot] += 1;
+    }
+
+    initial.push(initial.iter().max().map_or(0, |max| max + 1));
+    let mut partition = RefinablePartition::new(&initial);
+
+    let blocks = partition.start.len();
+    let largest = (0..blocks).max_by_key(|b| partition.size(*b));
+    let mut worklist: Vec<_> = (0..blocks).filter(|b| Some(*b) != largest).collect();
+    let mut in_worklist = vec![true; blocks];
+    if let Some(largest) = largest {
+        in_worklist[largest] = false;
+    }
+
+    let mut touched = Vec::new();
+    while let Some(splitter) = worklist.pop() {
+        in_worklist[splitter] = false;
+        let splitter: Vec<_> =
+            partition.elements[partition.start[splitter]..partition.end[splitter]].to_vec();
+
+        for a in 0..symbols.len() {
+            for p in &splitter {
+                let slot = a * (n + 1) + p;
+                for q in &sources[offsets[slot]..offsets[slot + 1]] {
+                    if partition.mark(*q) {
+                        touched.push(partition.block[*q]);
+                    }
+                }
+            }
+
+            for block in touched.drain(..) {
+                if let Some(new) = partition.split(block) {
+                    if in_worklist[block] || partition.size(new) <= partition.size(block) {
+                        in_worklist.push(true);
+                        worklist.push(new);
+                    } else {
+                        in_worklist.push(false);
+                    }
+                    if !in_worklist[block] && partition.size(new) > partition.size(block) {
+                        in_worklist[block] = true;
+                        worklist.push(block);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut classes: Vec<BTreeSet<_>> = (0..partition.start.len())
+        .map(|b| {
+            partition.elements[partition.start[b]..partition.end[b]]
+                .iter()
+                .filter(|q| **q != sink)
+                .map(|q| states[*q])
+                .collect()
+        })
+        .filter(|class: &BTreeSet<_>| !class.is_empty())
+        .collect();
+    classes.sort_by(|x, y| x.first().cmp(&y.first()));
+    classes.into()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::moore_partition_refinement;
+    use super::{
+        mealy_greatest_bisimulation, mealy_hopcroft_bisimulation, moore_greatest_bisimulation,
+        moore_hopcroft_bisimulation, moore_partition_refinement,
+    };
+    use crate::core::Int;
+    use crate::random::generate_random_ts_sized;
     use crate::representation::IntoTs;
     use crate::{DTS, TransitionSystem, tests::wiki_dfa};
+    use rand::{Rng, thread_rng};
 
     #[test]
     fn partition_refinement_moore() {
@@ -241,4 +506,58 @@ mod tests {
         let minimized = mm.into_mealy().minimize();
         assert_eq!(minimized.size(), 1)
     }
+
+    #[test]
+    fn hopcroft_agrees_with_naive_refinement() {
+        let dfa = wiki_dfa();
+        assert_eq!(
+            moore_hopcroft_bisimulation(&dfa),
+            moore_greatest_bisimulation(&dfa)
+        );
+
+        let partial = DTS::builder()
+            .with_state_colors([0, 0, 1, 0, 0])
+            .with_transitions([
+                (0, 'a', 0, 1),
+                (0, 'b', 1, 3),
+                (1, 'a', 0, 2),
+                (3, 'a', 0, 4),
+                (3, 'b', 1, 3),
+                (4, 'a', 0, 2),
+                (2, 'a', 0, 2),
+            ])
+            .into_dts_with_initial(0);
+        let moore = partial.clone().into_moore();
+        assert_eq!(moore_hopcroft_bisimulation(&moore).size(), 3);
+        assert_eq!(
+            moore_hopcroft_bisimulation(&moore),
+            moore_greatest_bisimulation(&moore)
+        );
+        let mealy = partial.into_mealy();
+        assert_eq!(
+            mealy_hopcroft_bisimulation(&mealy),
+            mealy_greatest_bisimulation(&mealy)
+        );
+
+        for _ in 0..50 {
+            let (ts, initial) = generate_random_ts_sized(2, 40);
+            let moore = ts
+                .clone()
+                .map_state_colors(|_| thread_rng().gen_bool(0.2))
+                .with_initial(initial)
+                .into_moore();
+            assert_eq!(
+                moore_hopcroft_bisimulation(&moore),
+                moore_greatest_bisimulation(&moore)
+            );
+            let mealy = ts
+                .map_edge_colors(|_| thread_rng().gen_range(0..2) as Int)
+                .with_initial(initial)
+                .into_mealy();
+            assert_eq!(
+                mealy_hopcroft_bisimulation(&mealy),
+                mealy_greatest_bisimulation(&mealy)
+            );
+        }
+    }
 }
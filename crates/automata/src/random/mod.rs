@@ -1,13 +1,14 @@
 #![allow(unused)]
-use crate::automaton::{DBA, DFA, DPA, MealyMachine, MooreMachine};
+use crate::automaton::{DBA, DFA, DPA, MealyMachine, MooreMachine, NBA};
 use crate::representation::IntoTs;
 use crate::ts::{DefaultIdType, Deterministic, ForAlphabet, Shrinkable, Sproutable, StateIndex};
-use crate::{DTS, TransitionSystem};
+use crate::{DTS, NTS, TransitionSystem};
 use automata_core::alphabet::{Alphabet, CharAlphabet};
 use automata_core::word::ReducedOmegaWord;
 use automata_core::{Int, Void, math, upw};
 use math::sample_continuous_bernoulli;
 use rand::{Rng, rngs::ThreadRng, thread_rng};
+use std::cell::RefCell;
 use std::cmp::min;
 use tracing::{debug, info};
 
@@ -84,9 +85,21 @@ pub fn generate_random_mealy(symbols: usize, max_color: usize, size: usize) -> M
 /// Calls [`generate_random_ts_sized`] and uses the returned transition system to build a
 /// [`MooreMachine`] with colors `0..=max_color`.
 pub fn generate_random_moore(symbols: usize, max_color: usize, size: usize) -> MooreMachine {
-    let (ts, initial) = generate_random_ts_sized(symbols, size);
+    generate_random_moore_with(&mut thread_rng(), symbols, max_color, size)
+}
+
+/// Works as [`generate_random_moore`], but draws all random choices from the given `rng`. Passing
+/// a seeded generator makes the resulting [`MooreMachine`] reproducible.
+pub fn generate_random_moore_with<R: Rng>(
+    rng: &mut R,
+    symbols: usize,
+    max_color: usize,
+    size: usize,
+) -> MooreMachine {
+    let (ts, initial) = generate_random_ts_sized_with(rng, symbols, size);
+    let rng = RefCell::new(rng);
     let mut mm = ts
-        .map_state_colors(|_| thread_rng().gen_range(0..=max_color) as Int)
+        .map_state_colors(|_| rng.borrow_mut().gen_range(0..=max_color) as Int)
         .with_initial(initial)
         .into_moore()
         .minimize()
@@ -103,6 +116,15 @@ pub fn generate_random_moore(symbols: usize, max_color: usize, size: usize) -> M
 ///
 /// Note that depending on which state is chosen as the initial state, there may be unreachable states.
 pub fn generate_random_ts_sized(symbols: usize, size: usize) -> (DTS, StateIndex<DTS>) {
+    generate_random_ts_sized_with(&mut thread_rng(), symbols, size)
+}
+
+/// Works as [`generate_random_ts_sized`], but draws the transitions from the given `rng`.
+pub fn generate_random_ts_sized_with<R: Rng>(
+    rng: &mut R,
+    symbols: usize,
+    size: usize,
+) -> (DTS, StateIndex<DTS>) {
     assert!(size > 0);
     let alphabet = CharAlphabet::of_size(symbols);
     let mut dts = DTS::for_alphabet(alphabet.clone());
@@ -111,7 +133,6 @@ pub fn generate_random_ts_sized(symbols: usize, size: usize) -> (DTS, StateIndex
         dts.add_state(Void);
     }
     // add edges
-    let mut rng = thread_rng();
     for q in dts.state_indices_vec() {
         for sym in alphabet.universe() {
             let target = rng.gen_range(0..(dts.size() as DefaultIdType));
@@ -154,6 +175,31 @@ pub fn generate_random_dpa(symbols: usize, size: usize, num_prios: Int, lambda:
         .into_dpa()
 }
 
+/// Generates a random [`NBA`] with `size` states over a [`CharAlphabet`] with `symbols` symbols.
+/// For each state `q`, symbol `a` and state `p`, an `a`-labeled edge from `q` to `p` is added
+/// with probability `density`. Each of these edges is accepting with probability `acceptance`,
+/// all states are non-accepting. State `0` is the initial state. Note that depending on
+/// `density`, some states may not be reachable.
+pub fn generate_random_nba(symbols: usize, size: usize, density: f64, acceptance: f64) -> NBA {
+    assert!(size > 0);
+    let alphabet = CharAlphabet::of_size(symbols);
+    let mut nts = NTS::for_alphabet(alphabet.clone());
+    for _ in 0..size {
+        nts.add_state(false);
+    }
+    let mut rng = thread_rng();
+    for q in 0..(size as DefaultIdType) {
+        for sym in alphabet.universe() {
+            for p in 0..(size as DefaultIdType) {
+                if rng.gen_bool(density) {
+                    nts.add_edge((q, sym, rng.gen_bool(acceptance), p));
+                }
+            }
+        }
+    }
+    NBA::from_parts(nts, 0)
+}
+
 /// Randomly draw a priority in range [0,num_prios) from continuous Bernoulli distribution
 pub fn draw_priority(num_prios: u8, lambda: f64) -> u8 {
     let r = sample_continuous_bernoulli(lambda);
@@ -0,0 +1,1177 @@
+//! Regular expressions over finite words of a [`CharAlphabet`].
+//!
+//! A [`Regex`] can be parsed from a string with the usual syntax: `|` denotes a union,
+//! juxtaposition a concatenation and the postfix operators `*`, `+` and `?` stand for the
+//! Kleene star, one or more repetitions and an optional occurrence, respectively. Parentheses
+//! group subexpressions, `()` is the empty word and `[]` the empty language. A character
+//! class such as `[a-c]` or `[^ab]` matches a single symbol, and `.` matches any symbol.
+//! Characters with a special meaning can be escaped with a backslash.
+//!
+//! Regular expressions can be turned into a [`DFA`] directly through Brzozowski derivatives
+//! (see [`Regex::to_dfa`]) or into a nondeterministic transition system with Thompson's
+//! construction (see [`Regex::thompson`]). Conversely, [`IntoDFA::to_regex`] computes a
+//! regular expression for the language of a [`DFA`] by eliminating its states.
+//!
+//! Omega-regular languages are described by an [`OmegaRegex`], which is a union of terms
+//! `UV^ω` where `U` and `V` are regular expressions. Such an expression can be translated
+//! into an [`NBA`], which in turn can be written in the HOA format.
+//!
+//! # Example
+//! ```
+//! use automata::automaton::DFA;
+//!
+//! let dfa = DFA::from_regex("(ab)*a").unwrap();
+//! assert!(dfa.accepts("aba"));
+//! assert!(!dfa.accepts("ab"));
+//! assert_eq!(dfa.to_regex().to_string(), "(ab)*a");
+//! ```
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::automaton::{DFA, IntoDFA, NBA};
+use crate::core::{
+    Void,
+    alphabet::{Alphabet, CharAlphabet},
+    math,
+};
+use crate::ts::{DefaultIdType, Deterministic, ForAlphabet, IsEdge, Sproutable};
+use crate::{DTS, NTS, Pointed, TransitionSystem};
+
+/// Errors that can occur when parsing a [`Regex`] or when building an automaton from it.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RegexError {
+    /// The input ended while another token was expected.
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    /// An unexpected character was encountered at the given position.
+    #[error("unexpected character {found:?} at position {position}")]
+    UnexpectedChar {
+        /// The position of the character in the input.
+        position: usize,
+        /// The character that was found.
+        found: char,
+    },
+    /// The group opened at the given position is never closed.
+    #[error("group opened at position {0} is not closed")]
+    UnclosedGroup(usize),
+    /// The character class opened at the given position is never closed.
+    #[error("character class opened at position {0} is not closed")]
+    UnclosedClass(usize),
+    /// A range in a character class whose lower end lies above its upper end.
+    #[error("invalid range {from:?}-{to:?} in character class")]
+    InvalidRange {
+        /// The lower end of the range.
+        from: char,
+        /// The upper end of the range.
+        to: char,
+    },
+    /// A term of an omega-regular expression ends at the given position without an omega
+    /// iteration `^ω`.
+    #[error("expected omega iteration before position {0}")]
+    MissingOmegaIteration(usize),
+    /// The expression mentions a symbol that does not belong to the given alphabet.
+    #[error("symbol {0:?} does not belong to the alphabet")]
+    SymbolNotInAlphabet(char),
+}
+
+/// A set of symbols, which matches a single symbol that belongs to the set. If the class
+/// is negated, it matches exactly the symbols that do not belong to the set.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharClass {
+    negated: bool,
+    symbols: BTreeSet<char>,
+}
+
+impl CharClass {
+    /// Creates a class that matches precisely the given symbols.
+    pub fn new<I: IntoIterator<Item = char>>(symbols: I) -> Self {
+        Self {
+            negated: false,
+            symbols: symbols.into_iter().collect(),
+        }
+    }
+
+    /// Creates a class that matches all symbols except for the given ones.
+    pub fn negated<I: IntoIterator<Item = char>>(symbols: I) -> Self {
+        Self {
+            negated: true,
+            symbols: symbols.into_iter().collect(),
+        }
+    }
+
+    /// Creates a class that matches any symbol.
+    pub fn any() -> Self {
+        Self::negated([])
+    }
+
+    /// Returns `true` if the given symbol is matched by `self`.
+    pub fn matches(&self, symbol: char) -> bool {
+        self.symbols.contains(&symbol) != self.negated
+    }
+
+    /// Returns `true` if `self` does not match any symbol.
+    pub fn is_empty(&self) -> bool {
+        !self.negated && self.symbols.is_empty()
+    }
+}
+
+impl Display for CharClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negated && self.symbols.is_empty() {
+            return write!(f, ".");
+        }
+        if !self.negated && self.symbols.len() == 1 {
+            let symbol = self.symbols.first().expect("class has one symbol");
+            return write_symbol(f, *symbol);
+        }
+        write!(f, "[{}", if self.negated { "^" } else { "" })?;
+        for symbol in &self.symbols {
+            write_symbol(f, *symbol)?;
+        }
+        write!(f, "]")
+    }
+}
+
+const SPECIAL: &str = "|*+?()[].\\^-";
+
+fn write_symbol(f: &mut std::fmt::Formatter<'_>, symbol: char) -> std::fmt::Result {
+    if SPECIAL.contains(symbol) {
+        f.write_char('\\')?;
+    }
+    f.write_char(symbol)
+}
+
+/// A regular expression over `char` symbols. The expression is kept in a normal form by
+/// the constructors [`Regex::concat`], [`Regex::union`] and [`Regex::star`]: concatenations
+/// and unions are flattened, unions are sets of alternatives and trivial subexpressions
+/// involving the empty word or the empty language are simplified away. Two expressions that
+/// are equal up to associativity, commutativity and idempotence of union are thus equal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Regex {
+    /// The empty language.
+    Empty,
+    /// The language that only contains the empty word.
+    Epsilon,
+    /// A single symbol that is matched by a [`CharClass`].
+    Class(CharClass),
+    /// The concatenation of at least two expressions.
+    Concat(Vec<Regex>),
+    /// The union of at least two expressions.
+    Union(BTreeSet<Regex>),
+    /// The Kleene star of an expression.
+    Star(Box<Regex>),
+}
+
+impl Regex {
+    /// Parses a regular expression from the given string, see the [module level
+    /// documentation](`crate::regex`) for the syntax.
+    pub fn parse(input: &str) -> Result<Self, RegexError> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let regex = parser.union()?;
+        match parser.peek() {
+            None => Ok(regex),
+            Some(found) => Err(RegexError::UnexpectedChar {
+                position: parser.pos,
+                found,
+            }),
+        }
+    }
+
+    /// Creates an expression that matches the single given symbol.
+    pub fn symbol(symbol: char) -> Self {
+        Self::class(CharClass::new([symbol]))
+    }
+
+    /// Creates an expression that matches a single symbol from the given class.
+    pub fn class(class: CharClass) -> Self {
+        if class.is_empty() {
+            Self::Empty
+        } else {
+            Self::Class(class)
+        }
+    }
+
+    /// Concatenates `self` with `other`.
+    pub fn concat(self, other: Regex) -> Self {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => Self::Empty,
+            (Self::Epsilon, regex) | (regex, Self::Epsilon) => regex,
+            (left, right) => {
+                let mut parts = left.into_concat_parts();
+                parts.extend(right.into_concat_parts());
+                Self::Concat(parts)
+            }
+        }
+    }
+
+    /// Builds the union of `self` and `other`.
+    pub fn union(self, other: Regex) -> Self {
+        match (self, other) {
+            (Self::Empty, regex) | (regex, Self::Empty) => regex,
+            (left, right) => {
+                let mut alternatives = left.into_union_parts();
+                alternatives.extend(right.into_union_parts());
+                // the empty word is redundant if another alternative already accepts it
+                if alternatives.len() > 1
+                    && alternatives
+                        .iter()
+                        .any(|alt| alt != &Self::Epsilon && alt.is_nullable())
+                {
+                    alternatives.remove(&Self::Epsilon);
+                }
+                if alternatives.len() == 1 {
+                    alternatives.pop_first().expect("there is one alternative")
+                } else {
+                    Self::Union(alternatives)
+                }
+            }
+        }
+    }
+
+    /// Builds the Kleene star of `self`.
+    pub fn star(self) -> Self {
+        match self {
+            Self::Empty | Self::Epsilon => Self::Epsilon,
+            Self::Star(regex) => Self::Star(regex),
+            regex => Self::Star(Box::new(regex)),
+        }
+    }
+
+    /// Builds an expression that matches one or more repetitions of `self`.
+    pub fn plus(self) -> Self {
+        self.clone().concat(self.star())
+    }
+
+    /// Builds an expression that matches `self` or the empty word.
+    pub fn optional(self) -> Self {
+        self.union(Self::Epsilon)
+    }
+
+    fn into_concat_parts(self) -> Vec<Regex> {
+        match self {
+            Self::Concat(parts) => parts,
+            regex => vec![regex],
+        }
+    }
+
+    fn into_union_parts(self) -> BTreeSet<Regex> {
+        match self {
+            Self::Union(alternatives) => alternatives,
+            regex => BTreeSet::from([regex]),
+        }
+    }
+
+    /// Returns `true` if the empty word matches `self`.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Self::Empty | Self::Class(_) => false,
+            Self::Epsilon | Self::Star(_) => true,
+            Self::Concat(parts) => parts.iter().all(Self::is_nullable),
+            Self::Union(alternatives) => alternatives.iter().any(Self::is_nullable),
+        }
+    }
+
+    /// Computes the Brzozowski derivative of `self` with respect to the given symbol, which
+    /// matches a word `w` if and only if `self` matches `symbol` followed by `w`.
+    pub fn derivative(&self, symbol: char) -> Self {
+        match self {
+            Self::Empty | Self::Epsilon => Self::Empty,
+            Self::Class(class) => {
+                if class.matches(symbol) {
+                    Self::Epsilon
+                } else {
+                    Self::Empty
+                }
+            }
+            Self::Concat(parts) => {
+                let (first, rest) = parts.split_first().expect("concatenation is not empty");
+                let rest = rest
+                    .iter()
+                    .cloned()
+                    .fold(Self::Epsilon, |acc, part| acc.concat(part));
+                let derivative = first.derivative(symbol).concat(rest.clone());
+                if first.is_nullable() {
+                    derivative.union(rest.derivative(symbol))
+                } else {
+                    derivative
+                }
+            }
+            Self::Union(alternatives) => alternatives
+                .iter()
+                .fold(Self::Empty, |acc, alt| acc.union(alt.derivative(symbol))),
+            Self::Star(regex) => regex.derivative(symbol).concat(self.clone()),
+        }
+    }
+
+    /// Returns `true` if `self` matches the given word.
+    pub fn matches<I: IntoIterator<Item = char>>(&self, word: I) -> bool {
+        word.into_iter()
+            .fold(self.clone(), |regex, symbol| regex.derivative(symbol))
+            .is_nullable()
+    }
+
+    /// Returns the set of symbols that are explicitly mentioned in `self`.
+    pub fn symbols(&self) -> BTreeSet<char> {
+        match self {
+            Self::Empty | Self::Epsilon => BTreeSet::new(),
+            Self::Class(class) => class.symbols.clone(),
+            Self::Concat(parts) => parts.iter().flat_map(Self::symbols).collect(),
+            Self::Union(alternatives) => alternatives.iter().flat_map(Self::symbols).collect(),
+            Self::Star(regex) => regex.symbols(),
+        }
+    }
+
+    /// Returns the [`CharAlphabet`] consisting of the symbols mentioned in `self`.
+    pub fn alphabet(&self) -> CharAlphabet {
+        self.symbols().into_iter().collect()
+    }
+
+    /// Builds a [`DFA`] over the given alphabet that accepts the language of `self`. The
+    /// states of the automaton correspond to the derivatives of `self`, which are identified
+    /// up to the normal form that is maintained by the constructors. The resulting automaton
+    /// is complete but not necessarily minimal.
+    pub fn to_dfa(&self, alphabet: &CharAlphabet) -> DFA {
+        let mut dts: DTS<CharAlphabet, bool, Void> = DTS::for_alphabet(alphabet.clone());
+        let mut states: math::Map<Regex, DefaultIdType> = math::Map::default();
+        let mut queue = VecDeque::new();
+
+        let initial = dts.add_state(self.is_nullable());
+        states.insert(self.clone(), initial);
+        queue.push_back(self.clone());
+
+        while let Some(regex) = queue.pop_front() {
+            let source = states[&regex];
+            for symbol in alphabet.universe() {
+                let derivative = regex.derivative(symbol);
+                let target = match states.get(&derivative) {
+                    Some(target) => *target,
+                    None => {
+                        let target = dts.add_state(derivative.is_nullable());
+                        states.insert(derivative.clone(), target);
+                        queue.push_back(derivative);
+                        target
+                    }
+                };
+                dts.add_edge((source, symbol, target));
+            }
+        }
+
+        DFA::from_parts(dts, initial)
+    }
+
+    /// Uses Thompson's construction to build a nondeterministic transition system over the
+    /// given alphabet that accepts the language of `self`, where a state is accepting if it
+    /// is colored with `true`. The returned state index is the initial state.
+    ///
+    /// The transitions on the empty word that Thompson's construction introduces are
+    /// eliminated afterwards, so only the initial state and the targets of transitions
+    /// on symbols are kept.
+    pub fn thompson(&self, alphabet: &CharAlphabet) -> (NTS<CharAlphabet, bool>, DefaultIdType) {
+        let mut thompson = Thompson::default();
+        let (start, end) = thompson.build(self, alphabet);
+
+        let mut nts = NTS::for_alphabet(alphabet.clone());
+        let mut indices = math::Map::default();
+        let mut queue = VecDeque::from([start]);
+        indices.insert(start, nts.add_state(thompson.closure(start).contains(&end)));
+
+        while let Some(q) = queue.pop_front() {
+            let source = indices[&q];
+            for p in thompson.closure(q) {
+                for &(symbol, r) in &thompson.transitions[p] {
+                    let target = match indices.get(&r) {
+                        Some(target) => *target,
+                        None => {
+                            let target = nts.add_state(thompson.closure(r).contains(&end));
+                            indices.insert(r, target);
+                            queue.push_back(r);
+                            target
+                        }
+                    };
+                    nts.add_edge((source, symbol, target));
+                }
+            }
+        }
+
+        (nts, indices[&start])
+    }
+}
+
+impl FromStr for Regex {
+    type Err = RegexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Regex {
+    fn fmt_with_precedence(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        precedence: u8,
+    ) -> std::fmt::Result {
+        let (own, parenthesize) = match self {
+            Self::Union(_) => (0, precedence > 0),
+            Self::Concat(_) => (1, precedence > 1),
+            _ => (2, false),
+        };
+        if parenthesize {
+            write!(f, "(")?;
+        }
+        match self {
+            Self::Empty => write!(f, "[]")?,
+            Self::Epsilon => write!(f, "()")?,
+            Self::Class(class) => write!(f, "{class}")?,
+            Self::Concat(parts) => {
+                for part in parts {
+                    part.fmt_with_precedence(f, own)?;
+                }
+            }
+            Self::Union(alternatives) => {
+                for (i, alt) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    alt.fmt_with_precedence(f, own + 1)?;
+                }
+            }
+            Self::Star(regex) => {
+                regex.fmt_with_precedence(f, 3)?;
+                write!(f, "*")?;
+            }
+        }
+        if parenthesize {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Regex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_precedence(f, 0)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn union(&mut self) -> Result<Regex, RegexError> {
+        let mut regex = self.concat()?;
+        while self.eat('|') {
+            regex = regex.union(self.concat()?);
+        }
+        Ok(regex)
+    }
+
+    fn concat(&mut self) -> Result<Regex, RegexError> {
+        let mut regex = Regex::Epsilon;
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            regex = regex.concat(self.postfix()?);
+        }
+        Ok(regex)
+    }
+
+    fn postfix(&mut self) -> Result<Regex, RegexError> {
+        let mut regex = self.atom()?;
+        loop {
+            if self.eat('*') {
+                regex = regex.star();
+            } else if self.eat('+') {
+                regex = regex.plus();
+            } else if self.eat('?') {
+                regex = regex.optional();
+            } else {
+                return Ok(regex);
+            }
+        }
+    }
+
+    fn eat_omega(&mut self) -> bool {
+        if self.peek() == Some('^') && matches!(self.chars.get(self.pos + 1), Some('ω' | 'w')) {
+            self.pos += 2;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn omega_union(&mut self) -> Result<Vec<(Regex, Regex)>, RegexError> {
+        let mut terms = vec![self.omega_term()?];
+        while self.eat('|') {
+            terms.push(self.omega_term()?);
+        }
+        Ok(terms)
+    }
+
+    fn omega_term(&mut self) -> Result<(Regex, Regex), RegexError> {
+        let mut prefix = Regex::Epsilon;
+        loop {
+            if matches!(self.peek(), None | Some('|')) {
+                return Err(RegexError::MissingOmegaIteration(self.pos));
+            }
+            let regex = self.postfix()?;
+            if self.eat_omega() {
+                return Ok((prefix, regex));
+            }
+            prefix = prefix.concat(regex);
+        }
+    }
+
+    fn atom(&mut self) -> Result<Regex, RegexError> {
+        let position = self.pos;
+        match self.next().ok_or(RegexError::UnexpectedEnd)? {
+            '(' => {
+                let regex = self.union()?;
+                if !self.eat(')') {
+                    return Err(RegexError::UnclosedGroup(position));
+                }
+                Ok(regex)
+            }
+            '[' => self.class(position),
+            '.' => Ok(Regex::class(CharClass::any())),
+            '\\' => Ok(Regex::symbol(self.next().ok_or(RegexError::UnexpectedEnd)?)),
+            found @ ('*' | '+' | '?' | ')' | ']') => {
+                Err(RegexError::UnexpectedChar { position, found })
+            }
+            symbol => Ok(Regex::symbol(symbol)),
+        }
+    }
+
+    fn class(&mut self, start: usize) -> Result<Regex, RegexError> {
+        let negated = self.eat('^');
+        let mut symbols = BTreeSet::new();
+        loop {
+            let from = match self.next().ok_or(RegexError::UnclosedClass(start))? {
+                ']' => break,
+                '\\' => self.next().ok_or(RegexError::UnclosedClass(start))?,
+                symbol => symbol,
+            };
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|next| *next != ']');
+            if !is_range {
+                symbols.insert(from);
+                continue;
+            }
+            self.pos += 1;
+            let to = match self.next().ok_or(RegexError::UnclosedClass(start))? {
+                '\\' => self.next().ok_or(RegexError::UnclosedClass(start))?,
+                symbol => symbol,
+            };
+            if to < from {
+                return Err(RegexError::InvalidRange { from, to });
+            }
+            symbols.extend(from..=to);
+        }
+        Ok(Regex::class(CharClass { negated, symbols }))
+    }
+}
+
+/// Thompson's construction with explicit transitions on the empty word.
+#[derive(Default)]
+struct Thompson {
+    epsilon: Vec<Vec<usize>>,
+    transitions: Vec<Vec<(char, usize)>>,
+}
+
+impl Thompson {
+    fn add_state(&mut self) -> usize {
+        self.epsilon.push(vec![]);
+        self.transitions.push(vec![]);
+        self.epsilon.len() - 1
+    }
+
+    fn build(&mut self, regex: &Regex, alphabet: &CharAlphabet) -> (usize, usize) {
+        let start = self.add_state();
+        let end = self.add_state();
+        match regex {
+            Regex::Empty => {}
+            Regex::Epsilon => self.epsilon[start].push(end),
+            Regex::Class(class) => {
+                for symbol in alphabet.universe().filter(|sym| class.matches(*sym)) {
+                    self.transitions[start].push((symbol, end));
+                }
+            }
+            Regex::Concat(parts) => {
+                let mut last = start;
+                for part in parts {
+                    let (s, e) = self.build(part, alphabet);
+                    self.epsilon[last].push(s);
+                    last = e;
+                }
+                self.epsilon[last].push(end);
+            }
+            Regex::Union(alternatives) => {
+                for alt in alternatives {
+                    let (s, e) = self.build(alt, alphabet);
+                    self.epsilon[start].push(s);
+                    self.epsilon[e].push(end);
+                }
+            }
+            Regex::Star(inner) => {
+                let (s, e) = self.build(inner, alphabet);
+                self.epsilon[start].extend([s, end]);
+                self.epsilon[e].extend([s, end]);
+            }
+        }
+        (start, end)
+    }
+
+    fn closure(&self, state: usize) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::from([state]);
+        let mut stack = vec![state];
+        while let Some(q) = stack.pop() {
+            for &p in &self.epsilon[q] {
+                if closure.insert(p) {
+                    stack.push(p);
+                }
+            }
+        }
+        closure
+    }
+}
+
+/// An omega-regular expression, which is a union of terms of the form `UV^ω`. Here, `U` and
+/// `V` are [`Regex`]es and `V^ω` denotes the infinite concatenations of non-empty words
+/// matched by `V`.
+///
+/// In the textual syntax, terms are separated by `|` and the omega iteration is written as
+/// `^ω` or `^w`, applied to a single atom. A prefix that is a union thus has to be
+/// parenthesized, as in `(a|b)*(ab)^ω|b^ω`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OmegaRegex {
+    terms: Vec<(Regex, Regex)>,
+}
+
+impl OmegaRegex {
+    /// Creates an expression for the union of the given terms, where each term consists of a
+    /// prefix and a period.
+    pub fn new<I: IntoIterator<Item = (Regex, Regex)>>(terms: I) -> Self {
+        Self {
+            terms: terms.into_iter().collect(),
+        }
+    }
+
+    /// Parses an omega-regular expression from the given string.
+    pub fn parse(input: &str) -> Result<Self, RegexError> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let terms = parser.omega_union()?;
+        match parser.peek() {
+            None => Ok(Self { terms }),
+            Some(found) => Err(RegexError::UnexpectedChar {
+                position: parser.pos,
+                found,
+            }),
+        }
+    }
+
+    /// Gives an iterator over the terms of `self`, each consisting of a prefix and a period.
+    pub fn terms(&self) -> impl Iterator<Item = &(Regex, Regex)> {
+        self.terms.iter()
+    }
+
+    /// Returns the set of symbols that are explicitly mentioned in `self`.
+    pub fn symbols(&self) -> BTreeSet<char> {
+        self.terms
+            .iter()
+            .flat_map(|(prefix, period)| prefix.symbols().into_iter().chain(period.symbols()))
+            .collect()
+    }
+
+    /// Returns the [`CharAlphabet`] consisting of the symbols mentioned in `self`.
+    pub fn alphabet(&self) -> CharAlphabet {
+        self.symbols().into_iter().collect()
+    }
+
+    /// Builds an [`NBA`] over the given alphabet that accepts the language of `self`.
+    ///
+    /// For every term `UV^ω`, the transition systems obtained from `U` and `V` by
+    /// [`Regex::thompson`] are combined. The initial state of the system for `V` becomes an
+    /// accepting hub, which is entered whenever `U` or an iteration of `V` is completed. All
+    /// terms share the initial state of the resulting automaton, which is possible because
+    /// Thompson's construction never leads back into the initial state.
+    pub fn to_nba(&self, alphabet: &CharAlphabet) -> NBA {
+        let mut nts: NTS<CharAlphabet, bool, bool> = NTS::for_alphabet(alphabet.clone());
+        let initial = nts.add_state(false);
+
+        for (prefix, period) in &self.terms {
+            let (u, u0) = prefix.thompson(alphabet);
+            let (v, v0) = period.thompson(alphabet);
+            let is_final = |ts: &NTS<CharAlphabet, bool>, q| ts.state_color(q) == Some(true);
+
+            let hub = nts.add_state(true);
+            let v_states: math::Map<_, _> = v
+                .state_indices()
+                .map(|q| (q, if q == v0 { hub } else { nts.add_state(false) }))
+                .collect();
+            let u_states: math::Map<_, _> = u
+                .state_indices()
+                .map(|q| {
+                    (
+                        q,
+                        if q == u0 {
+                            initial
+                        } else {
+                            nts.add_state(false)
+                        },
+                    )
+                })
+                .collect();
+
+            let mut hub_edges = vec![];
+            for q in v.state_indices() {
+                for edge in v.edges_from(q).expect("state must exist") {
+                    let symbol = *edge.expression();
+                    let mut targets = vec![v_states[&edge.target()]];
+                    if is_final(&v, edge.target()) {
+                        targets.push(hub);
+                    }
+                    for target in targets {
+                        nts.add_edge((v_states[&q], symbol, false, target));
+                        if q == v0 {
+                            hub_edges.push((symbol, target));
+                        }
+                    }
+                }
+            }
+
+            for q in u.state_indices() {
+                for edge in u.edges_from(q).expect("state must exist") {
+                    let symbol = *edge.expression();
+                    nts.add_edge((u_states[&q], symbol, false, u_states[&edge.target()]));
+                    if is_final(&u, edge.target()) {
+                        nts.add_edge((u_states[&q], symbol, false, hub));
+                    }
+                }
+            }
+            if is_final(&u, u0) {
+                for (symbol, target) in hub_edges {
+                    nts.add_edge((initial, symbol, false, target));
+                }
+            }
+        }
+
+        NBA::from_parts(nts, initial)
+    }
+}
+
+impl FromStr for OmegaRegex {
+    type Err = RegexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for OmegaRegex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "[]^ω");
+        }
+        for (i, (prefix, period)) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, "|")?;
+            }
+            if prefix != &Regex::Epsilon {
+                prefix.fmt_with_precedence(f, 1)?;
+            }
+            period.fmt_with_precedence(f, 3)?;
+            write!(f, "^ω")?;
+        }
+        Ok(())
+    }
+}
+
+impl NBA {
+    /// Parses the given omega-regular expression and builds an [`NBA`] for it, see
+    /// [`OmegaRegex::to_nba`]. The alphabet consists of the symbols that appear in the
+    /// expression.
+    pub fn from_omega_regex(regex: &str) -> Result<Self, RegexError> {
+        let regex = OmegaRegex::parse(regex)?;
+        Ok(regex.to_nba(&regex.alphabet()))
+    }
+
+    /// Works like [`NBA::from_omega_regex`], but builds the automaton over the given
+    /// alphabet. Fails if the expression mentions a symbol that does not belong to the
+    /// alphabet.
+    pub fn from_omega_regex_over(regex: &str, alphabet: &CharAlphabet) -> Result<Self, RegexError> {
+        let regex = OmegaRegex::parse(regex)?;
+        if let Some(symbol) = regex
+            .symbols()
+            .into_iter()
+            .find(|symbol| !alphabet.contains(*symbol))
+        {
+            return Err(RegexError::SymbolNotInAlphabet(symbol));
+        }
+        Ok(regex.to_nba(alphabet))
+    }
+}
+
+impl DFA {
+    /// Parses the given regular expression and builds a [`DFA`] for it, see
+    /// [`Regex::to_dfa`]. The alphabet consists of the symbols that appear in the
+    /// expression.
+    pub fn from_regex(regex: &str) -> Result<Self, RegexError> {
+        let regex = Regex::parse(regex)?;
+        Ok(regex.to_dfa(&regex.alphabet()))
+    }
+
+    /// Works like [`DFA::from_regex`], but builds the automaton over the given alphabet.
+    /// Fails if the expression mentions a symbol that does not belong to the alphabet.
+    pub fn from_regex_over(regex: &str, alphabet: &CharAlphabet) -> Result<Self, RegexError> {
+        let regex = Regex::parse(regex)?;
+        if let Some(symbol) = regex
+            .symbols()
+            .into_iter()
+            .find(|symbol| !alphabet.contains(*symbol))
+        {
+            return Err(RegexError::SymbolNotInAlphabet(symbol));
+        }
+        Ok(regex.to_dfa(alphabet))
+    }
+}
+
+impl<D> IntoDFA<D>
+where
+    D: Deterministic<Alphabet = CharAlphabet, StateColor = bool>,
+{
+    /// Computes a [`Regex`] for the language accepted by `self` through state elimination.
+    /// States that are not reachable or from which no accepting state can be reached are
+    /// ignored, and the remaining states are eliminated one after another, always picking a
+    /// state with few incoming and outgoing transitions.
+    pub fn to_regex(&self) -> Regex {
+        let states: Vec<_> = self.reachable_state_indices().collect();
+        let index: math::Map<_, usize> = states.iter().enumerate().map(|(i, q)| (*q, i)).collect();
+        let (start, end) = (states.len(), states.len() + 1);
+
+        let mut graph = EliminationGraph::new(states.len() + 2);
+        graph.add(start, index[&self.initial()], Regex::Epsilon);
+        for (i, &q) in states.iter().enumerate() {
+            if self.state_color(q) == Some(true) {
+                graph.add(i, end, Regex::Epsilon);
+            }
+            for edge in self.edges_from(q).expect("state must exist") {
+                graph.add(i, index[&edge.target()], Regex::symbol(*edge.expression()));
+            }
+        }
+
+        let productive = graph.reaching(end);
+        let mut remaining: BTreeSet<usize> = (0..states.len())
+            .filter(|q| productive.contains(q))
+            .collect();
+        while let Some(k) = remaining
+            .iter()
+            .min_by_key(|k| graph.incoming[**k].len() * graph.outgoing[**k].len())
+            .copied()
+        {
+            remaining.remove(&k);
+            graph.eliminate(k, &productive);
+        }
+
+        graph.outgoing[start].remove(&end).unwrap_or(Regex::Empty)
+    }
+}
+
+/// A graph whose edges are labeled with regular expressions, from which states can be
+/// eliminated while preserving the language between the remaining states.
+struct EliminationGraph {
+    outgoing: Vec<math::OrderedMap<usize, Regex>>,
+    incoming: Vec<BTreeSet<usize>>,
+}
+
+impl EliminationGraph {
+    fn new(size: usize) -> Self {
+        Self {
+            outgoing: vec![math::OrderedMap::new(); size],
+            incoming: vec![BTreeSet::new(); size],
+        }
+    }
+
+    fn add(&mut self, source: usize, target: usize, regex: Regex) {
+        let entry = self.outgoing[source].entry(target).or_insert(Regex::Empty);
+        *entry = std::mem::replace(entry, Regex::Empty).union(regex);
+        self.incoming[target].insert(source);
+    }
+
+    /// Returns the set of states from which `target` can be reached.
+    fn reaching(&self, target: usize) -> BTreeSet<usize> {
+        let mut reaching = BTreeSet::from([target]);
+        let mut stack = vec![target];
+        while let Some(q) = stack.pop() {
+            for &p in &self.incoming[q] {
+                if reaching.insert(p) {
+                    stack.push(p);
+                }
+            }
+        }
+        reaching
+    }
+
+    /// Removes `state` and connects each of its predecessors to each of its successors that
+    /// lies in `keep`.
+    fn eliminate(&mut self, state: usize, keep: &BTreeSet<usize>) {
+        let repeat = self.outgoing[state]
+            .remove(&state)
+            .map(Regex::star)
+            .unwrap_or(Regex::Epsilon);
+        self.incoming[state].remove(&state);
+
+        let targets: Vec<_> = std::mem::take(&mut self.outgoing[state])
+            .into_iter()
+            .collect();
+        for (target, _) in &targets {
+            self.incoming[*target].remove(&state);
+        }
+        for source in std::mem::take(&mut self.incoming[state]) {
+            let Some(into) = self.outgoing[source].remove(&state) else {
+                continue;
+            };
+            let into = into.concat(repeat.clone());
+            for (target, out) in targets.iter().filter(|(target, _)| keep.contains(target)) {
+                self.add(source, *target, into.clone().concat(out.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharClass, OmegaRegex, Regex, RegexError};
+    use crate::TransitionSystem;
+    use crate::automaton::{DFA, NBA};
+    use crate::core::{
+        alphabet::{Alphabet, CharAlphabet},
+        upw,
+    };
+    use crate::hoa::input::hoa_to_ts;
+    use crate::random::generate_random_ts_sized;
+    use crate::representation::CollectTs;
+    use rand::{Rng, thread_rng};
+
+    #[test]
+    fn regex_parsing() {
+        let regex = Regex::parse("(ab)*a").unwrap();
+        assert_eq!(
+            regex,
+            Regex::symbol('a')
+                .concat(Regex::symbol('b'))
+                .star()
+                .concat(Regex::symbol('a'))
+        );
+        assert_eq!(regex.to_string(), "(ab)*a");
+
+        for (input, printed) in [
+            ("a|b|a", "a|b"),
+            ("[a-c]+", "[abc][abc]*"),
+            ("a?b", "(()|a)b"),
+            ("(a*)*|()", "a*"),
+            ("[^ab]\\*.", "[^ab]\\*."),
+            ("[]a|b", "b"),
+            ("", "()"),
+        ] {
+            let regex: Regex = input.parse().unwrap();
+            assert_eq!(regex.to_string(), printed);
+            assert_eq!(Regex::parse(printed).unwrap(), regex);
+        }
+
+        assert_eq!(Regex::parse("(ab"), Err(RegexError::UnclosedGroup(0)));
+        assert_eq!(Regex::parse("a[bc"), Err(RegexError::UnclosedClass(1)));
+        assert_eq!(
+            Regex::parse("ab)"),
+            Err(RegexError::UnexpectedChar {
+                position: 2,
+                found: ')'
+            })
+        );
+        assert_eq!(
+            Regex::parse("*a"),
+            Err(RegexError::UnexpectedChar {
+                position: 0,
+                found: '*'
+            })
+        );
+        assert_eq!(
+            Regex::parse("[c-a]"),
+            Err(RegexError::InvalidRange { from: 'c', to: 'a' })
+        );
+        assert_eq!(Regex::parse("a\\"), Err(RegexError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn regex_to_dfa() {
+        let dfa = DFA::from_regex("(ab)*a").unwrap();
+        assert_eq!(Alphabet::size(dfa.alphabet()), 2);
+        for (word, expected) in [("a", true), ("aba", true), ("", false), ("ab", false)] {
+            assert_eq!(dfa.accepts(word), expected, "{word}");
+        }
+        assert_eq!(dfa.minimize().size(), 3);
+
+        let alphabet = CharAlphabet::of_size(3);
+        let dfa = DFA::from_regex_over("[^a].*", &alphabet).unwrap();
+        assert!(dfa.accepts("cab"));
+        assert!(!dfa.accepts("abc"));
+        assert_eq!(
+            DFA::from_regex_over("ad", &alphabet).err(),
+            Some(RegexError::SymbolNotInAlphabet('d'))
+        );
+
+        let class = Regex::class(CharClass::negated(['b']));
+        assert!(class.matches("c".chars()));
+        assert!(!class.matches("b".chars()));
+    }
+
+    #[test]
+    fn thompson_agrees_with_derivatives() {
+        let alphabet = CharAlphabet::of_size(3);
+        for input in [
+            "(ab)*a",
+            "(a|b)*abb",
+            "[ab]+c?|()",
+            "((a|ba)*c)*b",
+            ".*a.",
+            "[]",
+            "a**b*|c+",
+        ] {
+            let regex = Regex::parse(input).unwrap();
+            let dfa = regex.to_dfa(&alphabet);
+            let (nts, initial) = regex.thompson(&alphabet);
+            let determinized = nts
+                .subset_construction_from([initial])
+                .map_state_colors(|colors| colors.contains(&true))
+                .collect_dfa();
+            assert!(determinized.equivalent(&dfa), "mismatch on {input}");
+        }
+    }
+
+    #[test]
+    fn dfa_to_regex() {
+        let dfa = crate::tests::wiki_dfa();
+        let regex = dfa.to_regex();
+        assert!(regex.to_dfa(&dfa.alphabet().clone()).equivalent(&dfa));
+
+        let empty = DFA::from_regex_over("[]", &CharAlphabet::of_size(2)).unwrap();
+        assert_eq!(empty.to_regex(), Regex::Empty);
+
+        for _ in 0..20 {
+            let (ts, initial) = generate_random_ts_sized(2, 6);
+            let dfa = ts
+                .map_state_colors(|_| thread_rng().gen_bool(0.3))
+                .with_initial(initial)
+                .collect_dfa();
+            let regex = dfa.to_regex();
+            assert!(
+                regex.to_dfa(&CharAlphabet::of_size(2)).equivalent(&dfa),
+                "{regex} does not match the dfa"
+            );
+            assert_eq!(Regex::parse(&regex.to_string()).unwrap(), regex);
+        }
+    }
+
+    #[test]
+    fn omega_regex_parsing() {
+        let regex: OmegaRegex = "(a|b)*(ab)^w|b^ω".parse().unwrap();
+        assert_eq!(regex.terms().count(), 2);
+        assert_eq!(regex.to_string(), "(a|b)*(ab)^ω|b^ω");
+        assert_eq!(OmegaRegex::parse(&regex.to_string()).unwrap(), regex);
+        assert_eq!(regex.alphabet(), CharAlphabet::of_size(2));
+
+        assert_eq!(
+            OmegaRegex::parse("a*b"),
+            Err(RegexError::MissingOmegaIteration(3))
+        );
+        assert_eq!(
+            OmegaRegex::parse("a^ω|"),
+            Err(RegexError::MissingOmegaIteration(4))
+        );
+        assert_eq!(
+            OmegaRegex::parse("a^ωb"),
+            Err(RegexError::UnexpectedChar {
+                position: 3,
+                found: 'b'
+            })
+        );
+    }
+
+    #[test]
+    fn omega_regex_to_nba() {
+        let nba = NBA::from_omega_regex("(a|b)*(ab)^ω|b^ω").unwrap();
+        assert!(nba.accepts(upw!("ab")));
+        assert!(nba.accepts(upw!("bba", "ba")));
+        assert!(nba.accepts(upw!("b")));
+        assert!(!nba.accepts(upw!("aab", "b")));
+        assert!(!nba.accepts(upw!("a")));
+        assert!(!nba.accepts(upw!("abb")));
+
+        let nba = NBA::from_omega_regex("a?(b*c)^ω").unwrap();
+        assert!(nba.accepts(upw!("c")));
+        assert!(nba.accepts(upw!("a", "bbc")));
+        assert!(nba.accepts(upw!("a", "bc")));
+        assert!(!nba.accepts(upw!("c", "b")));
+        assert!(!nba.accepts(upw!("aa", "c")));
+
+        let alphabet = CharAlphabet::of_size(2);
+        let nba = NBA::from_omega_regex_over("a*(()|[])^ω", &alphabet).unwrap();
+        assert!(nba.is_empty());
+        assert_eq!(
+            NBA::from_omega_regex_over("c^ω", &alphabet).err(),
+            Some(RegexError::SymbolNotInAlphabet('c'))
+        );
+    }
+
+    #[test]
+    fn omega_regex_to_hoa() {
+        let nba = NBA::from_omega_regex("a^ω").unwrap();
+        assert_eq!(
+            nba.to_hoa(),
+            "HOA: v1\nAP: 1 \"a\"\nStates: 3\nStart: 0\nacc-name: Buchi\nAcceptance: 1 Inf(0)\nproperties: trans-labels explicit-labels trans-acc\n--BODY--\nState: 0\n[0] 1\n[0] 2\nState: 1\n[0] 1 {0}\n[0] 2 {0}\nState: 2\n--END--\n"
+        );
+
+        let nba = NBA::from_omega_regex("(a|b)*(ab)^ω|c^ω").unwrap();
+        let parsed = hoa_to_ts::<false>(&nba.to_hoa())
+            .unwrap()
+            .pop()
+            .expect("contains an automaton");
+        let parsed = NBA::try_from(parsed).expect("has Büchi acceptance");
+        assert_eq!(parsed.ts().size(), nba.ts().size());
+        assert!(!parsed.is_empty());
+    }
+}
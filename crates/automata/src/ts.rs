@@ -228,11 +228,8 @@ pub trait TransitionSystem: Sized {
     /// the same state with the same symbol.
     fn is_deterministic(&self) -> bool {
         for state in self.state_indices() {
-            for (l, r) in self
-                .edges_from(state)
-                .unwrap()
-                .zip(self.edges_from(state).unwrap().skip(1))
-            {
+            let edges: Vec<_> = self.edges_from(state).unwrap().collect();
+            for (l, r) in edges.iter().tuple_combinations() {
                 if self.alphabet().overlapping(l.expression(), r.expression()) {
                     trace!(
                         "found overlapping edges from {:?}: on {} to {:?} and on {} to {:?}",
@@ -693,6 +690,10 @@ impl<Ts: TransitionSystem> TransitionSystem for &Ts {
         Ts::state_indices(self)
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        Ts::contains_state_index(self, index)
+    }
+
     fn edges_from(&self, state: StateIndex<Self>) -> Option<Self::EdgesFromIter<'_>> {
         Ts::edges_from(self, state)
     }
@@ -734,6 +735,10 @@ impl<Ts: TransitionSystem> TransitionSystem for &mut Ts {
         Ts::state_indices(self)
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        Ts::contains_state_index(self, index)
+    }
+
     fn edges_from(&self, state: StateIndex<Self>) -> Option<Self::EdgesFromIter<'_>> {
         Ts::edges_from(self, state)
     }
@@ -25,6 +25,14 @@ use tracing::{trace, warn};
 
 /// Represents a non-deterministic transition system. It stores an [`Alphabet`], a list of [`LinkedListTransitionSystemState`]s and a list of [`LinkedListTransitionSystemEdge`]s.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "A: serde::Serialize, A::Expression: serde::Serialize, Q: serde::Serialize, C: serde::Serialize",
+        deserialize = "A: serde::Deserialize<'de>, A::Expression: serde::Deserialize<'de>, Q: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+    ))
+)]
 pub struct LinkedListTransitionSystem<
     A: Alphabet = CharAlphabet,
     Q = Void,
@@ -694,6 +702,12 @@ impl<A: Alphabet, Q: Color, C: Color, const DET: bool> TransitionSystem
         LinkedStateIndices(&self.states, 0)
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        self.states
+            .get(index.into_usize())
+            .is_some_and(|q| q.is_occupied())
+    }
+
     fn edges_from(&self, state: StateIndex<Self>) -> Option<Self::EdgesFromIter<'_>> {
         Some(NTSEdgesFromIter::new(
             &self.edges,
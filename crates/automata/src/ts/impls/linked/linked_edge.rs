@@ -7,6 +7,7 @@ use std::fmt::Debug;
 /// expression, as well as a source and target state index. Moreover, it stores the indices of the
 /// next and previous edge in the list of edges leaving the source state.
 #[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinkedListTransitionSystemEdge<E, C> {
     pub(super) out_prev: Option<usize>,
     pub(super) in_prev: Option<usize>,
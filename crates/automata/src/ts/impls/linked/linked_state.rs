@@ -6,6 +6,7 @@ use crate::core::Color;
 /// Stores information characterizing a state in a non-deterministic transition system, see [`crate::NTS`].
 /// It stores a color and a pointer to the index of the first edge leaving the state.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkedListTransitionSystemState<Q> {
     Occupied(Q, Option<usize>, Option<usize>),
     Vacant(Option<usize>, Option<usize>),
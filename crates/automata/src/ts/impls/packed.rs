@@ -1,9 +1,18 @@
+use itertools::Itertools;
+
 use crate::TransitionSystem;
-use crate::core::{Color, alphabet::SimpleAlphabet};
+use crate::core::{
+    Color,
+    alphabet::{Matcher, SimpleAlphabet},
+};
 use crate::ts::predecessors::PredecessorIterable;
-use crate::ts::{Deterministic, IsEdge, StateIndex};
+use crate::ts::{
+    Deterministic, EdgeExpression, EdgeTuple, ForAlphabet, IntoEdgeTuple, IsEdge, Shrinkable,
+    Sproutable, StateColor, StateIndex,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Id(pub usize);
 
 impl std::ops::Deref for Id {
@@ -18,23 +27,212 @@ impl std::ops::DerefMut for Id {
     }
 }
 
+/// A deterministic transition system that stores its edges in a dense array with one slot
+/// for every pair of state and alphabet symbol. The edge for state `q` and the `i`-th symbol
+/// of the alphabet is stored at position `q * |alphabet| + i`, which makes lookups cheap and
+/// keeps the edges leaving a state adjacent in memory.
+///
+/// Removed states leave a vacant slot behind, their indices are not reused. For every state,
+/// the positions of its incoming edges are kept in a reverse index, which allows iterating
+/// over the [predecessors](`PredecessorIterable`) without scanning all edges.
+///
+/// When deserializing, payloads whose edges or reverse index do not fit the states and the
+/// alphabet are rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawPacked<A, Q, C>",
+        bound(
+            serialize = "A: serde::Serialize, A::Expression: serde::Serialize, Q: serde::Serialize, C: serde::Serialize",
+            deserialize = "A: serde::Deserialize<'de>, A::Expression: serde::Deserialize<'de>, Q: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+        )
+    )
+)]
 pub struct Packed<A: SimpleAlphabet, Q: Color, C: Color> {
     alphabet: A,
     states: Vec<Option<Q>>,
-    edges: Vec<PackedEdge<A, C>>,
+    edges: Vec<Option<PackedEdge<A, C>>>,
+    predecessors: Vec<Vec<usize>>,
 }
 
 impl<A: SimpleAlphabet, Q: Color, C: Color> Packed<A, Q, C> {
+    /// Creates a new [`Packed`] transition system over the given alphabet. The `states` are
+    /// given by their colors, where `None` marks a vacant state. Each of the `edges` is put
+    /// into the slot that is determined by its source and expression.
+    ///
+    /// Panics if two edges share the same source and expression, or if an edge is incident to
+    /// a state that does not exist.
     pub fn new(alphabet: A, states: Vec<Option<Q>>, edges: Vec<PackedEdge<A, C>>) -> Self {
-        Self {
+        let mut ts = Self {
+            edges: vec![None; states.len() * alphabet.size()],
+            predecessors: vec![vec![]; states.len()],
+            alphabet,
+            states,
+        };
+        for edge in edges {
+            let (q, a, c, p) = (edge.source, edge.expression, edge.color, edge.target);
+            assert!(
+                ts.add_edge((q, a, c, p)).is_none(),
+                "there may be at most one edge from {q:?} on {a:?}"
+            );
+        }
+        ts
+    }
+
+    fn slot(&self, state: Id, expression: &A::Expression) -> usize {
+        let position = self
+            .alphabet
+            .try_position(*expression)
+            .expect("expression must be part of the alphabet");
+        *state * self.alphabet.size() + position
+    }
+
+    fn slots(&self, state: Id) -> std::ops::Range<usize> {
+        let start = *state * self.alphabet.size();
+        start..start + self.alphabet.size()
+    }
+
+    fn take_edge(&mut self, slot: usize) -> Option<EdgeTuple<Self>> {
+        let edge = self.edges[slot].take()?;
+        self.predecessors[*edge.target].retain(|pos| *pos != slot);
+        Some((edge.source, edge.expression, edge.color, edge.target))
+    }
+
+    fn take_edges_where<F>(&mut self, source: Id, filter: F) -> Option<Vec<EdgeTuple<Self>>>
+    where
+        F: Fn(&PackedEdge<A, C>) -> bool,
+    {
+        if !self.contains_state_index(source) {
+            return None;
+        }
+        let slots = self
+            .slots(source)
+            .filter(|slot| self.edges[*slot].as_ref().is_some_and(&filter))
+            .collect::<Vec<_>>();
+        Some(
+            slots
+                .into_iter()
+                .filter_map(|slot| self.take_edge(slot))
+                .collect(),
+        )
+    }
+}
+
+/// The reason why the parts of a deserialized [`Packed`] transition system do not fit together.
+#[cfg(feature = "serde")]
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum InvalidPacked {
+    /// There are not exactly as many edge slots as pairs of state and symbol.
+    #[error("expected {expected} edge slots but found {found}")]
+    EdgeCount { expected: usize, found: usize },
+    /// The reverse index does not have exactly one entry per state.
+    #[error("expected {expected} predecessor lists but found {found}")]
+    PredecessorCount { expected: usize, found: usize },
+    /// The edge in the given slot does not belong there, as its source or expression differ
+    /// from those of the slot, or it is incident to a state that does not exist.
+    #[error("the edge in slot {0} is misplaced or incident to a missing state")]
+    Edge(usize),
+    /// The predecessors of the given state do not list exactly the edges leading into it.
+    #[error("the predecessors of state {0} do not match its incoming edges")]
+    Predecessors(usize),
+}
+
+/// The fields of a [`Packed`] transition system as they are serialized, which are checked
+/// for consistency before a [`Packed`] is built from them.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(
+    deserialize = "A: serde::Deserialize<'de>, A::Expression: serde::Deserialize<'de>, Q: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+))]
+pub struct RawPacked<A: SimpleAlphabet, Q: Color, C: Color> {
+    alphabet: A,
+    states: Vec<Option<Q>>,
+    edges: Vec<Option<PackedEdge<A, C>>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl<A: SimpleAlphabet, Q: Color, C: Color> TryFrom<RawPacked<A, Q, C>> for Packed<A, Q, C> {
+    type Error = InvalidPacked;
+
+    /// Checks that there is one edge slot for every pair of state and symbol, that each
+    /// edge sits in the slot of its source and expression and connects existing states, and
+    /// that the reverse index lists exactly the incoming edges of every state.
+    fn try_from(raw: RawPacked<A, Q, C>) -> Result<Self, Self::Error> {
+        let RawPacked {
             alphabet,
             states,
             edges,
+            predecessors,
+        } = raw;
+        let size = alphabet.size();
+        if edges.len() != states.len() * size {
+            return Err(InvalidPacked::EdgeCount {
+                expected: states.len() * size,
+                found: edges.len(),
+            });
+        }
+        if predecessors.len() != states.len() {
+            return Err(InvalidPacked::PredecessorCount {
+                expected: states.len(),
+                found: predecessors.len(),
+            });
+        }
+
+        let exists = |q: Id| matches!(states.get(*q), Some(Some(_)));
+        let mut incoming = vec![vec![]; states.len()];
+        for (slot, edge) in edges.iter().enumerate() {
+            let Some(edge) = edge else { continue };
+            if *edge.source != slot / size
+                || alphabet.try_position(edge.expression) != Some(slot % size)
+                || !exists(edge.source)
+                || !exists(edge.target)
+            {
+                return Err(InvalidPacked::Edge(slot));
+            }
+            incoming[*edge.target].push(slot);
         }
+        for (state, (listed, expected)) in predecessors.iter().zip(incoming).enumerate() {
+            if listed.iter().copied().sorted().collect_vec() != expected {
+                return Err(InvalidPacked::Predecessors(state));
+            }
+        }
+
+        Ok(Self {
+            alphabet,
+            states,
+            edges,
+            predecessors,
+        })
+    }
+}
+
+impl<A, Q, C, D> From<&D> for Packed<A, Q, C>
+where
+    A: SimpleAlphabet,
+    Q: Color,
+    C: Color,
+    D: Deterministic<Alphabet = A, StateColor = Q, EdgeColor = C>,
+{
+    /// Collects the states and edges of a deterministic transition system into a [`Packed`]
+    /// representation. The states are numbered in the order in which they appear in
+    /// [`TransitionSystem::state_indices`].
+    fn from(ts: &D) -> Self {
+        Self::sprout_from_ts(ts)
     }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "A::Expression: serde::Serialize, C: serde::Serialize",
+        deserialize = "A::Expression: serde::Deserialize<'de>, C: serde::Deserialize<'de>"
+    ))
+)]
 pub struct PackedEdge<A: SimpleAlphabet, C: Color> {
     source: Id,
     target: Id,
@@ -68,7 +266,7 @@ impl<A: SimpleAlphabet, Q: Color, C: Color> TransitionSystem for Packed<A, Q, C>
         Self: 'this;
 
     type EdgesFromIter<'this>
-        = std::slice::Iter<'this, PackedEdge<A, C>>
+        = std::iter::Flatten<std::slice::Iter<'this, Option<PackedEdge<A, C>>>>
     where
         Self: 'this;
 
@@ -78,12 +276,10 @@ impl<A: SimpleAlphabet, Q: Color, C: Color> TransitionSystem for Packed<A, Q, C>
         Self: 'this;
 
     fn edges_from(&self, state: StateIndex<Self>) -> Option<Self::EdgesFromIter<'_>> {
-        let Some(Some(_color)) = self.states.get(*state) else {
+        if !self.contains_state_index(state) {
             return None;
-        };
-        let start = *state * self.alphabet.size();
-        let end = start + self.alphabet.size();
-        Some(self.edges[start..end].iter())
+        }
+        Some(self.edges[self.slots(state)].iter().flatten())
     }
 
     fn alphabet(&self) -> &Self::Alphabet {
@@ -94,6 +290,10 @@ impl<A: SimpleAlphabet, Q: Color, C: Color> TransitionSystem for Packed<A, Q, C>
         PackedStateIndices::new(&self.states)
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        matches!(self.states.get(*index), Some(Some(_)))
+    }
+
     fn state_color(&self, state: StateIndex<Self>) -> Option<Self::StateColor> {
         let c = self.states.get(*state)?;
         c.clone()
@@ -109,72 +309,186 @@ impl<A: SimpleAlphabet, Q: Color, C: Color> PredecessorIterable for Packed<A, Q,
         Self: 'this;
 
     type EdgesToIter<'this>
-        =
-        std::iter::Filter<std::slice::Iter<'this, PackedEdge<A, C>>, fn(&&PackedEdge<A, C>) -> bool>
+        = PackedEdgesTo<'this, A, C>
     where
         Self: 'this;
 
     fn predecessors(&self, state: StateIndex<Self>) -> Option<Self::EdgesToIter<'_>> {
-        todo!()
+        if !self.contains_state_index(state) {
+            return None;
+        }
+        Some(PackedEdgesTo::new(
+            &self.edges,
+            self.predecessors[*state].iter(),
+        ))
     }
 }
 
-pub struct PackedEdgesFrom<'a, A: SimpleAlphabet, C: Color> {
-    array: &'a [PackedEdge<A, C>],
-    end: usize,
-    pos: usize,
+impl<A: SimpleAlphabet, Q: Color, C: Color> ForAlphabet<A> for Packed<A, Q, C> {
+    fn for_alphabet(from: A) -> Self {
+        Self::new(from, vec![], vec![])
+    }
+
+    fn for_alphabet_size_hint(from: A, size_hint: usize) -> Self {
+        Self {
+            edges: Vec::with_capacity(size_hint * from.size()),
+            predecessors: Vec::with_capacity(size_hint),
+            states: Vec::with_capacity(size_hint),
+            alphabet: from,
+        }
+    }
 }
 
-impl<'a, A: SimpleAlphabet, C: Color> Iterator for PackedEdgesFrom<'a, A, C> {
+impl<A: SimpleAlphabet, Q: Color, C: Color> Sproutable for Packed<A, Q, C> {
+    fn add_state(&mut self, color: StateColor<Self>) -> Self::StateIndex {
+        let id = Id(self.states.len());
+        self.states.push(Some(color));
+        self.predecessors.push(vec![]);
+        self.edges
+            .extend(std::iter::repeat_n(None, self.alphabet.size()));
+        id
+    }
+
+    fn add_edge<E>(&mut self, t: E) -> Option<EdgeTuple<Self>>
+    where
+        E: IntoEdgeTuple<Self>,
+    {
+        let (q, a, c, p) = t.into_edge_tuple();
+        assert!(
+            self.contains_state_index(q),
+            "cannot add edge from non-existent state {q:?}"
+        );
+        assert!(
+            self.contains_state_index(p),
+            "cannot add edge to non-existent state {p:?}"
+        );
+
+        let slot = self.slot(q, &a);
+        let out = self.take_edge(slot);
+        self.edges[slot] = Some(PackedEdge::new(q, a, c, p));
+        self.predecessors[*p].push(slot);
+        out
+    }
+
+    fn set_state_color(&mut self, index: StateIndex<Self>, color: StateColor<Self>) {
+        assert!(
+            self.contains_state_index(index),
+            "cannot set color of non-existent state {index:?}"
+        );
+        self.states[*index] = Some(color);
+    }
+}
+
+impl<A: SimpleAlphabet, Q: Color, C: Color> Shrinkable for Packed<A, Q, C> {
+    fn remove_state(&mut self, state: StateIndex<Self>) -> Option<Self::StateColor> {
+        if !self.contains_state_index(state) {
+            return None;
+        }
+        self.remove_edges_from(state);
+        self.remove_edges_to(state);
+        self.states[*state].take()
+    }
+
+    fn remove_edges_from_matching(
+        &mut self,
+        source: StateIndex<Self>,
+        matcher: impl Matcher<EdgeExpression<Self>>,
+    ) -> Option<Vec<EdgeTuple<Self>>> {
+        self.take_edges_where(source, |edge| matcher.matches(&edge.expression))
+    }
+
+    fn remove_edges_between_matching(
+        &mut self,
+        source: StateIndex<Self>,
+        target: StateIndex<Self>,
+        matcher: impl Matcher<EdgeExpression<Self>>,
+    ) -> Option<Vec<EdgeTuple<Self>>> {
+        if !self.contains_state_index(target) {
+            return None;
+        }
+        self.take_edges_where(source, |edge| {
+            edge.target == target && matcher.matches(&edge.expression)
+        })
+    }
+
+    fn remove_edges_between(
+        &mut self,
+        source: StateIndex<Self>,
+        target: StateIndex<Self>,
+    ) -> Option<Vec<EdgeTuple<Self>>> {
+        if !self.contains_state_index(target) {
+            return None;
+        }
+        self.take_edges_where(source, |edge| edge.target == target)
+    }
+
+    fn remove_edges_from(&mut self, source: StateIndex<Self>) -> Option<Vec<EdgeTuple<Self>>> {
+        self.take_edges_where(source, |_| true)
+    }
+
+    fn remove_edges_to(&mut self, target: StateIndex<Self>) -> Option<Vec<EdgeTuple<Self>>> {
+        if !self.contains_state_index(target) {
+            return None;
+        }
+        let slots = std::mem::take(&mut self.predecessors[*target]);
+        Some(
+            slots
+                .into_iter()
+                .filter_map(|slot| self.take_edge(slot))
+                .collect(),
+        )
+    }
+}
+
+/// Iterates over the incoming edges of a state in a [`Packed`] transition system by looking
+/// up the positions stored in its reverse index.
+pub struct PackedEdgesTo<'a, A: SimpleAlphabet, C: Color> {
+    edges: &'a [Option<PackedEdge<A, C>>],
+    positions: std::slice::Iter<'a, usize>,
+}
+
+impl<'a, A: SimpleAlphabet, C: Color> Iterator for PackedEdgesTo<'a, A, C> {
     type Item = &'a PackedEdge<A, C>;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos < self.end {
-            assert!(self.end <= self.array.len());
-            self.pos += 1;
-            Some(&self.array[self.pos - 1])
-        } else {
-            None
-        }
+        let pos = self.positions.next()?;
+        Some(
+            self.edges[*pos]
+                .as_ref()
+                .expect("reverse index only points to existing edges"),
+        )
     }
 }
 
-impl<'a, A: SimpleAlphabet, C: Color> PackedEdgesFrom<'a, A, C> {
-    pub fn new(array: &'a [PackedEdge<A, C>], pos: usize, end: usize) -> Self {
-        if end > array.len() || pos > end {
-            panic!(
-                "invalid acces pos: {pos} end: {end} in length {} array\n{array:?}",
-                array.len()
-            );
-        }
-        Self { array, end, pos }
+impl<'a, A: SimpleAlphabet, C: Color> PackedEdgesTo<'a, A, C> {
+    pub fn new(
+        edges: &'a [Option<PackedEdge<A, C>>],
+        positions: std::slice::Iter<'a, usize>,
+    ) -> Self {
+        Self { edges, positions }
     }
 }
 
 pub struct PackedStateIndices<'a, Q> {
     array: &'a [Option<Q>],
     pos: usize,
-    length: usize,
 }
 
 impl<Q> Iterator for PackedStateIndices<'_, Q> {
     type Item = Id;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos < self.length {
+        while let Some(state) = self.array.get(self.pos) {
             self.pos += 1;
-            Some(Id(self.pos - 1))
-        } else {
-            None
+            if state.is_some() {
+                return Some(Id(self.pos - 1));
+            }
         }
+        None
     }
 }
 
 impl<'a, Q> PackedStateIndices<'a, Q> {
     pub fn new(array: &'a [Option<Q>]) -> Self {
-        Self {
-            array,
-            pos: 0,
-            length: array.len(),
-        }
+        Self { array, pos: 0 }
     }
 }
 
@@ -195,3 +509,129 @@ impl<'a, A: SimpleAlphabet, C: Color> IsEdge<'a, A::Expression, Id, C> for &'a P
         &self.expression
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Id, Packed};
+    use crate::automaton::{DFA, DPA, MinEvenParityCondition};
+    use crate::core::{Int, Void, alphabet::CharAlphabet, upw};
+    use crate::random::generate_random_omega_words;
+    use crate::ts::{Deterministic, PredecessorIterable, Shrinkable, Sproutable, TSBuilder};
+    use crate::{Pointed, TransitionSystem};
+
+    #[test]
+    fn packed_sprout_and_shrink() {
+        let mut ts: Packed<CharAlphabet, bool, Void> =
+            crate::ts::ForAlphabet::for_alphabet(CharAlphabet::of_size(2));
+        let q0 = ts.add_state(false);
+        let q1 = ts.add_state(true);
+        assert!(ts.add_edge((q0, 'a', q1)).is_none());
+        assert!(ts.add_edge((q1, 'a', q1)).is_none());
+        assert!(ts.add_edge((q1, 'b', q0)).is_none());
+        assert_eq!(ts.add_edge((q0, 'a', q0)), Some((q0, 'a', Void, q1)));
+        assert!(ts.add_edge((q0, 'a', q1)).is_some());
+
+        assert_eq!(ts.reached_state_index_from(q0, "aab"), Some(q0));
+        assert_eq!(ts.predecessors(q1).unwrap().count(), 2);
+        assert_eq!(ts.predecessors(q0).unwrap().count(), 1);
+
+        assert_eq!(ts.remove_edges_from_matching(q1, 'a').unwrap().len(), 1);
+        assert_eq!(ts.predecessors(q1).unwrap().count(), 1);
+
+        assert_eq!(ts.remove_state(q1), Some(true));
+        assert_eq!(ts.size(), 1);
+        assert_eq!(ts.state_indices().collect::<Vec<_>>(), vec![q0]);
+        assert!(ts.edges_from(q0).unwrap().next().is_none());
+        assert!(ts.predecessors(q1).is_none());
+        assert!(ts.predecessors(q0).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn packed_automata() {
+        let dfa = TSBuilder::default()
+            .with_state_colors([false, true, false])
+            .with_edges([
+                (0, 'a', 1),
+                (0, 'b', 2),
+                (1, 'a', 1),
+                (1, 'b', 0),
+                (2, 'a', 2),
+                (2, 'b', 2),
+            ])
+            .into_dfa(0);
+        let packed: DFA<CharAlphabet, Void, Packed<CharAlphabet, bool, Void>> =
+            DFA::from_parts(Packed::from(&dfa), Id(0));
+        for word in ["", "a", "ab", "aba", "b", "abaa", "abb"] {
+            assert_eq!(dfa.accepts(word), packed.accepts(word));
+        }
+        assert!(packed.equivalent(&dfa));
+        assert_eq!(packed.minimize().size(), 3);
+
+        let dpa = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 1),
+                (0, 'b', 1, 0),
+                (1, 'a', 2, 1),
+                (1, 'b', 3, 0),
+            ])
+            .into_dpa(0);
+        let packed: DPA<
+            CharAlphabet,
+            Void,
+            MinEvenParityCondition,
+            Packed<CharAlphabet, Void, Int>,
+        > = DPA::from_parts(Packed::from(&dpa), Id(dpa.initial() as usize));
+        let alphabet = CharAlphabet::of_size(2);
+        for word in generate_random_omega_words(&alphabet, 0, 4, 1, 4, 20) {
+            assert_eq!(dpa.accepts(&word), packed.accepts(&word));
+            assert_eq!(
+                dpa.recurrent_state_indices(&word).map(|set| set.len()),
+                packed.recurrent_state_indices(&word).map(|set| set.len()),
+            );
+        }
+        assert!(packed.accepts(upw!("a")));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn packed_deserialization_rejects_corrupted_payloads() {
+        let mut ts: Packed<CharAlphabet, bool, Void> =
+            crate::ts::ForAlphabet::for_alphabet(CharAlphabet::of_size(2));
+        let q0 = ts.add_state(false);
+        let q1 = ts.add_state(true);
+        ts.add_edge((q0, 'a', q1));
+        ts.add_edge((q1, 'b', q0));
+        ts.add_edge((q1, 'a', q1));
+
+        let json = serde_json::to_value(&ts).unwrap();
+        let back: Packed<CharAlphabet, bool, Void> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(back, ts);
+
+        let rejected = |corrupt: &dyn Fn(&mut serde_json::Value)| {
+            let mut json = json.clone();
+            corrupt(&mut json);
+            serde_json::from_value::<Packed<CharAlphabet, bool, Void>>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        let message = rejected(&|json| {
+            json["edges"].as_array_mut().unwrap().pop();
+        });
+        assert!(message.contains("expected 4 edge slots"), "{message}");
+        let message = rejected(&|json| {
+            json["predecessors"].as_array_mut().unwrap().pop();
+        });
+        assert!(
+            message.contains("expected 2 predecessor lists"),
+            "{message}"
+        );
+        let message = rejected(&|json| json["edges"][0]["source"] = 1.into());
+        assert!(message.contains("slot 0"), "{message}");
+        let message = rejected(&|json| json["edges"][3]["target"] = 7.into());
+        assert!(message.contains("slot 3"), "{message}");
+        let message = rejected(&|json| json["states"][0] = serde_json::Value::Null);
+        assert!(message.contains("slot 0"), "{message}");
+        let message = rejected(&|json| json["predecessors"][0] = vec![17].into());
+        assert!(message.contains("predecessors of state 0"), "{message}");
+    }
+}
@@ -69,6 +69,10 @@ where
         self.ts().state_indices()
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        self.ts().contains_state_index(index)
+    }
+
     fn state_color(&self, state: StateIndex<Self>) -> Option<Self::StateColor> {
         self.ts().state_color(state)
     }
@@ -325,6 +329,10 @@ where
         self.ts().state_indices()
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        self.ts().contains_state_index(index)
+    }
+
     fn state_color(&self, state: StateIndex<Self>) -> Option<Self::StateColor> {
         self.ts().state_color(state)
     }
@@ -550,6 +558,10 @@ where
         self.ts().state_indices()
     }
 
+    fn contains_state_index(&self, index: Self::StateIndex) -> bool {
+        self.ts().contains_state_index(index)
+    }
+
     fn state_color(&self, state: StateIndex<Self>) -> Option<Self::StateColor> {
         let color = self.ts().state_color(state)?;
         Some((self.f())(color))
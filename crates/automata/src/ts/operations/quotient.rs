@@ -26,6 +26,7 @@ pub struct Quotient<Ts: TransitionSystem> {
     ts: Ts,
     expressions: math::OrderedMap<SymbolOf<Ts>, EdgeExpression<Ts>>,
     partition: Partition<Ts::StateIndex>,
+    classes: math::Map<Ts::StateIndex, usize>,
 }
 
 impl<Ts: Deterministic + Pointed> Pointed for Quotient<Ts> {
@@ -67,7 +68,7 @@ impl<Ts: TransitionSystem> Quotient<Ts> {
     /// Tries to find the id of the quotient class containing the given state `q`. If
     /// the state is not in the partition, `None` is returned.
     pub fn find_id_by_state(&self, q: Ts::StateIndex) -> Option<usize> {
-        self.partition.iter().position(|o| o.contains(&q))
+        self.classes.get(&q).copied()
     }
 
     /// Extracts the underlying right congruence by erasing the state and edge colors and then collecting
@@ -90,6 +91,11 @@ impl<Ts: TransitionSystem> Quotient<Ts> {
                 .map(|sym| (sym, ts.alphabet().make_expression(sym).clone()))
                 .collect(),
             ts,
+            classes: partition
+                .iter()
+                .enumerate()
+                .flat_map(|(id, class)| class.iter().map(move |q| (*q, id)))
+                .collect(),
             partition,
         }
     }
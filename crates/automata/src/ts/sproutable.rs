@@ -1,5 +1,5 @@
 use crate::{Pointed, TransitionSystem};
-use automata_core::alphabet::{Alphabet, CharAlphabet};
+use automata_core::alphabet::{Alphabet, CharAlphabet, Matcher, PropAlphabet};
 use automata_core::math::Bijection;
 use bit_set::BitSet;
 use itertools::Itertools;
@@ -66,6 +66,34 @@ impl IndexedAlphabet for CharAlphabet {
     }
 }
 
+/// Symbols and expressions of a [`PropAlphabet`] are indexed by their position in
+/// [`Alphabet::universe`], i.e. by the order in which the valuations are enumerated. An
+/// expression is indexed by the first symbol that matches it, which is unique for the
+/// expressions that are created with [`Alphabet::make_expression`].
+impl IndexedAlphabet for PropAlphabet {
+    fn symbol_to_index(&self, sym: Self::Symbol) -> usize {
+        self.universe()
+            .position(|x| x == sym)
+            .expect("Must be present in alphabet")
+    }
+
+    fn expression_to_index(&self, sym: &Self::Expression) -> usize {
+        self.universe()
+            .position(|x| x.matches(sym))
+            .expect("Must be matched by a symbol of the alphabet")
+    }
+
+    fn symbol_from_index(&self, index: usize) -> Self::Symbol {
+        self.universe()
+            .nth(index)
+            .expect("Index must be smaller than the size of the alphabet")
+    }
+
+    fn expression_from_index(&self, index: usize) -> Self::Expression {
+        self.make_expression(self.symbol_from_index(index))
+    }
+}
+
 /// Trait for transition systems that allow insertion of states and transitions.
 pub trait Sproutable: TransitionSystem {
     /// Adds a new state with the given color. The method returns the index of the newly created
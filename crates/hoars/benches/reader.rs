@@ -0,0 +1,46 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use hoars::HoaReader;
+
+const AUTOMATON: &str = r#"HOA: v1
+States: 3
+Start: 0
+AP: 2 "a" "b"
+acc-name: parity min even 3
+Acceptance: 3 Inf(0) | (Fin(1) & Inf(2))
+--BODY--
+State: 0
+[0&1] 1 {0}
+[0&!1] 2
+[!0] 0 {1}
+State: 1
+[0] 2 {2}
+[!0] 1
+State: 2
+[t] 0 {1}
+--END--
+"#;
+
+/// Reading should take time linear in the number of automata, so the throughput is
+/// expected to be the same for all sizes.
+fn read_many_automata(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hoa_reader");
+    for count in [10, 100, 1000, 10000] {
+        let input = AUTOMATON.repeat(count);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("reader", count), &input, |b, input| {
+            b.iter(|| {
+                let read = HoaReader::new(input.as_bytes())
+                    .filter(Result::is_ok)
+                    .count();
+                assert_eq!(read, count);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("split", count), &input, |b, input| {
+            b.iter(|| assert_eq!(hoars::parse_hoa_automata(input).len(), count))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, read_many_automata);
+criterion_main!(benches);
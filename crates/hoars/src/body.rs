@@ -1,10 +1,10 @@
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 
 use chumsky::prelude::*;
 
 use crate::{
-    AcceptanceSignature, AtomicProposition, Id, Label, LabelExpression, StateConjunction,
-    lexer::Token, value,
+    AbstractLabelExpression, AcceptanceSignature, AtomicProposition, Id, Label, LabelExpression,
+    StateConjunction, lexer::Token, value,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -18,21 +18,35 @@ pub struct RawState(
     Option<AcceptanceSignature>,
 );
 
+/// An edge as it appears in the body, its label is absent if the edge is labeled
+/// implicitly or if the label is given by the source state.
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct ExplicitEdge(Label, StateConjunction, Option<AcceptanceSignature>);
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct ImplicitEdge(StateConjunction, Option<AcceptanceSignature>);
+struct RawEdge(
+    Option<Label>,
+    StateConjunction,
+    Option<AcceptanceSignature>,
+    Range<usize>,
+);
 
 /// Represents an edge in a HOA automaton. It contains the [`crate::LabelExpression`], the
-/// [`StateConjunction`] and the [`AcceptanceSignature`] of the edge.
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// [`StateConjunction`] and the [`AcceptanceSignature`] of the edge. A parsed edge also
+/// knows where it occurs in the input, which is not taken into account when comparing edges.
+#[derive(Clone, Debug)]
 pub struct Edge(
     pub(crate) Label,
     pub(crate) StateConjunction,
     pub(crate) AcceptanceSignature,
+    pub(crate) Option<Range<usize>>,
 );
 
+impl PartialEq for Edge {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1 && self.2 == other.2
+    }
+}
+
+impl Eq for Edge {}
+
 impl Edge {
     /// Returns the label of the edge.
     pub fn label(&self) -> &Label {
@@ -54,6 +68,12 @@ impl Edge {
         &self.2
     }
 
+    /// Returns the positions (counted in characters) that the edge occupies in the input it
+    /// was parsed from, or `None` if it was not parsed.
+    pub fn span(&self) -> Option<&Range<usize>> {
+        self.3.as_ref()
+    }
+
     /// Tries to get the target (singular) of the transition. Returns `None` if the
     /// transition does not have a singular target.
     pub fn target(&self) -> Option<Id> {
@@ -66,23 +86,30 @@ impl Edge {
         state_conjunction: StateConjunction,
         acceptance_signature: AcceptanceSignature,
     ) -> Self {
-        Self(label_expression, state_conjunction, acceptance_signature)
+        Self(
+            label_expression,
+            state_conjunction,
+            acceptance_signature,
+            None,
+        )
     }
 }
 
 /// Represents a state in a HOA automaton. It contains the [`Id`] of the state, an optional
-/// comment and a list of outgoing edges.
+/// comment and a list of outgoing edges. The last field indicates whether the edges are
+/// labeled implicitly and still need to be expanded.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct State(
     pub(crate) Id,
     pub(crate) Option<String>,
     pub(crate) Vec<Edge>,
+    pub(crate) bool,
 );
 
 impl State {
     /// Constructs a new state from its parts.
     pub fn from_parts(id: Id, comment: Option<String>, edges: Vec<Edge>) -> Self {
-        Self(id, comment, edges)
+        Self(id, comment, edges, false)
     }
 
     /// Extracts the id of the state.
@@ -106,36 +133,58 @@ impl State {
     }
 }
 
-impl From<(Option<AcceptanceSignature>, ExplicitEdge)> for Edge {
-    fn from((state_acc, edge): (Option<AcceptanceSignature>, ExplicitEdge)) -> Self {
-        let acc = match (state_acc, &edge.2) {
+impl Edge {
+    /// Builds an edge from its raw form, the acceptance signature of the source state
+    /// is moved onto the edge.
+    fn from_raw(label: Label, state_acc: Option<&AcceptanceSignature>, edge: RawEdge) -> Self {
+        let acc = match (state_acc, edge.2) {
             (None, None) => AcceptanceSignature(Vec::new()),
-            (Some(acc), None) => acc,
-            (None, Some(acc)) => acc.clone(),
+            (Some(acc), None) => acc.clone(),
+            (None, Some(acc)) => acc,
             (Some(left), Some(right)) => {
                 AcceptanceSignature(left.iter().cloned().chain(right.iter().cloned()).collect())
             }
         };
-        Edge(edge.0, edge.1, acc)
+        Edge(label, edge.1, acc, Some(edge.3))
     }
 }
 
-impl TryFrom<(RawState, Vec<ExplicitEdge>)> for State {
+impl TryFrom<(RawState, Vec<RawEdge>)> for State {
     type Error = String;
 
-    fn try_from((state, edges): (RawState, Vec<ExplicitEdge>)) -> Result<Self, Self::Error> {
-        let mut out_edges = vec![];
+    fn try_from((state, edges): (RawState, Vec<RawEdge>)) -> Result<Self, Self::Error> {
         let RawState(state_label, id, state_text, state_acc) = state;
+        let labeled = edges.iter().filter(|edge| edge.0.is_some()).count();
 
-        if state_label.is_some() {
-            return Err("Transformation from state-based to transition-based requires adding a new initial state etc (see example 'non-deterministic state-based Büchi Automaton')".to_string());
+        if state_label.is_some() && labeled > 0 {
+            return Err(format!(
+                "State {id} is labeled, so its edges must not carry a label"
+            ));
         }
-
-        for raw_edge in edges {
-            out_edges.push(Edge::from((state_acc.clone(), raw_edge)));
+        if labeled > 0 && labeled < edges.len() {
+            return Err(format!(
+                "State {id} mixes labeled and implicitly labeled edges"
+            ));
         }
 
-        Ok(State(id, state_text, out_edges))
+        // implicitly labeled edges get a placeholder, which is replaced once the number
+        // of atomic propositions is known
+        let implicit = state_label.is_none() && labeled == 0 && !edges.is_empty();
+        let out_edges = edges
+            .into_iter()
+            .map(|mut edge| {
+                let label = edge
+                    .0
+                    .take()
+                    .or_else(|| state_label.clone())
+                    .unwrap_or(Label(LabelExpression::Abstract(
+                        AbstractLabelExpression::Boolean(true),
+                    )));
+                Edge::from_raw(label, state_acc.as_ref(), edge)
+            })
+            .collect();
+
+        Ok(State(id, state_text, out_edges, implicit))
     }
 }
 
@@ -147,24 +196,16 @@ pub fn label() -> impl Parser<Token, Label, Error = Simple<Token>> {
         .map(Label)
 }
 
-fn explicit_edge() -> impl Parser<Token, ExplicitEdge, Error = Simple<Token>> {
+fn edge() -> impl Parser<Token, RawEdge, Error = Simple<Token>> {
     label()
+        .or_not()
         .then(value::state_conjunction())
         .then(value::acceptance_signature().or_not())
-        .map(|((label, state_conjunction), acceptance_signature)| {
-            ExplicitEdge(label, state_conjunction, acceptance_signature)
+        .map_with_span(|((label, state_conjunction), acceptance_signature), span| {
+            RawEdge(label, state_conjunction, acceptance_signature, span)
         })
 }
 
-// For now, we allow only explicit edges, but this might change in the future. In any case, we already have the necessary
-// infrastructure to support implicit edges.
-#[allow(unused)]
-fn implicit_edge() -> impl Parser<Token, ImplicitEdge, Error = Simple<Token>> {
-    value::state_conjunction()
-        .then(value::acceptance_signature().or_not())
-        .map(|(label, acceptance_signature)| ImplicitEdge(label, acceptance_signature))
-}
-
 pub fn state() -> impl Parser<Token, State, Error = Simple<Token>> {
     just(Token::Header("State".to_string()))
         .ignore_then(
@@ -175,7 +216,7 @@ pub fn state() -> impl Parser<Token, State, Error = Simple<Token>> {
                 .then(value::acceptance_signature().or_not())
                 .map(|(((l, i), t), a)| RawState(l, i, t, a)),
         )
-        .then(explicit_edge().repeated())
+        .then(edge().repeated())
         .try_map(|input, span| State::try_from(input).map_err(|err| Simple::custom(span, err)))
 }
 
@@ -29,6 +29,14 @@ impl StateConjunction {
     }
 }
 
+impl Deref for StateConjunction {
+    type Target = Vec<crate::Id>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// An atomic proposition is named by a string.
 pub type AtomicProposition = String;
 
@@ -236,7 +244,7 @@ impl TryFrom<String> for AcceptanceName {
 
 /// Represents properties of an automaton. For more information
 /// see the documentation of the [HOA format](https://adl.github.io/hoaf/#properties).
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[allow(missing_docs)]
 pub enum Property {
     StateLabels,
@@ -279,7 +287,7 @@ impl TryFrom<String> for Property {
             "weak" => Ok(Property::Weak),
             "very-weak" => Ok(Property::VeryWeak),
             "inherently-weak" => Ok(Property::InherentlyWeak),
-            "terminatl" => Ok(Property::Terminal),
+            "terminal" => Ok(Property::Terminal),
             "tight" => Ok(Property::Tight),
             "colored" => Ok(Property::Colored),
             unknown => Err(format!("{} is not a valid property", unknown)),
@@ -190,12 +190,11 @@ impl Header {
         self.iter().find_map(|i| i.count_states())
     }
 
-    pub fn acceptance_name(&self) -> AcceptanceName {
+    /// Returns the name of the acceptance condition, if an `acc-name` header is present.
+    pub fn acceptance_name(&self) -> Option<AcceptanceName> {
         self.iter()
             .find_map(|i| i.try_acceptance_name())
-            .expect("Acceptance header must be present")
-            .0
-            .clone()
+            .map(|(name, _)| name.clone())
     }
 }
 
@@ -1,22 +1,29 @@
 use chumsky::{Parser, Stream};
 
-use crate::{FromHoaError, HoaRepresentation, build_error_report, lexer};
+use chumsky::error::Simple;
+
+use crate::{FromHoaError, HoaRepresentation, SyntaxError, build_error_report, lexer};
+
+/// Converts the errors of the lexer or parser into [`SyntaxError`]s, the detailed report
+/// is only logged.
+fn syntax_errors<I: Iterator<Item = Simple<String>>>(input: &str, errs: I) -> Vec<SyntaxError> {
+    let errs: Vec<_> = errs.collect();
+    tracing::debug!("{}", build_error_report(input, errs.iter().cloned()));
+    errs.into_iter().map(SyntaxError::from).collect()
+}
 
 pub fn from_hoa(value: &str) -> Result<HoaRepresentation, FromHoaError> {
-    if value.contains("--ABORT--") {
+    if lexer::markers(value).any(|(_, marker)| marker == lexer::Marker::Abort) {
         return Err(FromHoaError::Abort);
     }
     let input = value;
     let start = std::time::Instant::now();
-    let tokens = lexer::tokenizer()
-        .parse(input)
-        .map_err(|error_list| {
-            build_error_report(
-                input,
-                error_list.into_iter().map(|err| err.map(|c| c.to_string())),
-            )
-        })
-        .map_err(FromHoaError::LexerError)?;
+    let tokens = lexer::tokenize(input).map_err(|error_list| {
+        FromHoaError::LexerError(syntax_errors(
+            input,
+            error_list.into_iter().map(|err| err.map(|c| c.to_string())),
+        ))
+    })?;
     tracing::info!("Tokenization took {}µs", start.elapsed().as_micros());
 
     let length = input.chars().count();
@@ -24,12 +31,23 @@ pub fn from_hoa(value: &str) -> Result<HoaRepresentation, FromHoaError> {
     let out = HoaRepresentation::parser()
         .parse(Stream::from_iter(length..length + 1, tokens.into_iter()))
         .map_err(|error_list| {
-            build_error_report(
+            FromHoaError::ParserError(syntax_errors(
                 input,
                 error_list.into_iter().map(|err| err.map(|c| c.to_string())),
-            )
-        })
-        .map_err(FromHoaError::ParserError);
+            ))
+        });
     tracing::info!("Actual parsing took {}µs", start.elapsed().as_micros());
-    out
+
+    let mut out = out?;
+    match out.header().get_version() {
+        Some(version) if version == "v1" => {}
+        version => {
+            return Err(FromHoaError::UnsupportedVersion(
+                version.unwrap_or_default(),
+            ));
+        }
+    }
+    out.unalias()?;
+    out.expand_implicit_labels()?;
+    Ok(out)
 }
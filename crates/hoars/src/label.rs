@@ -1,7 +1,10 @@
 use biodivine_lib_bdd::{Bdd, BddPartialValuation, BddVariable, BddVariableSet};
 use itertools::Itertools;
+use std::collections::HashMap;
 use std::ops::Deref;
 
+use crate::FromHoaError;
+
 /// Newtype wrapper around a [`crate::LabelExpression`], implements [`Deref`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Label(pub LabelExpression);
@@ -21,6 +24,7 @@ pub enum AbstractLabelExpression {
     Negated(Box<AbstractLabelExpression>),
     Conjunction(Vec<AbstractLabelExpression>),
     Disjunction(Vec<AbstractLabelExpression>),
+    Alias(String),
 }
 
 pub(crate) enum Atomic {
@@ -29,11 +33,12 @@ pub(crate) enum Atomic {
 }
 
 impl Atomic {
-    pub(crate) fn to_value(&self, vars: &[BddVariable]) -> (BddVariable, bool) {
-        match self {
-            Atomic::Positive(i) => (vars[*i as usize], true),
-            Atomic::Negative(i) => (vars[*i as usize], false),
-        }
+    pub(crate) fn to_value(&self, vars: &[BddVariable]) -> Result<(BddVariable, bool), String> {
+        let (Atomic::Positive(i) | Atomic::Negative(i)) = self;
+        let var = vars
+            .get(*i as usize)
+            .ok_or_else(|| format!("AP identifier {i} is too high"))?;
+        Ok((*var, matches!(self, Atomic::Positive(_))))
     }
 }
 
@@ -75,27 +80,98 @@ impl AbstractLabelExpression {
             AbstractLabelExpression::Conjunction(cs) => {
                 if let Some(ints) = cs.iter().map(|c| c.try_atom()).collect::<Option<Vec<_>>>() {
                     let valuation = BddPartialValuation::from_values(
-                        &ints.into_iter().map(|a| a.to_value(vars)).collect_vec(),
+                        &ints
+                            .into_iter()
+                            .map(|a| a.to_value(vars))
+                            .collect::<Result<Vec<_>, _>>()?,
                     );
                     Ok(vs.mk_conjunctive_clause(&valuation))
                 } else {
-                    Err(format!(
-                        "could not parse label expression conjunct {cs:?}, expected atom"
-                    ))
+                    cs.into_iter().try_fold(vs.mk_true(), |acc, c| {
+                        Ok(acc.and(&c.try_into_bdd(vs, vars)?))
+                    })
                 }
             }
             AbstractLabelExpression::Disjunction(ds) => {
                 if let Some(ints) = ds.iter().map(|c| c.try_atom()).collect::<Option<Vec<_>>>() {
                     let valuation = BddPartialValuation::from_values(
-                        &ints.into_iter().map(|a| a.to_value(vars)).collect_vec(),
+                        &ints
+                            .into_iter()
+                            .map(|a| a.to_value(vars))
+                            .collect::<Result<Vec<_>, _>>()?,
                     );
                     Ok(vs.mk_disjunctive_clause(&valuation))
                 } else {
-                    Err(format!(
-                        "could not parse label expression disjunct {ds:?}, expected atom"
-                    ))
+                    ds.into_iter().try_fold(vs.mk_false(), |acc, d| {
+                        Ok(acc.or(&d.try_into_bdd(vs, vars)?))
+                    })
                 }
             }
+            AbstractLabelExpression::Alias(name) => Err(format!("unresolved alias @{name}")),
+        }
+    }
+
+    /// Replaces every alias in `self` by the expression it stands for. Aliases may refer to
+    /// other aliases, but not to themselves.
+    pub(crate) fn unalias(
+        &self,
+        aliases: &HashMap<String, AbstractLabelExpression>,
+        visiting: &mut Vec<String>,
+    ) -> Result<Self, FromHoaError> {
+        let unalias_all = |exprs: &[Self], visiting: &mut Vec<String>| {
+            exprs
+                .iter()
+                .map(|e| e.unalias(aliases, visiting))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match self {
+            AbstractLabelExpression::Boolean(_) | AbstractLabelExpression::Integer(_) => {
+                self.clone()
+            }
+            AbstractLabelExpression::Negated(e) => {
+                AbstractLabelExpression::Negated(Box::new(e.unalias(aliases, visiting)?))
+            }
+            AbstractLabelExpression::Conjunction(cs) => {
+                AbstractLabelExpression::Conjunction(unalias_all(cs, visiting)?)
+            }
+            AbstractLabelExpression::Disjunction(ds) => {
+                AbstractLabelExpression::Disjunction(unalias_all(ds, visiting)?)
+            }
+            AbstractLabelExpression::Alias(name) => {
+                if visiting.contains(name) {
+                    return Err(FromHoaError::CyclicAlias(name.clone()));
+                }
+                let expr = aliases
+                    .get(name)
+                    .ok_or_else(|| FromHoaError::UndefinedAlias(name.clone()))?;
+                visiting.push(name.clone());
+                let resolved = expr.unalias(aliases, visiting)?;
+                visiting.pop();
+                resolved
+            }
+        })
+    }
+
+    /// Builds the expression that is satisfied only by the valuation of `aps` atomic
+    /// propositions whose binary encoding is `index`, where the bit at position `i`
+    /// corresponds to the atomic proposition `i`. This is how implicit labels are defined.
+    pub(crate) fn implicit(index: usize, aps: u16) -> Self {
+        if aps == 0 {
+            return AbstractLabelExpression::Boolean(true);
+        }
+        let literals = (0..aps)
+            .map(|ap| {
+                if index & (1 << ap) != 0 {
+                    AbstractLabelExpression::Integer(ap)
+                } else {
+                    AbstractLabelExpression::Negated(Box::new(AbstractLabelExpression::Integer(ap)))
+                }
+            })
+            .collect_vec();
+        if literals.len() == 1 {
+            literals.into_iter().next().unwrap()
+        } else {
+            AbstractLabelExpression::Conjunction(literals)
         }
     }
 }
@@ -124,6 +200,45 @@ pub enum LabelExpression {
     Abstract(AbstractLabelExpression),
 }
 
+impl std::fmt::Display for LabelExpression {
+    /// An [`HoaExpression`] is written as a disjunction of the conjunctive clauses that
+    /// correspond to the paths of the BDD.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bdd = match self {
+            LabelExpression::Abstract(expr) => return write!(f, "{expr}"),
+            LabelExpression::Expression(bdd) => bdd,
+        };
+        if bdd.is_false() {
+            return write!(f, "f");
+        }
+        let clauses = bdd
+            .sat_clauses()
+            .map(|clause| {
+                let literals = clause
+                    .to_values()
+                    .into_iter()
+                    .map(|(var, value)| {
+                        let ap = AbstractLabelExpression::Integer(var.to_index() as u16);
+                        match value {
+                            true => ap,
+                            false => AbstractLabelExpression::Negated(Box::new(ap)),
+                        }
+                    })
+                    .collect_vec();
+                match literals.len() {
+                    0 => AbstractLabelExpression::Boolean(true),
+                    1 => literals.into_iter().next().unwrap(),
+                    _ => AbstractLabelExpression::Conjunction(literals),
+                }
+            })
+            .collect_vec();
+        match clauses.len() {
+            1 => write!(f, "{}", clauses[0]),
+            _ => write!(f, "{}", AbstractLabelExpression::Disjunction(clauses)),
+        }
+    }
+}
+
 impl LabelExpression {
     pub fn try_into_hoa_expression(self, num_aps: u8) -> Result<HoaExpression, String> {
         match self {
@@ -39,11 +39,18 @@ impl std::fmt::Display for Token {
     }
 }
 
+/// The reference tokenizer, which is used to check [`tokenize`] and to recover from
+/// errors when testing the header parser.
+#[cfg(test)]
 pub fn tokenizer() -> impl Parser<char, Vec<(Token, Span)>, Error = Simple<char>> {
     let int = text::int(10).map(Token::Int);
 
     let str_ = just('"')
-        .ignore_then(filter(|c| *c != '"').repeated())
+        .ignore_then(
+            filter(|c| *c != '"' && *c != '\\')
+                .or(just('\\').ignore_then(any()))
+                .repeated(),
+        )
         .then_ignore(just('"'))
         .collect::<String>()
         .map(Token::Text);
@@ -90,4 +97,269 @@ pub fn tokenizer() -> impl Parser<char, Vec<(Token, Span)>, Error = Simple<char>
         .padded_by(comment.repeated())
         .padded()
         .repeated()
+        .then_ignore(chumsky::primitive::end())
+}
+
+/// Splits `input` into tokens, this produces the same tokens and spans (counted in
+/// characters) as [`tokenizer`], but avoids the overhead of the parser combinators,
+/// which dominates the time it takes to read large files.
+pub fn tokenize(input: &str) -> Result<Vec<(Token, Span)>, Vec<Simple<char>>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let starts_with = |pos: usize, s: &str| {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| chars.get(pos + i) == Some(&c))
+    };
+    let ident_len = |pos: usize| {
+        if !chars
+            .get(pos)
+            .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_')
+        {
+            return 0;
+        }
+        1 + chars[pos + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric() || **c == '_' || **c == '-')
+            .count()
+    };
+    let error = |pos: usize| {
+        vec![Simple::expected_input_found(
+            pos..pos + 1,
+            None,
+            chars.get(pos).copied(),
+        )]
+    };
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if starts_with(pos, "/*") {
+            let Some(len) = (pos + 2..chars.len()).find(|&i| starts_with(i, "*/")) else {
+                return Err(error(chars.len()));
+            };
+            pos = len + 2;
+            continue;
+        }
+
+        let start = pos;
+        let token = if c.is_ascii_digit() {
+            let len = if c == '0' {
+                1
+            } else {
+                chars[pos..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count()
+            };
+            pos += len;
+            Token::Int(chars[start..pos].iter().collect())
+        } else if starts_with(pos, "--ABORT--") {
+            pos += "--ABORT--".len();
+            Token::BodyEnd
+        } else if starts_with(pos, "--END--") {
+            pos += "--END--".len();
+            Token::BodyEnd
+        } else if starts_with(pos, "--BODY--") {
+            pos += "--BODY--".len();
+            Token::BodyStart
+        } else if c == '"' {
+            // a backslash escapes the character that follows it
+            let mut text = String::new();
+            pos += 1;
+            loop {
+                match chars.get(pos) {
+                    None => return Err(error(chars.len())),
+                    Some('"') => break,
+                    Some('\\') if pos + 1 < chars.len() => {
+                        text.push(chars[pos + 1]);
+                        pos += 2;
+                    }
+                    Some('\\') => return Err(error(chars.len())),
+                    Some(c) => {
+                        text.push(*c);
+                        pos += 1;
+                    }
+                }
+            }
+            pos += 1;
+            Token::Text(text)
+        } else if "!|&".contains(c) {
+            pos += 1;
+            Token::Op(c)
+        } else if "(){}[]".contains(c) {
+            pos += 1;
+            Token::Paren(c)
+        } else if c == '@' {
+            let len = ident_len(pos + 1);
+            if len == 0 {
+                return Err(error(pos + 1));
+            }
+            pos += len + 1;
+            Token::Alias(chars[start + 1..pos].iter().collect())
+        } else {
+            let len = ident_len(pos);
+            if len == 0 {
+                return Err(error(pos));
+            }
+            pos += len;
+            let ident: String = chars[start..pos].iter().collect();
+            if chars.get(pos) == Some(&':') {
+                pos += 1;
+                Token::Header(ident)
+            } else {
+                match ident.as_str() {
+                    "Fin" => Token::Fin,
+                    "Inf" => Token::Inf,
+                    _ => Token::Identifier(ident),
+                }
+            }
+        };
+        tokens.push((token, start..pos));
+    }
+    Ok(tokens)
+}
+
+/// A marker that terminates an automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Marker {
+    /// `--END--`, the automaton is complete.
+    End,
+    /// `--ABORT--`, the automaton is to be discarded.
+    Abort,
+}
+
+impl Marker {
+    const END: &'static str = "--END--";
+    const ABORT: &'static str = "--ABORT--";
+
+    /// The number of bytes that the marker occupies.
+    pub(crate) fn len(self) -> usize {
+        match self {
+            Marker::End => Self::END.len(),
+            Marker::Abort => Self::ABORT.len(),
+        }
+    }
+}
+
+/// The part of the input that the [`MarkerScanner`] is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ScanContext {
+    #[default]
+    Code,
+    String,
+    /// Within a string, right after a backslash.
+    Escape,
+    Comment,
+}
+
+/// Finds the markers that terminate automata without lexing the input. Unlike a plain text
+/// search, it skips over quoted strings and comments, so a marker that occurs in a name or
+/// a comment is not mistaken for the end of an automaton. The scanner remembers whether it
+/// stopped within a string or comment, so input can be fed to it piece by piece.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MarkerScanner {
+    context: ScanContext,
+}
+
+impl MarkerScanner {
+    /// Scans `text` from the byte position `from` on and returns the position and kind of
+    /// the first marker. If there is none, the position from which on scanning has to
+    /// resume once more text is appended is returned as error, this is before a possible
+    /// incomplete marker or comment delimiter at the end of `text`.
+    pub(crate) fn find(&mut self, text: &str, from: usize) -> Result<(usize, Marker), usize> {
+        let bytes = text.as_bytes();
+        let mut pos = from;
+        while pos < bytes.len() {
+            let rest = &bytes[pos..];
+            match self.context {
+                ScanContext::Code => {
+                    for (marker, name) in
+                        [(Marker::End, Marker::END), (Marker::Abort, Marker::ABORT)]
+                    {
+                        if rest.starts_with(name.as_bytes()) {
+                            return Ok((pos, marker));
+                        }
+                    }
+                    let incomplete = |delimiter: &str| {
+                        rest.len() < delimiter.len() && delimiter.as_bytes().starts_with(rest)
+                    };
+                    if [Marker::END, Marker::ABORT, "/*"]
+                        .into_iter()
+                        .any(incomplete)
+                    {
+                        return Err(pos);
+                    }
+                    match rest[0] {
+                        b'"' => self.context = ScanContext::String,
+                        b'/' if rest.starts_with(b"/*") => {
+                            self.context = ScanContext::Comment;
+                            pos += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                ScanContext::String => match rest[0] {
+                    b'"' => self.context = ScanContext::Code,
+                    b'\\' => self.context = ScanContext::Escape,
+                    _ => {}
+                },
+                ScanContext::Escape => self.context = ScanContext::String,
+                ScanContext::Comment => {
+                    if rest.starts_with(b"*/") {
+                        self.context = ScanContext::Code;
+                        pos += 1;
+                    } else if rest == b"*" {
+                        return Err(pos);
+                    }
+                }
+            }
+            pos += 1;
+        }
+        Err(pos)
+    }
+}
+
+/// Returns an iterator over the positions and kinds of all markers in `text`, see
+/// [`MarkerScanner`].
+pub(crate) fn markers(text: &str) -> impl Iterator<Item = (usize, Marker)> + '_ {
+    let mut scanner = MarkerScanner::default();
+    let mut from = 0;
+    std::iter::from_fn(move || {
+        let (pos, marker) = scanner.find(text, from).ok()?;
+        from = pos + marker.len();
+        Some((pos, marker))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::Parser;
+
+    use super::{Token, tokenize, tokenizer};
+
+    #[test]
+    fn tokenize_agrees_with_tokenizer() {
+        for input in [
+            "HOA: v1\nStates: 10 /* a comment */ Start: 0 & 1\n",
+            "AP: 2 \"a\" \"b c\"\nAlias: @a-1 0 & !1\nacc-name: parity min even 3",
+            "Acceptance: 3 Inf(0) | (Fin(1) & Inf(2)) Fin: 007",
+            "--BODY--\nState: 0 \"s\" {0 1}\n[@a-1 | t] 1 & 2 {0}\n--END----ABORT--",
+            r#"name: "a \"quoted\" \\ name" tool: "\\""#,
+        ] {
+            assert_eq!(tokenize(input), Ok(tokenizer().parse(input).unwrap()));
+        }
+        assert!(tokenize("States: 1 %").is_err());
+        assert!(tokenize("AP: 1 \"a").is_err());
+        assert!(tokenize(r#"AP: 1 "a\""#).is_err());
+        assert_eq!(
+            tokenize(r#""a \"b\" \\""#).map(|tokens| tokens[0].0.clone()),
+            Ok(Token::Text(r#"a "b" \"#.to_string()))
+        );
+        assert!(tokenize("/* unterminated").is_err());
+    }
 }
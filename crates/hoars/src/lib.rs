@@ -6,6 +6,8 @@ mod header;
 pub mod input;
 mod lexer;
 pub mod output;
+pub mod properties;
+pub mod reader;
 mod value;
 
 pub mod label;
@@ -16,6 +18,7 @@ pub use label::{
 
 use tracing::warn;
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 
 use ariadne::{Color, Fmt, ReportKind, Source};
@@ -31,6 +34,8 @@ pub use format::{
 
 pub use body::{Body, Edge, State};
 pub use header::{Header, HeaderItem};
+pub use properties::{PropertyViolation, Violation};
+pub use reader::HoaReader;
 
 use itertools::Itertools;
 use lexer::Token;
@@ -49,12 +54,28 @@ pub enum FromHoaError {
     ParseAcceptanceCondition(String),
     /// There was an error in the body.
     UnsupportedBody,
-    /// Lexer encountered an error, contains detailed report.
-    LexerError(String),
-    /// Parser encountered an error, contains detailed report.
-    ParserError(String),
+    /// Lexer encountered errors at the given positions.
+    LexerError(Vec<SyntaxError>),
+    /// Parser encountered errors at the given positions.
+    ParserError(Vec<SyntaxError>),
     /// Abort token was encountered.
     Abort,
+    /// A label refers to an alias that is not defined.
+    UndefinedAlias(String),
+    /// The definition of the given alias refers to itself.
+    CyclicAlias(String),
+    /// A state with implicit labels does not have one edge per valuation of the atomic
+    /// propositions.
+    ImplicitLabels {
+        /// The state whose edges are labeled implicitly.
+        state: Id,
+        /// The number of edges that is required.
+        expected: usize,
+        /// The number of edges that is present.
+        found: usize,
+    },
+    /// Reading the input failed.
+    Io(String),
 }
 
 impl Display for FromHoaError {
@@ -71,8 +92,55 @@ impl Display for FromHoaError {
                 write!(f, "Could not parse acceptance condition: {}", message)
             }
             FromHoaError::Abort => write!(f, "Abort token encountered"),
-            FromHoaError::LexerError(rep) => write!(f, "Lexer error: {}", rep),
-            FromHoaError::ParserError(rep) => write!(f, "Parser error: {}", rep),
+            FromHoaError::LexerError(errors) => {
+                write!(f, "Lexer error: {}", errors.iter().join("; "))
+            }
+            FromHoaError::ParserError(errors) => {
+                write!(f, "Parser error: {}", errors.iter().join("; "))
+            }
+            FromHoaError::UndefinedAlias(name) => write!(f, "Alias @{} is not defined", name),
+            FromHoaError::CyclicAlias(name) => write!(f, "Alias @{} refers to itself", name),
+            FromHoaError::ImplicitLabels {
+                state,
+                expected,
+                found,
+            } => write!(
+                f,
+                "State {} has {} implicitly labeled edges, but {} are required",
+                state, found, expected
+            ),
+            FromHoaError::Io(message) => write!(f, "Could not read input: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FromHoaError {}
+
+/// An error that the lexer or parser encountered. The span refers to the positions (counted
+/// in characters) of the input at which the error occurred.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxError {
+    /// The range of positions in the input that the error refers to.
+    pub span: std::ops::Range<usize>,
+    /// Describes what went wrong.
+    pub message: String,
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl From<Simple<String>> for SyntaxError {
+    fn from(value: Simple<String>) -> Self {
+        Self {
+            span: value.span(),
+            message: value.to_string(),
         }
     }
 }
@@ -136,13 +204,59 @@ impl HoaRepresentation {
     }
 
     /// Creates a new HOA automaton from the given version, header and
-    /// body. This function will also unalias the automaton.
+    /// body. Note that aliases and implicit labels are only resolved when parsing,
+    /// see [`Self::unalias`] and [`Self::expand_implicit_labels`].
     pub fn from_parts(header: Header, body: Body) -> Self {
         let mut out = Self { header, body };
         out.body.sort_by(|x, y| x.0.cmp(&y.0));
         out
     }
 
+    /// Replaces every alias that occurs in a label by the expression it stands for.
+    pub fn unalias(&mut self) -> Result<(), FromHoaError> {
+        let aliases: HashMap<_, _> = self
+            .aliases()
+            .into_iter()
+            .map(|(name, expr)| (name.0, expr))
+            .collect();
+        if aliases.is_empty() {
+            return Ok(());
+        }
+        for state in self.body.iter_mut() {
+            for edge in state.edges_mut() {
+                if let LabelExpression::Abstract(expr) = &edge.label().0 {
+                    let resolved = expr.unalias(&aliases, &mut vec![])?;
+                    *edge.label_mut() = Label(LabelExpression::Abstract(resolved));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Assigns labels to the edges of states that use implicit labels. The `i`-th edge of
+    /// such a state is labeled with the valuation whose binary encoding is `i`, so each of
+    /// these states needs precisely one edge for each valuation of the atomic propositions.
+    pub fn expand_implicit_labels(&mut self) -> Result<(), FromHoaError> {
+        let aps = self.num_aps() as u16;
+        let expected = 1usize.checked_shl(aps as u32).unwrap_or(usize::MAX);
+        for state in self.body.iter_mut().filter(|state| state.3) {
+            if state.2.len() != expected {
+                return Err(FromHoaError::ImplicitLabels {
+                    state: state.id(),
+                    expected,
+                    found: state.2.len(),
+                });
+            }
+            for (index, edge) in state.2.iter_mut().enumerate() {
+                *edge.label_mut() = Label(LabelExpression::Abstract(
+                    AbstractLabelExpression::implicit(index, aps),
+                ));
+            }
+            state.3 = false;
+        }
+        Ok(())
+    }
+
     /// Verifies that the automaton is well-formed. This means that
     /// - the number of states is set correctly
     /// - all states are defined exactly once
@@ -173,30 +287,15 @@ impl HoaRepresentation {
 
     /// Returns the number of states in the automaton.
     pub fn num_states(&self) -> Option<usize> {
-        debug_assert!(
-            self.header()
-                .iter()
-                .filter(|item| matches!(item, HeaderItem::States(_)))
-                .count()
-                == 1,
-            "The number of states must be set exactly once!"
-        );
         self.header().iter().find_map(|item| match item {
             HeaderItem::States(id) => Some(*id as usize),
             _ => None,
         })
     }
 
-    /// Returns the number of edges in the automaton.
+    /// Returns the initial state conjunctions of the automaton, which is empty if there
+    /// is no `Start` header.
     pub fn start(&self) -> Vec<&StateConjunction> {
-        debug_assert!(
-            self.header()
-                .iter()
-                .filter(|item| matches!(item, HeaderItem::Start(_)))
-                .count()
-                >= 1,
-            "At least one initial state conjunction has to be present!"
-        );
         self.header()
             .iter()
             .filter_map(|item| match item {
@@ -206,47 +305,32 @@ impl HoaRepresentation {
             .collect()
     }
 
-    /// Returns the set of all atomic propositions in the automaton.
-    pub fn aps(&self) -> &Vec<String> {
-        let aps = self
-            .header()
+    /// Returns the set of all atomic propositions in the automaton, which is empty if there
+    /// is no `AP` header.
+    pub fn aps(&self) -> &[String] {
+        self.header()
             .iter()
-            .filter_map(|item| match item {
-                HeaderItem::AP(ap) => Some(ap),
+            .find_map(|item| match item {
+                HeaderItem::AP(ap) => Some(ap.as_slice()),
                 _ => None,
             })
-            .collect_vec();
-        debug_assert!(aps.len() == 1, "There must be exactly one AP header!");
-        aps.first().unwrap()
+            .unwrap_or_default()
     }
 
-    /// Counts the number of atomic propositions in the automaton.
+    /// Counts the number of atomic propositions in the automaton. If there are more than
+    /// fit into a `u8`, this saturates at [`u8::MAX`].
     pub fn num_aps(&self) -> u8 {
-        self.aps()
-            .len()
-            .try_into()
-            .expect("Could not fit number of aps in u8!")
-    }
-
-    /// Returns the acceptance condition of the automaton.
-    pub fn acceptance(&self) -> HoaAcceptance {
-        debug_assert!(
-            self.header()
-                .iter()
-                .filter(|item| matches!(item, HeaderItem::Acceptance(..)))
-                .count()
-                == 1,
-            "There must be exactly one Acceptance header!"
-        );
-        self.header()
-            .iter()
-            .find_map(|item| match item {
-                HeaderItem::Acceptance(acceptance_sets, condition) => {
-                    Some((*acceptance_sets as usize, condition.clone()))
-                }
-                _ => None,
-            })
-            .expect("Acceptance header is missing!")
+        self.aps().len().try_into().unwrap_or(u8::MAX)
+    }
+
+    /// Returns the acceptance condition of the automaton, if an `Acceptance` header is present.
+    pub fn acceptance(&self) -> Option<HoaAcceptance> {
+        self.header().iter().find_map(|item| match item {
+            HeaderItem::Acceptance(acceptance_sets, condition) => {
+                Some((*acceptance_sets as usize, condition.clone()))
+            }
+            _ => None,
+        })
     }
 
     /// Returns the aliases of the automaton.
@@ -262,14 +346,6 @@ impl HoaRepresentation {
 
     /// Returns the acceptance name of the automaton.
     pub fn acceptance_name(&self) -> Option<(&AcceptanceName, &Vec<AcceptanceInfo>)> {
-        debug_assert!(
-            self.header()
-                .iter()
-                .filter(|item| matches!(item, HeaderItem::AcceptanceName(..)))
-                .count()
-                == 1,
-            "There must be exactly one AcceptanceName header!"
-        );
         self.header().iter().find_map(|item| match item {
             HeaderItem::AcceptanceName(name, info) => Some((name, info)),
             _ => None,
@@ -298,34 +374,41 @@ impl TryFrom<&str> for HoaRepresentation {
 
 impl std::fmt::Display for AbstractLabelExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Writes `expr` as an operand of a negation or a binary operator, which requires
+        /// parentheses around conjunctions and disjunctions.
+        fn operand(
+            f: &mut std::fmt::Formatter<'_>,
+            expr: &AbstractLabelExpression,
+        ) -> std::fmt::Result {
+            match expr {
+                AbstractLabelExpression::Conjunction(_)
+                | AbstractLabelExpression::Disjunction(_) => write!(f, "({expr})"),
+                _ => Display::fmt(expr, f),
+            }
+        }
+
         match self {
             AbstractLabelExpression::Boolean(b) => match b {
                 true => write!(f, "t"),
                 false => write!(f, "f"),
             },
             AbstractLabelExpression::Integer(i) => write!(f, "{i}"),
+            AbstractLabelExpression::Alias(name) => write!(f, "@{name}"),
             AbstractLabelExpression::Negated(expr) => {
-                write!(f, "!{}", expr)
+                write!(f, "!")?;
+                operand(f, expr)
             }
-            AbstractLabelExpression::Conjunction(conjuncts) => {
-                let mut it = conjuncts.iter();
-                if let Some(first) = it.next() {
-                    Display::fmt(first, f)?;
-                }
-                for succ in it {
-                    write!(f, " & ")?;
-                    Display::fmt(succ, f)?;
-                }
-                Ok(())
-            }
-            AbstractLabelExpression::Disjunction(disjuncts) => {
-                let mut it = disjuncts.iter();
-                if let Some(first) = it.next() {
-                    Display::fmt(first, f)?;
-                }
-                for succ in it {
-                    write!(f, " | ")?;
-                    Display::fmt(succ, f)?;
+            AbstractLabelExpression::Conjunction(operands)
+            | AbstractLabelExpression::Disjunction(operands) => {
+                let separator = match self {
+                    AbstractLabelExpression::Conjunction(_) => " & ",
+                    _ => " | ",
+                };
+                for (i, expr) in operands.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(separator)?;
+                    }
+                    operand(f, expr)?;
                 }
                 Ok(())
             }
@@ -417,35 +500,56 @@ fn print_error_report<I: Iterator<Item = Simple<String>>>(input: &str, errs: I)
     tracing::error!("{}", build_error_report(input, errs))
 }
 
+/// Returns the position right after the `--END--` of the first automaton in `input` that
+/// is not aborted, or `None` if there is no such automaton.
 pub fn first_automaton_split_position(input: &str) -> Option<usize> {
-    const ENDLEN: usize = "--END--".len();
+    split_hoa_automata(input).next().map(|range| range.end)
+}
 
-    'outer: loop {
-        if let Some(end) = input.find("--END--") {
-            if let Some(abort) = input.find("--ABORT--") {
-                if abort < end {
-                    continue 'outer;
-                }
-            }
-            return Some(end + ENDLEN);
-        } else {
-            return None;
-        }
-    }
+/// Splits `input` into the ranges that contain one automaton each. Every range starts
+/// without leading whitespace and ends with `--END--`, automata that are terminated by
+/// `--ABORT--` are skipped. Markers within quoted strings and comments are ignored.
+pub fn split_hoa_automata(input: &str) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+    let mut start = 0;
+    lexer::markers(input).filter_map(move |(pos, marker)| {
+        let end = pos + marker.len();
+        let rest = &input[start..end];
+        let leading = rest.len() - rest.trim_start().len();
+        let range = start + leading..end;
+        start = end;
+        (marker == lexer::Marker::End).then_some(range)
+    })
 }
 
+/// Parses all automata in `input`, see [`split_hoa_automata`]. Automata that can not be
+/// parsed are skipped with a warning, use [`try_parse_hoa_automata`] to obtain the errors.
 pub fn parse_hoa_automata(input: &str) -> Vec<HoaRepresentation> {
-    let mut out = Vec::new();
-    for hoa_aut in input.split_inclusive("--END--") {
-        if !hoa_aut.contains("--BODY--") {
-            continue;
-        }
-        match hoa_aut.try_into() {
-            Ok(aut) => out.push(aut),
-            Err(e) => warn!("Error when parsing automaton: {}", e),
-        }
-    }
-    out
+    try_parse_hoa_automata(input)
+        .filter(|(range, _)| input[range.clone()].contains("--BODY--"))
+        .filter_map(|(_, aut)| match aut {
+            Ok(aut) => Some(aut),
+            Err(e) => {
+                warn!("Error when parsing automaton: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Parses all automata in `input`, see [`split_hoa_automata`]. Yields for each automaton
+/// the range of `input` that it occupies together with the result of parsing it.
+pub fn try_parse_hoa_automata(
+    input: &str,
+) -> impl Iterator<
+    Item = (
+        std::ops::Range<usize>,
+        Result<HoaRepresentation, FromHoaError>,
+    ),
+> + '_ {
+    split_hoa_automata(input).map(|range| {
+        let aut = HoaRepresentation::try_from(&input[range.clone()]);
+        (range, aut)
+    })
 }
 
 #[cfg(test)]
@@ -453,8 +557,8 @@ mod tests {
     use tracing::error;
 
     use crate::{
-        AcceptanceAtom, AcceptanceCondition, AcceptanceName, AcceptanceSignature, Body, HeaderItem,
-        HoaRepresentation, StateConjunction,
+        AcceptanceAtom, AcceptanceCondition, AcceptanceName, AcceptanceSignature, Body,
+        FromHoaError, HeaderItem, HoaRepresentation, LabelExpression, StateConjunction,
         body::{Edge, State},
         header::Header,
         label::AnonymousAbstract,
@@ -468,6 +572,24 @@ mod tests {
         assert_eq!(first, Some(15));
     }
 
+    #[test]
+    fn split_automata_and_syntax_errors() {
+        let contents = "HOA: v1\n--ABORT--\nHOA: v1\n--END--\n  HOA: v2\n--END--";
+        let ranges = super::split_hoa_automata(contents).collect::<Vec<_>>();
+        assert_eq!(ranges, vec![18..33, 36..51]);
+
+        let contents = "HOA: v1\nStates: 1 %\n--BODY--\n--END--";
+        let Err(FromHoaError::LexerError(errors)) = HoaRepresentation::try_from(contents) else {
+            panic!("expected a lexer error");
+        };
+        assert_eq!(errors[0].span, 18..19);
+
+        assert_eq!(
+            HoaRepresentation::try_from("HOA: v2\n--BODY--\n--END--"),
+            Err(FromHoaError::UnsupportedVersion("v2".to_string()))
+        );
+    }
+
     #[test]
     fn real_test_1() {
         let contents = r#"HOA: v1
@@ -559,4 +681,135 @@ mod tests {
             ))
         )
     }
+
+    #[test]
+    fn aliases_are_resolved() {
+        let contents = r#"HOA: v1
+             AP: 2 "a" "b"
+             States: 1
+             Start: 0
+             Alias: @a 0
+             Alias: @ab @a & 1
+             Acceptance: 0 t
+             --BODY--
+             State: 0
+              [@ab] 0
+              [!@a] 0
+             --END--
+             "#;
+        let hoa_aut = HoaRepresentation::try_from(contents).expect("is valid");
+        let labels: Vec<_> = hoa_aut.body()[0]
+            .edges()
+            .iter()
+            .map(|edge| match &edge.label().0 {
+                LabelExpression::Abstract(expr) => expr.to_string(),
+                LabelExpression::Expression(_) => unreachable!("labels are parsed as abstract"),
+            })
+            .collect();
+        assert_eq!(labels, vec!["0 & 1", "!0"]);
+
+        let cyclic = contents.replace("Alias: @a 0", "Alias: @a @ab");
+        assert_eq!(
+            HoaRepresentation::try_from(cyclic.as_str()),
+            Err(FromHoaError::CyclicAlias("ab".to_string()))
+        );
+        let undefined = contents.replace("Alias: @a 0", "Alias: @c 0");
+        assert_eq!(
+            HoaRepresentation::try_from(undefined.as_str()),
+            Err(FromHoaError::UndefinedAlias("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn written_labels_can_be_parsed() {
+        let contents = r#"HOA: v1
+             AP: 3 "a" "b" "c"
+             States: 1
+             Start: 0
+             Acceptance: 0 t
+             --BODY--
+             State: 0
+              [!(0 | 1) & 2] 0
+              [(0 & !1) | !(2 & t)] 0
+             --END--
+             "#;
+        let hoa_aut = HoaRepresentation::try_from(contents).expect("is valid");
+        let labels: Vec<_> = hoa_aut.body()[0]
+            .edges()
+            .iter()
+            .map(|edge| edge.label().to_string())
+            .collect();
+        assert_eq!(labels, vec!["[!(0 | 1) & 2]", "[(0 & !1) | !(2 & t)]"]);
+
+        let written = crate::output::to_hoa(&hoa_aut);
+        let reparsed = HoaRepresentation::try_from(written.as_str()).expect("is valid");
+        assert_eq!(reparsed.body(), hoa_aut.body());
+
+        let mut parsed = hoa_aut.body()[0].edges()[0].label().clone();
+        parsed.0 =
+            LabelExpression::Expression((*parsed).clone().try_into_hoa_expression(3).unwrap());
+        assert_eq!(parsed.to_string(), "[!0 & !1 & 2]");
+    }
+
+    #[test]
+    fn implicit_and_state_labels() {
+        let contents = r#"HOA: v1
+             AP: 1 "a"
+             States: 2
+             Start: 0
+             Acceptance: 1 Inf(0)
+             --BODY--
+             State: 0 {0}
+              1
+              0
+             State: [0] 1
+              1
+              0
+             --END--
+             "#;
+        let hoa_aut = HoaRepresentation::try_from(contents).expect("is valid");
+        let q0 = State::from_parts(
+            0,
+            None,
+            vec![
+                Edge::from_parts(
+                    AnonymousAbstract::not_var_label(0),
+                    StateConjunction(vec![1]),
+                    AcceptanceSignature(vec![0]),
+                ),
+                Edge::from_parts(
+                    AnonymousAbstract::var_label(0),
+                    StateConjunction(vec![0]),
+                    AcceptanceSignature(vec![0]),
+                ),
+            ],
+        );
+        let q1 = State::from_parts(
+            1,
+            None,
+            vec![
+                Edge::from_parts(
+                    AnonymousAbstract::var_label(0),
+                    StateConjunction(vec![1]),
+                    AcceptanceSignature(vec![]),
+                ),
+                Edge::from_parts(
+                    AnonymousAbstract::var_label(0),
+                    StateConjunction(vec![0]),
+                    AcceptanceSignature(vec![]),
+                ),
+            ],
+        );
+        assert_eq!(hoa_aut.body().as_slice(), &[q0, q1]);
+
+        let missing = contents.replace("State: 0 {0}\n              1\n", "State: 0 {0}\n");
+        assert_eq!(
+            HoaRepresentation::try_from(missing.as_str()),
+            Err(FromHoaError::ImplicitLabels {
+                state: 0,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
 }
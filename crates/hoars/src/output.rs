@@ -8,6 +8,21 @@ use crate::{
     StateConjunction,
 };
 
+/// Encloses `text` in double quotes, escaping `\` and `"` with a backslash, so that it
+/// can be used as a string in the HOA format.
+pub fn quoted(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
 pub fn to_hoa(aut: &HoaRepresentation) -> String {
     aut.header()
         .into_iter()
@@ -28,7 +43,7 @@ impl Display for HeaderItem {
                 f,
                 "AP: {} {}",
                 aps.len(),
-                aps.iter().map(|ap| format!("\"{}\"", ap)).join(" ")
+                aps.iter().map(|ap| quoted(ap)).join(" ")
             ),
             HeaderItem::Alias(alias_name, alias_expression) => {
                 write!(f, "Alias: {} {}", alias_name, alias_expression)
@@ -39,10 +54,14 @@ impl Display for HeaderItem {
             HeaderItem::AcceptanceName(identifier, vec_info) => {
                 write!(f, "acc-name: {} {}", identifier, vec_info.iter().join(" "))
             }
-            HeaderItem::Tool(name, options) => {
-                write!(f, "tool: {} {}", name, options.iter().join(" "))
+            HeaderItem::Tool(name, version) => {
+                write!(f, "tool: {}", quoted(name))?;
+                match version {
+                    Some(version) => write!(f, " {}", quoted(version)),
+                    None => Ok(()),
+                }
             }
-            HeaderItem::Name(name) => write!(f, "name: {}", name),
+            HeaderItem::Name(name) => write!(f, "name: {}", quoted(name)),
             HeaderItem::Properties(properties) => {
                 write!(f, "properties: {}", properties.iter().join(" "))
             }
@@ -75,7 +94,7 @@ impl Display for Property {
                 Property::NoUniversalBranching => "no-univ-branch",
                 Property::Deterministic => "deterministic",
                 Property::Complete => "complete",
-                Property::Unambiguous => "unabmiguous",
+                Property::Unambiguous => "unambiguous",
                 Property::StutterInvariant => "stutter-invariant",
                 Property::Weak => "weak",
                 Property::VeryWeak => "very-weak",
@@ -150,7 +169,7 @@ impl Display for StateConjunction {
 
 impl Display for Label {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{:?}]", self.0)
+        write!(f, "[{}]", self.0)
     }
 }
 
@@ -172,7 +191,7 @@ impl Display for Edge {
 impl Display for State {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(acc) = &self.1 {
-            writeln!(f, "State: {} \"{}\"", self.0, acc)?;
+            writeln!(f, "State: {} {}", self.0, quoted(acc))?;
         } else {
             writeln!(f, "State: {}", self.0)?;
         }
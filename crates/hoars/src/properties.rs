@@ -0,0 +1,537 @@
+//! Checks the `properties` that the header of an automaton declares against the structure
+//! of the automaton, and infers the properties that hold.
+use std::fmt::Display;
+
+use biodivine_lib_bdd::Bdd;
+use itertools::Itertools;
+
+use crate::{HeaderItem, HoaRepresentation, Id, Property};
+
+/// The properties that [`HoaRepresentation::validate_properties`] is able to check, all
+/// other properties are taken for granted.
+pub const CHECKED_PROPERTIES: [Property; 8] = [
+    Property::Deterministic,
+    Property::Complete,
+    Property::UniversalBranching,
+    Property::NoUniversalBranching,
+    Property::StateAcceptance,
+    Property::Colored,
+    Property::Weak,
+    Property::VeryWeak,
+];
+
+/// Describes why a property does not hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The automaton has the given number of initial states (counting each state of an
+    /// initial conjunction), but a deterministic automaton has exactly one.
+    InitialStates(usize),
+    /// The edges with the given positions leaving `state` have overlapping labels.
+    OverlappingEdges {
+        /// The source state of the edges.
+        state: Id,
+        /// The positions of the two edges among the edges leaving `state`.
+        edges: (usize, usize),
+    },
+    /// An edge leaving the given state, or the initial state if `None`, leads to a
+    /// conjunction of states.
+    UniversalBranching(Option<Id>),
+    /// Neither an edge nor the initial state leads to a conjunction of states.
+    NoUniversalBranching,
+    /// The labels of the edges leaving the given state do not cover all valuations.
+    Incomplete(Id),
+    /// The edges leaving the given state belong to different acceptance sets.
+    MixedStateAcceptance(Id),
+    /// The edge with the given position leaving `state` does not belong to exactly one
+    /// acceptance set.
+    NotColored {
+        /// The source state of the edge.
+        state: Id,
+        /// The position of the edge among the edges leaving `state`.
+        edge: usize,
+    },
+    /// Two edges in the strongly connected component of the given state belong to
+    /// different acceptance sets.
+    NotWeak(Id),
+    /// The strongly connected component of the given state has more than one state.
+    NontrivialScc(Id),
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::InitialStates(count) => write!(f, "there are {} initial states", count),
+            Violation::OverlappingEdges {
+                state,
+                edges: (first, second),
+            } => write!(
+                f,
+                "edges {} and {} leaving state {} overlap",
+                first, second, state
+            ),
+            Violation::UniversalBranching(Some(state)) => {
+                write!(f, "an edge leaving state {} branches universally", state)
+            }
+            Violation::UniversalBranching(None) => {
+                write!(f, "the initial state is a conjunction")
+            }
+            Violation::NoUniversalBranching => write!(f, "there is no universal branching"),
+            Violation::Incomplete(state) => {
+                write!(f, "state {} lacks an edge for some valuation", state)
+            }
+            Violation::MixedStateAcceptance(state) => write!(
+                f,
+                "edges leaving state {} belong to different acceptance sets",
+                state
+            ),
+            Violation::NotColored { state, edge } => write!(
+                f,
+                "edge {} leaving state {} does not belong to exactly one acceptance set",
+                edge, state
+            ),
+            Violation::NotWeak(state) => write!(
+                f,
+                "edges in the SCC of state {} belong to different acceptance sets",
+                state
+            ),
+            Violation::NontrivialScc(state) => {
+                write!(f, "the SCC of state {} has more than one state", state)
+            }
+        }
+    }
+}
+
+/// A property that is declared in the header, but does not hold for the automaton.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyViolation {
+    /// The property that does not hold.
+    pub property: Property,
+    /// The reason why it does not hold.
+    pub violation: Violation,
+}
+
+impl Display for PropertyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "property {} does not hold: {}",
+            self.property, self.violation
+        )
+    }
+}
+
+impl std::error::Error for PropertyViolation {}
+
+impl HoaRepresentation {
+    /// Returns the properties that are declared in the header.
+    pub fn properties(&self) -> Vec<Property> {
+        self.header()
+            .iter()
+            .filter_map(|item| match item {
+                HeaderItem::Properties(properties) => Some(properties.iter().copied()),
+                _ => None,
+            })
+            .flatten()
+            .unique()
+            .collect()
+    }
+
+    /// Checks each declared property against the structure of the automaton and returns
+    /// a [`PropertyViolation`] for every one that does not hold. Only the properties in
+    /// [`CHECKED_PROPERTIES`] are checked. If some label can not be interpreted, the
+    /// properties that depend on labels are not checked either.
+    pub fn validate_properties(&self) -> Vec<PropertyViolation> {
+        let checker = Checker::new(self);
+        self.properties()
+            .into_iter()
+            .filter_map(|property| {
+                checker
+                    .check(property)?
+                    .err()
+                    .map(|violation| PropertyViolation {
+                        property,
+                        violation,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns all properties from [`CHECKED_PROPERTIES`] that hold for the automaton.
+    pub fn infer_properties(&self) -> Vec<Property> {
+        let checker = Checker::new(self);
+        CHECKED_PROPERTIES
+            .into_iter()
+            .filter(|property| matches!(checker.check(*property), Some(Ok(()))))
+            .collect()
+    }
+
+    /// Adds a `properties` header item for all properties that [`Self::infer_properties`]
+    /// finds, but that are not declared yet. This way, they are included when the
+    /// automaton is written.
+    pub fn add_inferred_properties(&mut self) {
+        let declared = self.properties();
+        let missing = self
+            .infer_properties()
+            .into_iter()
+            .filter(|property| !declared.contains(property))
+            .collect_vec();
+        if !missing.is_empty() {
+            let position = self
+                .header()
+                .iter()
+                .position(|item| matches!(item, HeaderItem::Properties(_)))
+                .unwrap_or(self.header().len());
+            self.header_mut()
+                .insert(position, HeaderItem::Properties(missing));
+        }
+    }
+}
+
+/// Holds the information about an automaton that is needed to check its properties.
+struct Checker<'a> {
+    aut: &'a HoaRepresentation,
+    size: usize,
+    /// The labels of all edges, or `None` if some label could not be interpreted.
+    labels: Option<Vec<Vec<Bdd>>>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(aut: &'a HoaRepresentation) -> Self {
+        let referenced = aut
+            .body()
+            .iter()
+            .flat_map(|state| {
+                std::iter::once(state.id()).chain(
+                    state
+                        .edges()
+                        .iter()
+                        .flat_map(|edge| edge.state_conjunction().iter().copied()),
+                )
+            })
+            .chain(aut.start().into_iter().flat_map(|c| c.iter().copied()))
+            .map(|id| id as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let size = aut.num_states().unwrap_or(0).max(referenced);
+
+        let num_aps = aut.num_aps();
+        let labels = aut
+            .body()
+            .iter()
+            .map(|state| {
+                state
+                    .edges()
+                    .iter()
+                    .map(|edge| (**edge.label()).clone().try_into_hoa_expression(num_aps))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .ok();
+        Self { aut, size, labels }
+    }
+
+    /// Checks whether `property` holds, returns `None` if that can not be determined.
+    fn check(&self, property: Property) -> Option<Result<(), Violation>> {
+        Some(match property {
+            Property::Deterministic => self.deterministic()?,
+            Property::Complete => self.complete()?,
+            Property::UniversalBranching => match self.universal_branching() {
+                Err(_) => Ok(()),
+                Ok(()) => Err(Violation::NoUniversalBranching),
+            },
+            Property::NoUniversalBranching => self.universal_branching(),
+            Property::StateAcceptance => self.state_acceptance(),
+            Property::Colored => self.colored(),
+            Property::Weak => self.weak(false),
+            Property::VeryWeak => self.weak(true),
+            _ => return None,
+        })
+    }
+
+    fn universal_branching(&self) -> Result<(), Violation> {
+        if self.aut.start().iter().any(|start| start.len() > 1) {
+            return Err(Violation::UniversalBranching(None));
+        }
+        for state in self.aut.body().iter() {
+            if state
+                .edges()
+                .iter()
+                .any(|edge| edge.state_conjunction().len() > 1)
+            {
+                return Err(Violation::UniversalBranching(Some(state.id())));
+            }
+        }
+        Ok(())
+    }
+
+    fn deterministic(&self) -> Option<Result<(), Violation>> {
+        let labels = self.labels.as_ref()?;
+        let initial = self.aut.start().iter().map(|start| start.len()).sum();
+        if initial != 1 {
+            return Some(Err(Violation::InitialStates(initial)));
+        }
+        if let Err(violation) = self.universal_branching() {
+            return Some(Err(violation));
+        }
+        for (state, labels) in self.aut.body().iter().zip(labels) {
+            for (second, label) in labels.iter().enumerate() {
+                if let Some(first) = labels[..second]
+                    .iter()
+                    .position(|other| !other.and(label).is_false())
+                {
+                    return Some(Err(Violation::OverlappingEdges {
+                        state: state.id(),
+                        edges: (first, second),
+                    }));
+                }
+            }
+        }
+        Some(Ok(()))
+    }
+
+    fn complete(&self) -> Option<Result<(), Violation>> {
+        let labels = self.labels.as_ref()?;
+        if self.aut.start().is_empty() {
+            return Some(Err(Violation::InitialStates(0)));
+        }
+        let mut covered = vec![false; self.size];
+        for (state, labels) in self.aut.body().iter().zip(labels) {
+            let union = labels
+                .iter()
+                .skip(1)
+                .fold(labels.first().cloned(), |acc, l| acc.map(|acc| acc.or(l)));
+            if union.is_some_and(|union| union.is_true()) {
+                covered[state.id() as usize] = true;
+            }
+        }
+        Some(match covered.iter().position(|covered| !covered) {
+            Some(state) => Err(Violation::Incomplete(state as Id)),
+            None => Ok(()),
+        })
+    }
+
+    fn state_acceptance(&self) -> Result<(), Violation> {
+        for state in self.aut.body().iter() {
+            if !state
+                .edges()
+                .iter()
+                .map(|edge| edge.acceptance_signature().iter().sorted().collect_vec())
+                .all_equal()
+            {
+                return Err(Violation::MixedStateAcceptance(state.id()));
+            }
+        }
+        Ok(())
+    }
+
+    fn colored(&self) -> Result<(), Violation> {
+        for state in self.aut.body().iter() {
+            if let Some(edge) = state
+                .edges()
+                .iter()
+                .position(|edge| edge.acceptance_signature().iter().unique().count() != 1)
+            {
+                return Err(Violation::NotColored {
+                    state: state.id(),
+                    edge,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that all edges within an SCC belong to the same acceptance sets and, if
+    /// `very` is set, that every SCC consists of a single state.
+    fn weak(&self, very: bool) -> Result<(), Violation> {
+        let scc = self.sccs();
+        let mut marks: Vec<Option<Vec<Id>>> = vec![None; self.size];
+        for state in self.aut.body().iter() {
+            let source = state.id() as usize;
+            for edge in state.edges() {
+                if !edge
+                    .state_conjunction()
+                    .iter()
+                    .any(|target| scc[*target as usize] == scc[source])
+                {
+                    continue;
+                }
+                let signature = edge
+                    .acceptance_signature()
+                    .iter()
+                    .copied()
+                    .sorted()
+                    .collect();
+                match &marks[scc[source]] {
+                    Some(existing) if *existing != signature => {
+                        return Err(Violation::NotWeak(state.id()));
+                    }
+                    _ => marks[scc[source]] = Some(signature),
+                }
+            }
+        }
+        if very {
+            let mut seen = vec![false; self.size];
+            for state in 0..self.size {
+                if std::mem::replace(&mut seen[scc[state]], true) {
+                    return Err(Violation::NontrivialScc(state as Id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the strongly connected components with Kosaraju's algorithm, the result
+    /// assigns to each state the index of its component.
+    fn sccs(&self) -> Vec<usize> {
+        let mut successors = vec![vec![]; self.size];
+        let mut predecessors = vec![vec![]; self.size];
+        for state in self.aut.body().iter() {
+            for edge in state.edges() {
+                for &target in edge.state_conjunction().iter() {
+                    successors[state.id() as usize].push(target as usize);
+                    predecessors[target as usize].push(state.id() as usize);
+                }
+            }
+        }
+
+        let mut visited = vec![false; self.size];
+        let mut order = Vec::with_capacity(self.size);
+        for root in 0..self.size {
+            if std::mem::replace(&mut visited[root], true) {
+                continue;
+            }
+            let mut stack = vec![(root, 0)];
+            while let Some((state, next)) = stack.pop() {
+                if let Some(&successor) = successors[state].get(next) {
+                    stack.push((state, next + 1));
+                    if !std::mem::replace(&mut visited[successor], true) {
+                        stack.push((successor, 0));
+                    }
+                } else {
+                    order.push(state);
+                }
+            }
+        }
+
+        let mut scc = vec![usize::MAX; self.size];
+        for (index, &root) in order.iter().rev().enumerate() {
+            if scc[root] != usize::MAX {
+                continue;
+            }
+            scc[root] = index;
+            let mut stack = vec![root];
+            while let Some(state) = stack.pop() {
+                for &predecessor in &predecessors[state] {
+                    if scc[predecessor] == usize::MAX {
+                        scc[predecessor] = index;
+                        stack.push(predecessor);
+                    }
+                }
+            }
+        }
+        scc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropertyViolation, Violation};
+    use crate::{HoaRepresentation, Property};
+
+    const AUTOMATON: &str = r#"HOA: v1
+States: 3
+Start: 0
+AP: 1 "a"
+Acceptance: 1 Inf(0)
+properties: deterministic complete state-acc colored very-weak no-univ-branch
+--BODY--
+State: 0
+[0] 1 {0}
+[0] 2
+State: 1
+[t] 1 {0}
+State: 2
+[!0] 2 {0}
+[0] 0 {0}
+--END--
+"#;
+
+    #[test]
+    fn validate_declared_properties() {
+        let aut = HoaRepresentation::try_from(AUTOMATON).unwrap();
+        let violation = |property, violation| PropertyViolation {
+            property,
+            violation,
+        };
+        assert_eq!(
+            aut.validate_properties(),
+            vec![
+                violation(
+                    Property::Deterministic,
+                    Violation::OverlappingEdges {
+                        state: 0,
+                        edges: (0, 1)
+                    }
+                ),
+                violation(Property::Complete, Violation::Incomplete(0)),
+                violation(
+                    Property::StateAcceptance,
+                    Violation::MixedStateAcceptance(0)
+                ),
+                violation(
+                    Property::Colored,
+                    Violation::NotColored { state: 0, edge: 1 }
+                ),
+                violation(Property::VeryWeak, Violation::NotWeak(2)),
+            ]
+        );
+
+        let fixed = AUTOMATON
+            .replace("[0] 2\n", "[!0] 2 {0}\n")
+            .replace("[0] 0 {0}", "[0] 1 {0}");
+        let mut aut = HoaRepresentation::try_from(fixed.as_str()).unwrap();
+        assert_eq!(aut.validate_properties(), vec![]);
+        assert_eq!(
+            aut.infer_properties(),
+            vec![
+                Property::Deterministic,
+                Property::Complete,
+                Property::NoUniversalBranching,
+                Property::StateAcceptance,
+                Property::Colored,
+                Property::Weak,
+                Property::VeryWeak,
+            ]
+        );
+        aut.add_inferred_properties();
+        let written = crate::output::to_hoa(&aut);
+        assert!(written.contains("properties: weak\n"), "{written}");
+    }
+
+    #[test]
+    fn universal_branching_and_sccs() {
+        let hoa = r#"HOA: v1
+States: 2
+Start: 0 & 1
+AP: 0
+Acceptance: 1 Inf(0)
+properties: univ-branch no-univ-branch weak
+--BODY--
+State: 0
+[t] 1 {0}
+State: 1
+[t] 0 {0}
+--END--
+"#;
+        let aut = HoaRepresentation::try_from(hoa).unwrap();
+        assert_eq!(
+            aut.validate_properties()
+                .into_iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>(),
+            vec!["property no-univ-branch does not hold: the initial state is a conjunction"]
+        );
+        assert!(aut.infer_properties().contains(&Property::Weak));
+        assert!(!aut.infer_properties().contains(&Property::VeryWeak));
+    }
+}
@@ -0,0 +1,200 @@
+//! Incremental reading of HOA automata from a [`BufRead`].
+use std::io::BufRead;
+use std::ops::Range;
+
+use crate::lexer::{Marker, MarkerScanner};
+use crate::{FromHoaError, HoaRepresentation};
+
+/// Reads automata in the HOA format from a [`BufRead`] and yields them one at a time.
+///
+/// Input is consumed line by line into an internal buffer, and as soon as the buffer
+/// contains an `--END--`, the automaton in front of it is lexed and parsed. Only the
+/// newly read part of the buffer is scanned for the end of an automaton, where quoted
+/// strings and comments are skipped, so they may contain markers. The buffer
+/// is compacted once the consumed part dominates it. So the memory that is used is
+/// bounded by (roughly twice) the size of the largest automaton and the time is linear
+/// in the size of the input, regardless of how many automata it contains.
+///
+/// Automata that are terminated by `--ABORT--` are skipped, text after the last
+/// `--END--` is ignored. An automaton that can not be parsed results in an error, after
+/// which reading continues with the next automaton. Once reading from the underlying
+/// reader fails, [`FromHoaError::Io`] is returned and the iterator ends.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HoaReader<R> {
+    read: R,
+    buf: String,
+    /// The position in `buf` at which the next automaton starts.
+    start: usize,
+    /// The position in `buf` from which on it has not been scanned for markers.
+    scanned: usize,
+    /// Remembers whether scanning stopped within a string or comment.
+    scanner: MarkerScanner,
+    /// The number of bytes that were removed from the front of `buf`.
+    offset: usize,
+    /// The byte range of the most recently yielded automaton.
+    last: Range<usize>,
+    failed: bool,
+}
+
+impl<R> HoaReader<R> {
+    /// Creates a new reader, which reads automata from `read`.
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            buf: String::new(),
+            start: 0,
+            scanned: 0,
+            scanner: MarkerScanner::default(),
+            offset: 0,
+            last: 0..0,
+            failed: false,
+        }
+    }
+
+    /// Returns the byte range of the input that the most recently yielded automaton (or
+    /// parsing error) originates from. The range starts at the first non-whitespace
+    /// character and ends with `--END--`.
+    pub fn last_range(&self) -> Range<usize> {
+        self.last.clone()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Looks for the first marker from `self.scanned` on, returns its position and kind.
+    /// If there is none, `self.scanned` is advanced as far as possible.
+    fn find_marker(&mut self) -> Option<(usize, Marker)> {
+        match self.scanner.find(&self.buf, self.scanned) {
+            Ok(found) => Some(found),
+            Err(resume) => {
+                self.scanned = resume;
+                None
+            }
+        }
+    }
+
+    /// Drops the part of the buffer that was consumed already, provided it is larger than
+    /// the rest. This ensures that every byte is moved at most once on average.
+    fn compact(&mut self) {
+        if self.start > 0 && 2 * self.start >= self.buf.len() {
+            self.buf.drain(..self.start);
+            self.offset += self.start;
+            self.scanned -= self.start;
+            self.start = 0;
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for HoaReader<R> {
+    type Item = Result<HoaRepresentation, FromHoaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            match self.find_marker() {
+                Some((abort, Marker::Abort)) => {
+                    tracing::trace!("skipping aborted automaton");
+                    self.start = abort + Marker::Abort.len();
+                    self.scanned = self.start;
+                }
+                Some((end, Marker::End)) => {
+                    let end = end + Marker::End.len();
+                    let text = &self.buf[self.start..end];
+                    let begin = self.start + (text.len() - text.trim_start().len());
+                    self.last = self.offset + begin..self.offset + end;
+                    let aut = HoaRepresentation::try_from(&self.buf[begin..end]);
+                    self.start = end;
+                    self.scanned = end;
+                    return Some(aut);
+                }
+                None => {
+                    self.compact();
+                    match self.read.read_line(&mut self.buf) {
+                        Ok(0) => {
+                            if !self.buf[self.start..].trim().is_empty() {
+                                tracing::trace!("input ended within an automaton, discarding it");
+                            }
+                            return None;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            self.failed = true;
+                            return Some(Err(FromHoaError::Io(e.to_string())));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HoaReader;
+    use crate::{FromHoaError, HeaderItem, HoaRepresentation};
+
+    const AUTOMATON: &str = "HOA: v1\nStates: 1\nStart: 0\nAcceptance: 1 Inf(0)\nAP: 1 \"a\"\n\
+        --BODY--\nState: 0\n[0] 0 {0}\n[!0] 0\n--END--\n";
+
+    #[test]
+    fn read_automata_incrementally() {
+        let aborted = "HOA: v1\nStates: 1\n--BODY--\nState: 0\n--ABORT--\n";
+        let broken = AUTOMATON.replace("Start: 0", "Start: x");
+        // the last automaton shares its line with the previous one
+        let input = format!(
+            "{AUTOMATON}{aborted}\n  {broken}{}{}",
+            AUTOMATON.trim_end(),
+            AUTOMATON
+        );
+        let mut reader = HoaReader::new(input.as_bytes());
+
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(first.num_states(), Some(1));
+        assert_eq!(reader.last_range(), 0..AUTOMATON.len() - 1);
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(FromHoaError::ParserError(_)))
+        ));
+        let start = AUTOMATON.len() + aborted.len() + 3;
+        assert_eq!(reader.last_range(), start..start + broken.len() - 1);
+        assert_eq!(&input[reader.last_range()], broken.trim_end());
+
+        assert_eq!(reader.next(), Some(Ok(first.clone())));
+        assert_eq!(reader.next(), Some(Ok(first)));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn markers_in_strings_and_comments_are_skipped() {
+        // the name spans several lines, so the reader stops scanning within it
+        let name = "\\\" --END--\n--ABORT--";
+        let named = AUTOMATON.replace(
+            "HOA: v1\n",
+            "HOA: v1\nname: \"\\\\\\\" --END--\n--ABORT--\"\n/* --END--\n*/\n",
+        );
+        let input = format!("{named}{AUTOMATON}");
+        let mut reader = HoaReader::new(input.as_bytes());
+
+        let first = reader.next().unwrap().unwrap();
+        let found = first.header().iter().find_map(|item| match item {
+            HeaderItem::Name(name) => Some(name.clone()),
+            _ => None,
+        });
+        assert_eq!(found.as_deref(), Some(name));
+        assert_eq!(reader.last_range(), 0..named.len() - 1);
+        assert!(reader.next().unwrap().is_ok());
+        assert_eq!(reader.next(), None);
+
+        let ranges: Vec<_> = crate::split_hoa_automata(&input).collect();
+        assert_eq!(
+            ranges,
+            vec![0..named.len() - 1, named.len()..input.len() - 1]
+        );
+        assert!(HoaRepresentation::try_from(&input[ranges[0].clone()]).is_ok());
+    }
+}
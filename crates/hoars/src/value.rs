@@ -20,9 +20,12 @@ pub fn boolean() -> impl Parser<Token, bool, Error = Simple<Token>> + Clone {
 }
 
 pub fn integer() -> impl Parser<Token, Id, Error = Simple<Token>> + Clone {
-    select! {
-        Token::Int(n) => n.parse().unwrap(),
-    }
+    filter_map(|span, token| match token {
+        Token::Int(n) => n
+            .parse()
+            .map_err(|_| Simple::custom(span, format!("integer {n} is out of range"))),
+        other => Err(Simple::expected_input_found(span, None, Some(other))),
+    })
 }
 
 pub fn text() -> impl Parser<Token, String, Error = Simple<Token>> + Clone {
@@ -54,18 +57,21 @@ pub fn acceptance_signature() -> impl Parser<Token, AcceptanceSignature, Error =
 }
 
 pub fn acceptance_info() -> impl Parser<Token, AcceptanceInfo, Error = Simple<Token>> {
-    select! {
-        Token::Identifier(ident) => AcceptanceInfo::Identifier(ident),
-        Token::Int(n) => AcceptanceInfo::Int(n.parse().unwrap())
-    }
+    identifier()
+        .map(AcceptanceInfo::Identifier)
+        .or(integer().map(AcceptanceInfo::Int))
 }
 
 pub fn label_expression() -> impl Parser<Token, AbstractLabelExpression, Error = Simple<Token>> {
     recursive(|label_expression| {
         let value = boolean()
             .map(AbstractLabelExpression::Boolean)
-            .or(integer().map(|i| AbstractLabelExpression::Integer(i as u16)));
-        // .or(alias_name().map(|aname| LabelExpression::Alias(AliasName(aname))));
+            .or(integer().try_map(|i, span| {
+                u16::try_from(i)
+                    .map(AbstractLabelExpression::Integer)
+                    .map_err(|_| Simple::custom(span, format!("proposition {i} is out of range")))
+            }))
+            .or(alias_name().map(AbstractLabelExpression::Alias));
 
         let atom = value
             .or(label_expression.delimited_by(just(Token::Paren('(')), just(Token::Paren(')'))));